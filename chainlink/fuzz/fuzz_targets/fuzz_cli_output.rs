@@ -10,7 +10,7 @@ use arbitrary::Arbitrary;
 use libfuzzer_sys::fuzz_target;
 use tempfile::tempdir;
 
-use chainlink::db::Database;
+use chainlink::db::{Database, SearchOptions};
 
 #[derive(Arbitrary, Debug)]
 struct CliOutputInput {
@@ -65,15 +65,15 @@ fuzz_target!(|input: CliOutputInput| {
     if !input.title.is_empty() {
         let search_term: String = input.title.chars().take(10).collect();
         if !search_term.is_empty() {
-            let _ = db.search_issues(&search_term);
+            let _ = db.search_issues(&search_term, &SearchOptions::default());
         }
     }
 
     // Test blocked/ready lists
     if created_ids.len() >= 2 {
         let _ = db.add_dependency(created_ids[0], created_ids[1]);
-        let _ = db.list_blocked_issues();
-        let _ = db.list_ready_issues();
+        let _ = db.list_blocked_with_meta();
+        let _ = db.list_ready_issues(false);
     }
 
     // Test comments with Unicode
@@ -80,17 +80,17 @@ fuzz_target!(|input: DependencyGraphInput| {
             }
             DependencyOp::CheckReady => {
                 // Should never panic or hang
-                let _ = db.list_ready_issues();
+                let _ = db.list_ready_issues(false);
             }
             DependencyOp::CheckBlocked => {
                 // Should never panic or hang
-                let _ = db.list_blocked_issues();
+                let _ = db.list_blocked_with_meta();
             }
         }
     }
 
     // Final verification - these should never panic
-    let _ = db.list_ready_issues();
-    let _ = db.list_blocked_issues();
+    let _ = db.list_ready_issues(false);
+    let _ = db.list_blocked_with_meta();
     let _ = db.list_issues(None, None, None);
 });
@@ -0,0 +1,154 @@
+#![no_main]
+
+use std::collections::{HashMap, HashSet};
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use tempfile::tempdir;
+
+use chainlink::db::Database;
+
+/// Issues created up front; every op picks from these by index.
+const ISSUE_COUNT: usize = 6;
+
+#[derive(Arbitrary, Debug, Clone)]
+enum DepsOp {
+    Block { blocked_idx: u8, blocker_idx: u8 },
+    Duplicate { dup_idx: u8, original_idx: u8 },
+    Unblock { blocked_idx: u8, blocker_idx: u8 },
+    Close { idx: u8 },
+    Reopen { idx: u8 },
+    Delete { idx: u8 },
+    Blockers { idx: u8 },
+    Blocking { idx: u8 },
+    Counts,
+}
+
+#[derive(Arbitrary, Debug)]
+struct DepsInput {
+    ops: Vec<DepsOp>,
+}
+
+/// Panic if the "blocks" edges contain a self-edge or a cycle.
+fn assert_acyclic(edges: &[(i64, i64)]) {
+    let mut blocking: HashMap<i64, Vec<i64>> = HashMap::new();
+    for &(blocked, blocker) in edges {
+        assert_ne!(blocked, blocker, "self-dependency on #{}", blocked);
+        blocking.entry(blocker).or_default().push(blocked);
+    }
+
+    // Iterative three-colour DFS: a grey node reached again is a back edge
+    let mut done: HashSet<i64> = HashSet::new();
+    for &start in blocking.keys() {
+        if done.contains(&start) {
+            continue;
+        }
+        let mut on_path: HashSet<i64> = HashSet::new();
+        let mut stack: Vec<(i64, usize)> = vec![(start, 0)];
+        on_path.insert(start);
+        while let Some((node, next)) = stack.pop() {
+            let children = blocking.get(&node).map(Vec::as_slice).unwrap_or(&[]);
+            if next < children.len() {
+                stack.push((node, next + 1));
+                let child = children[next];
+                assert!(!on_path.contains(&child), "cycle through #{}", child);
+                if !done.contains(&child) {
+                    on_path.insert(child);
+                    stack.push((child, 0));
+                }
+            } else {
+                on_path.remove(&node);
+                done.insert(node);
+            }
+        }
+    }
+}
+
+fuzz_target!(|input: DepsInput| {
+    let dir = match tempdir() {
+        Ok(d) => d,
+        Err(_) => return,
+    };
+    let db_path = dir.path().join("issues.db");
+
+    let db = match Database::open(&db_path) {
+        Ok(d) => d,
+        Err(_) => return,
+    };
+
+    let mut ids = Vec::new();
+    for i in 0..ISSUE_COUNT {
+        match db.create_issue(&format!("Issue {}", i), None, "medium") {
+            Ok(id) => ids.push(id),
+            Err(_) => return,
+        }
+    }
+    let pick = |idx: u8| ids[idx as usize % ids.len()];
+
+    // Limit operations to prevent timeout
+    for op in input.ops.iter().take(200) {
+        let before = db.list_dependencies().expect("list dependencies");
+
+        match op {
+            DepsOp::Block {
+                blocked_idx,
+                blocker_idx,
+            } => {
+                let (blocked, blocker) = (pick(*blocked_idx), pick(*blocker_idx));
+                if db.add_dependency(blocked, blocker).is_err() {
+                    // A rejected edge (self-block, cycle, missing issue) must change nothing
+                    assert_eq!(db.list_dependencies().unwrap(), before);
+                }
+            }
+            DepsOp::Duplicate {
+                dup_idx,
+                original_idx,
+            } => {
+                let _ =
+                    db.add_dependency_of_kind(pick(*dup_idx), pick(*original_idx), "duplicates");
+            }
+            DepsOp::Unblock {
+                blocked_idx,
+                blocker_idx,
+            } => {
+                let (blocked, blocker) = (pick(*blocked_idx), pick(*blocker_idx));
+                let removed = db.remove_dependency(blocked, blocker).unwrap_or(false);
+                assert_eq!(removed, before.contains(&(blocked, blocker)));
+            }
+            DepsOp::Close { idx } => {
+                let _ = db.close_issue(pick(*idx));
+            }
+            DepsOp::Reopen { idx } => {
+                let _ = db.reopen_issue(pick(*idx));
+            }
+            DepsOp::Delete { idx } => {
+                let _ = db.delete_issue(pick(*idx));
+            }
+            DepsOp::Blockers { idx } => {
+                let id = pick(*idx);
+                if let Ok(blockers) = db.get_blockers(id) {
+                    for blocker in blockers {
+                        assert!(before.contains(&(id, blocker)));
+                    }
+                }
+            }
+            DepsOp::Blocking { idx } => {
+                let id = pick(*idx);
+                if let Ok(blocked) = db.get_blocking(id) {
+                    for b in blocked {
+                        assert!(before.contains(&(b, id)));
+                    }
+                }
+            }
+            DepsOp::Counts => {
+                let _ = db.dependency_counts();
+            }
+        }
+
+        assert_acyclic(&db.list_dependencies().expect("list dependencies"));
+    }
+
+    // Readiness queries must agree with the final graph and never panic
+    let _ = db.list_ready_issues(false);
+    let _ = db.list_blocked_with_meta();
+});
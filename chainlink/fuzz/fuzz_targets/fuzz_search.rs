@@ -3,7 +3,7 @@
 use libfuzzer_sys::fuzz_target;
 use tempfile::tempdir;
 
-use chainlink::db::Database;
+use chainlink::db::{Database, SearchOptions};
 
 fuzz_target!(|query: String| {
     let dir = match tempdir() {
@@ -23,5 +23,12 @@ fuzz_target!(|query: String| {
     let _ = db.create_issue("Third issue", Some("More content"), "low");
 
     // Fuzz search - should never panic, even with malicious SQL
-    let _ = db.search_issues(&query);
+    let _ = db.search_issues(&query, &SearchOptions::default());
+    let _ = db.search_issues(
+        &query,
+        &SearchOptions {
+            prefix: true,
+            ..Default::default()
+        },
+    );
 });
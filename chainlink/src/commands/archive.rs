@@ -1,12 +1,10 @@
 use anyhow::{bail, Result};
 
 use crate::db::Database;
+use crate::output::Reporter;
 
-pub fn archive(db: &Database, id: i64) -> Result<()> {
-    let issue = match db.get_issue(id)? {
-        Some(i) => i,
-        None => bail!("Issue #{} not found", id),
-    };
+pub fn archive(db: &Database, out: &Reporter, id: i64) -> Result<()> {
+    let issue = db.require_issue(id)?;
 
     if issue.status != "closed" {
         bail!(
@@ -17,17 +15,17 @@ pub fn archive(db: &Database, id: i64) -> Result<()> {
     }
 
     if db.archive_issue(id)? {
-        println!("Archived issue #{}", id);
+        out.info(&format!("Archived issue #{}", id));
     } else {
-        println!("Issue #{} could not be archived", id);
+        out.info(&format!("Issue #{} could not be archived", id));
     }
 
     Ok(())
 }
 
-pub fn unarchive(db: &Database, id: i64) -> Result<()> {
+pub fn unarchive(db: &Database, out: &Reporter, id: i64) -> Result<()> {
     if db.unarchive_issue(id)? {
-        println!("Unarchived issue #{} (now closed)", id);
+        out.info(&format!("Unarchived issue #{} (now closed)", id));
     } else {
         bail!("Issue #{} not found or not archived", id);
     }
@@ -58,18 +56,18 @@ pub fn list(db: &Database) -> Result<()> {
     Ok(())
 }
 
-pub fn archive_older(db: &Database, days: i64) -> Result<()> {
+pub fn archive_older(db: &Database, out: &Reporter, days: i64) -> Result<()> {
     let count = db.archive_older_than(days)?;
     if count > 0 {
-        println!(
+        out.info(&format!(
             "Archived {} issue(s) closed more than {} days ago",
             count, days
-        );
+        ));
     } else {
-        println!(
+        out.info(&format!(
             "No issues to archive (none closed more than {} days ago)",
             days
-        );
+        ));
     }
 
     Ok(())
@@ -94,7 +92,7 @@ mod tests {
         let id = db.create_issue("Test issue", None, "medium").unwrap();
         db.close_issue(id).unwrap();
 
-        archive(&db, id).unwrap();
+        archive(&db, &Reporter::default(), id).unwrap();
         let archived = db.list_archived_issues().unwrap();
         assert!(
             archived.iter().any(|i| i.id == id),
@@ -107,7 +105,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = archive(&db, id);
+        let result = archive(&db, &Reporter::default(), id);
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -119,7 +117,7 @@ mod tests {
     fn test_archive_nonexistent_fails() {
         let (db, _dir) = setup_test_db();
 
-        let result = archive(&db, 99999);
+        let result = archive(&db, &Reporter::default(), 99999);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -129,9 +127,9 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
         db.close_issue(id).unwrap();
-        archive(&db, id).unwrap();
+        archive(&db, &Reporter::default(), id).unwrap();
 
-        unarchive(&db, id).unwrap();
+        unarchive(&db, &Reporter::default(), id).unwrap();
         let archived = db.list_archived_issues().unwrap();
         assert!(
             !archived.iter().any(|i| i.id == id),
@@ -149,7 +147,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = unarchive(&db, id);
+        let result = unarchive(&db, &Reporter::default(), id);
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -171,7 +169,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
         db.close_issue(id).unwrap();
-        archive(&db, id).unwrap();
+        archive(&db, &Reporter::default(), id).unwrap();
 
         list(&db).unwrap();
         let archived = db.list_archived_issues().unwrap();
@@ -183,7 +181,7 @@ mod tests {
     fn test_archive_older_none() {
         let (db, _dir) = setup_test_db();
 
-        archive_older(&db, 30).unwrap();
+        archive_older(&db, &Reporter::default(), 30).unwrap();
         let archived = db.list_archived_issues().unwrap();
         assert!(
             archived.is_empty(),
@@ -197,11 +195,11 @@ mod tests {
         let id = db.create_issue("Test issue", None, "medium").unwrap();
         db.close_issue(id).unwrap();
 
-        archive(&db, id).unwrap();
+        archive(&db, &Reporter::default(), id).unwrap();
         let archived = db.list_archived_issues().unwrap();
         assert!(archived.iter().any(|i| i.id == id));
 
-        unarchive(&db, id).unwrap();
+        unarchive(&db, &Reporter::default(), id).unwrap();
         let archived = db.list_archived_issues().unwrap();
         assert!(!archived.iter().any(|i| i.id == id));
     }
@@ -211,7 +209,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
         db.close_issue(id).unwrap();
-        archive(&db, id).unwrap();
+        archive(&db, &Reporter::default(), id).unwrap();
 
         let open_issues = db.list_issues(Some("open"), None, None).unwrap();
         let closed_issues = db.list_issues(Some("closed"), None, None).unwrap();
@@ -221,21 +219,21 @@ mod tests {
 
     proptest! {
         #[test]
-        fn prop_archive_requires_closed(title in "[a-zA-Z0-9 ]{1,30}") {
+        fn prop_archive_requires_closed(title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,29}") {
             let (db, _dir) = setup_test_db();
             let id = db.create_issue(&title, None, "medium").unwrap();
 
-            let result = archive(&db, id);
+            let result = archive(&db, &Reporter::default(), id);
             prop_assert!(result.is_err());
         }
 
         #[test]
-        fn prop_archive_closed_succeeds(title in "[a-zA-Z0-9 ]{1,30}") {
+        fn prop_archive_closed_succeeds(title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,29}") {
             let (db, _dir) = setup_test_db();
             let id = db.create_issue(&title, None, "medium").unwrap();
             db.close_issue(id).unwrap();
 
-            archive(&db, id).unwrap();
+            archive(&db, &Reporter::default(), id).unwrap();
             let archived = db.list_archived_issues().unwrap();
             prop_assert!(archived.iter().any(|i| i.id == id));
         }
@@ -0,0 +1,76 @@
+use anyhow::{bail, Result};
+
+use crate::db::{Database, UNASSIGNED};
+use crate::output::Reporter;
+
+pub fn run(db: &Database, out: &Reporter, id: i64, assignee: Option<&str>) -> Result<()> {
+    let assignee = assignee.map(str::trim).filter(|a| !a.is_empty());
+    if assignee == Some(UNASSIGNED) {
+        bail!(
+            "'{}' is reserved for filtering unassigned issues. Omit the name to unassign.",
+            UNASSIGNED
+        );
+    }
+
+    if !db.set_assignee(id, assignee)? {
+        bail!("Issue #{} not found", id);
+    }
+
+    match assignee {
+        Some(name) => out.info(&format!("Assigned issue #{} to {}", id, name)),
+        None => out.info(&format!("Unassigned issue #{}", id)),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_assign_and_unassign() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        run(&db, &Reporter::default(), id, Some("alice")).unwrap();
+        assert_eq!(db.get_assignee(id).unwrap(), Some("alice".to_string()));
+
+        run(&db, &Reporter::default(), id, None).unwrap();
+        assert_eq!(db.get_assignee(id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_assign_empty_name_unassigns() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+        db.set_assignee(id, Some("alice")).unwrap();
+
+        run(&db, &Reporter::default(), id, Some("")).unwrap();
+        assert_eq!(db.get_assignee(id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_assign_reserved_name_rejected() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        assert!(run(&db, &Reporter::default(), id, Some("none")).is_err());
+    }
+
+    #[test]
+    fn test_assign_nonexistent_issue() {
+        let (db, _dir) = setup_test_db();
+        let result = run(&db, &Reporter::default(), 99999, Some("alice"));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+}
@@ -0,0 +1,80 @@
+use anyhow::Result;
+
+use crate::db::Database;
+use crate::utils::{pad, sanitize_display, truncate};
+
+/// List the immediate subissues of `id`, one level deep.
+pub fn render(db: &Database, id: i64) -> Result<String> {
+    db.require_issue(id)?;
+    let children = db.get_subissues(id)?;
+
+    if children.is_empty() {
+        return Ok(format!("Issue #{} has no subissues.\n", id));
+    }
+
+    let scale = db.priority_scale()?;
+    let mut out = format!("Subissues of #{}:\n", id);
+    for child in children {
+        out.push_str(&format!(
+            "  #{:<4} {:10} {} {}\n",
+            child.id,
+            format!("[{}]", child.status),
+            pad(&truncate(&sanitize_display(&child.title), 40), 40),
+            scale.display_padded(&child.priority, scale.column_width(), false)
+        ));
+    }
+    Ok(out)
+}
+
+pub fn run(db: &Database, id: i64) -> Result<()> {
+    print!("{}", render(db, id)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_render_direct_children_only() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let a = db.create_subissue(epic, "Child A", None, "low").unwrap();
+        let b = db
+            .create_subissue(epic, "Child B", None, "critical")
+            .unwrap();
+        db.create_subissue(a, "Grandchild", None, "medium").unwrap();
+        db.close_issue(b).unwrap();
+
+        let out = render(&db, epic).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], format!("Subissues of #{}:", epic));
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with(&format!("  #{:<4} [open]     Child A", a)));
+        assert!(lines[1].trim_end().ends_with("low"));
+        assert!(lines[2].starts_with(&format!("  #{:<4} [closed]   Child B", b)));
+        assert!(lines[2].trim_end().ends_with("critical"));
+        assert!(!out.contains("Grandchild"));
+    }
+
+    #[test]
+    fn test_render_no_children_or_missing_issue() {
+        let (db, _dir) = setup_test_db();
+        let lone = db.create_issue("Lone", None, "medium").unwrap();
+
+        assert_eq!(
+            render(&db, lone).unwrap(),
+            format!("Issue #{} has no subissues.\n", lone)
+        );
+        assert!(render(&db, 999).is_err());
+        run(&db, lone).unwrap();
+    }
+}
@@ -0,0 +1,46 @@
+use anyhow::Result;
+
+use crate::db::{Database, IssueFilter};
+
+/// The number of matching issues and a newline, nothing else, so scripts can
+/// capture it directly.
+pub fn render(db: &Database, filter: &IssueFilter<'_>) -> Result<String> {
+    Ok(format!("{}\n", db.count_issues(filter)?))
+}
+
+pub fn run(db: &Database, filter: &IssueFilter<'_>) -> Result<()> {
+    print!("{}", render(db, filter)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_prints_only_the_count() {
+        let (db, _dir) = setup_test_db();
+        let filter = IssueFilter {
+            status: Some("open"),
+            priority: Some("high"),
+            ..Default::default()
+        };
+        assert_eq!(render(&db, &filter).unwrap(), "0\n");
+
+        db.create_issue("Urgent", None, "high").unwrap();
+        db.create_issue("Also urgent", None, "high").unwrap();
+        db.create_issue("Later", None, "low").unwrap();
+        let done = db.create_issue("Done", None, "high").unwrap();
+        db.close_issue(done).unwrap();
+
+        assert_eq!(render(&db, &filter).unwrap(), "2\n");
+    }
+}
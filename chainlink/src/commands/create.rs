@@ -1,9 +1,11 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::db::Database;
 
-const VALID_PRIORITIES: [&str; 4] = ["low", "medium", "high", "critical"];
-
 /// Built-in issue templates
 pub struct Template {
     pub name: &'static str,
@@ -67,22 +69,85 @@ pub fn list_templates() -> Vec<&'static str> {
     TEMPLATES.iter().map(|t| t.name).collect()
 }
 
-pub fn validate_priority(priority: &str) -> bool {
-    VALID_PRIORITIES.contains(&priority)
+/// Read an issue description from a file. Trailing whitespace is dropped, and a
+/// blank file means no description.
+pub fn description_from_file(path: &Path) -> Result<Option<String>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read description file {}", path.display()))?;
+    Ok(non_blank(text))
+}
+
+/// Let the user write a description in `$VISUAL` or `$EDITOR` (vi if neither is
+/// set). Saving an empty file means no description.
+pub fn description_from_editor() -> Result<Option<String>> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    edit_description(&editor)
+}
+
+/// Run `editor` (a program plus optional arguments) on a fresh temp file and
+/// return what was saved.
+fn edit_description(editor: &str) -> Result<Option<String>> {
+    // Unique per call, so concurrent edits (or tests) never share a file
+    static EDITS: AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "chainlink-description-{}-{}.md",
+        std::process::id(),
+        EDITS.fetch_add(1, Ordering::Relaxed)
+    ));
+    // create_new refuses an existing path, so a planted file or symlink is never followed
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(&path).with_context(|| {
+        format!(
+            "Failed to create temp file {} for the editor",
+            path.display()
+        )
+    })?;
+
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = Command::new(program).args(words).arg(&path).status();
+    let text = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    let status = status.with_context(|| format!("Failed to launch editor '{}'", editor))?;
+    if !status.success() {
+        bail!("Editor '{}' exited with {}", editor, status);
+    }
+    Ok(non_blank(
+        text.context("Failed to read the edited description")?,
+    ))
+}
+
+fn non_blank(text: String) -> Option<String> {
+    let text = text.trim_end();
+    (!text.trim_start().is_empty()).then(|| text.to_string())
 }
 
 /// Options shared by create and subissue commands.
 pub struct CreateOpts<'a> {
     pub labels: &'a [String],
+    /// Existing issues that block the new one; any bad id rolls back the create
+    pub blocked_by: &'a [i64],
     pub work: bool,
     pub quiet: bool,
+    /// Reuse an open issue with the same normalized title instead of creating one
+    pub unique: bool,
 }
 
+/// Create an issue. Without an explicit `priority` the template's priority is
+/// used, falling back to the database's default priority.
 pub fn run(
     db: &Database,
     title: &str,
     description: Option<&str>,
-    priority: &str,
+    priority: Option<&str>,
     template: Option<&str>,
     opts: &CreateOpts<'_>,
 ) -> Result<()> {
@@ -97,11 +162,7 @@ pub fn run(
         })?;
 
         // Template priority is default, user can override
-        let priority = if priority != "medium" {
-            priority
-        } else {
-            tmpl.priority
-        };
+        let priority = priority.unwrap_or(tmpl.priority);
 
         // Combine template description prefix with user description
         let desc = match (tmpl.description_prefix, description) {
@@ -113,31 +174,73 @@ pub fn run(
         (priority.to_string(), desc, Some(tmpl.label))
     } else {
         (
-            priority.to_string(),
+            priority.unwrap_or(db.default_priority()).to_string(),
             description.map(|s| s.to_string()),
             None,
         )
     };
 
-    if !validate_priority(&final_priority) {
-        bail!(
-            "Invalid priority '{}'. Must be one of: {}",
-            final_priority,
-            VALID_PRIORITIES.join(", ")
-        );
+    db.check_priority(&final_priority)?;
+
+    if opts.unique {
+        if let Some(id) = db.find_issue_by_title(title, None)? {
+            // The labels and blockers still apply; the existing text is kept
+            db.transaction(|| -> Result<()> {
+                for lbl in template_label
+                    .iter()
+                    .copied()
+                    .chain(opts.labels.iter().map(String::as_str))
+                {
+                    db.add_label(id, lbl)?;
+                }
+                for &blocker in opts.blocked_by {
+                    db.require_issue(blocker)?;
+                    db.add_dependency(id, blocker)?;
+                }
+                Ok(())
+            })?;
+            let existing = db.require_issue(id)?;
+            if final_description.is_some() && final_description != existing.description {
+                eprintln!(
+                    "Warning: kept the existing description of #{}; use 'chainlink update' to change it",
+                    id
+                );
+            }
+            if opts.quiet {
+                println!("{}", id);
+            } else {
+                println!("Issue #{} already exists", id);
+                if !opts.blocked_by.is_empty() {
+                    let blockers: Vec<String> =
+                        opts.blocked_by.iter().map(|b| format!("#{}", b)).collect();
+                    println!("  Blocked by: {}", blockers.join(", "));
+                }
+            }
+            return start_work(db, id, title, opts);
+        }
     }
 
-    let id = db.create_issue(title, final_description.as_deref(), &final_priority)?;
+    // Create, label and link in one transaction so a bad blocker leaves nothing behind
+    let id = db.transaction(|| -> Result<i64> {
+        let id = db.create_issue(title, final_description.as_deref(), &final_priority)?;
 
-    // Auto-add label from template
-    if let Some(lbl) = template_label {
-        db.add_label(id, lbl)?;
-    }
+        // Auto-add label from template
+        if let Some(lbl) = template_label {
+            db.add_label(id, lbl)?;
+        }
 
-    // Add user-specified labels
-    for lbl in opts.labels {
-        db.add_label(id, lbl)?;
-    }
+        // Add user-specified labels
+        for lbl in opts.labels {
+            db.add_label(id, lbl)?;
+        }
+
+        for &blocker in opts.blocked_by {
+            db.require_issue(blocker)?;
+            db.add_dependency(id, blocker)?;
+        }
+
+        Ok(id)
+    })?;
 
     if opts.quiet {
         println!("{}", id);
@@ -146,21 +249,13 @@ pub fn run(
         if let Some(tmpl) = template {
             println!("  Applied template: {}", tmpl);
         }
-    }
-
-    // Set as active session work item
-    if opts.work {
-        if let Ok(Some(session)) = db.get_current_session() {
-            db.set_session_issue(session.id, id)?;
-            if !opts.quiet {
-                println!("Now working on: #{} {}", id, title);
-            }
-        } else if !opts.quiet {
-            eprintln!("Warning: --work specified but no active session");
+        if !opts.blocked_by.is_empty() {
+            let blockers: Vec<String> = opts.blocked_by.iter().map(|b| format!("#{}", b)).collect();
+            println!("  Blocked by: {}", blockers.join(", "));
         }
     }
 
-    Ok(())
+    start_work(db, id, title, opts)
 }
 
 pub fn run_subissue(
@@ -171,19 +266,7 @@ pub fn run_subissue(
     priority: &str,
     opts: &CreateOpts<'_>,
 ) -> Result<()> {
-    if !validate_priority(priority) {
-        bail!(
-            "Invalid priority '{}'. Must be one of: {}",
-            priority,
-            VALID_PRIORITIES.join(", ")
-        );
-    }
-
-    // Verify parent exists
-    let parent = db.get_issue(parent_id)?;
-    if parent.is_none() {
-        bail!("Parent issue #{} not found", parent_id);
-    }
+    db.check_priority(priority)?;
 
     let id = db.create_subissue(parent_id, title, description, priority)?;
 
@@ -198,7 +281,11 @@ pub fn run_subissue(
         println!("Created subissue #{} under #{}", id, parent_id);
     }
 
-    // Set as active session work item
+    start_work(db, id, title, opts)
+}
+
+/// Make `id` the active session's work item if `--work` was given.
+fn start_work(db: &Database, id: i64, title: &str, opts: &CreateOpts<'_>) -> Result<()> {
     if opts.work {
         if let Ok(Some(session)) = db.get_current_session() {
             db.set_session_issue(session.id, id)?;
@@ -216,38 +303,214 @@ pub fn run_subissue(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::PriorityScale;
     use proptest::prelude::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
 
     // ==================== Unit Tests ====================
 
+    #[test]
+    fn test_description_from_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notes.md");
+        fs::write(&path, "Line one\n\nLine two\n\n").unwrap();
+        assert_eq!(
+            description_from_file(&path).unwrap(),
+            Some("Line one\n\nLine two".to_string())
+        );
+
+        fs::write(&path, " \n\n").unwrap();
+        assert_eq!(description_from_file(&path).unwrap(), None);
+
+        let err = description_from_file(&dir.path().join("missing.md")).unwrap_err();
+        assert!(err.to_string().contains("missing.md"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_description_empty_or_failing_editor() {
+        // `true` saves nothing, which means no description
+        assert_eq!(edit_description("true").unwrap(), None);
+        assert!(edit_description("false").is_err());
+        assert!(edit_description("chainlink-no-such-editor").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_description_reads_saved_text() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let editor = dir.path().join("editor.sh");
+        fs::write(
+            &editor,
+            "#!/bin/sh\nprintf 'Written in editor\\n' > \"$1\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&editor, fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(
+            edit_description(editor.to_str().unwrap()).unwrap(),
+            Some("Written in editor".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_blocked_by_adds_edges() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("Blocker A", None, "medium").unwrap();
+        let b = db.create_issue("Blocker B", None, "medium").unwrap();
+
+        let opts = CreateOpts {
+            labels: &["api".to_string()],
+            blocked_by: &[a, b],
+            work: false,
+            quiet: true,
+            unique: false,
+        };
+        run(&db, "New work", None, Some("medium"), None, &opts).unwrap();
+
+        let issues = db.list_issues(None, None, None).unwrap();
+        let new = issues.iter().find(|i| i.title == "New work").unwrap();
+        let mut blockers = db.get_blockers(new.id).unwrap();
+        blockers.sort();
+        assert_eq!(blockers, vec![a, b]);
+        assert_eq!(db.get_labels(new.id).unwrap(), vec!["api"]);
+    }
+
+    #[test]
+    fn test_run_unique_applies_labels_and_blockers() {
+        let (db, _dir) = setup_test_db();
+        let existing = db
+            .create_issue("New work", Some("Original"), "low")
+            .unwrap();
+        let a = db.create_issue("Blocker A", None, "medium").unwrap();
+
+        let opts = CreateOpts {
+            labels: &["api".to_string()],
+            blocked_by: &[a],
+            work: false,
+            quiet: true,
+            unique: true,
+        };
+        run(&db, "new  WORK", Some("Replacement"), None, None, &opts).unwrap();
+
+        assert_eq!(db.list_issues(None, None, None).unwrap().len(), 2);
+        assert_eq!(db.get_blockers(existing).unwrap(), vec![a]);
+        assert_eq!(db.get_labels(existing).unwrap(), vec!["api"]);
+        let issue = db.get_issue(existing).unwrap().unwrap();
+        assert_eq!(issue.description.as_deref(), Some("Original"));
+    }
+
+    #[test]
+    fn test_run_bad_blocker_rolls_back() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("Blocker A", None, "medium").unwrap();
+
+        let opts = CreateOpts {
+            labels: &["api".to_string()],
+            blocked_by: &[a, 999],
+            work: false,
+            quiet: true,
+            unique: false,
+        };
+        assert!(run(&db, "New work", None, Some("medium"), None, &opts).is_err());
+
+        let issues = db.list_issues(None, None, None).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert!(db.list_dependencies().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_run_uses_default_priority() {
+        let (mut db, _dir) = setup_test_db();
+        db.set_default_priority("high").unwrap();
+        let opts = CreateOpts {
+            labels: &[],
+            blocked_by: &[],
+            work: false,
+            quiet: true,
+            unique: false,
+        };
+
+        run(&db, "Defaulted", None, None, None, &opts).unwrap();
+        run(&db, "Explicit", None, Some("low"), None, &opts).unwrap();
+        // Templates still win over the default, and an explicit priority wins over both
+        run(&db, "Research", None, None, Some("research"), &opts).unwrap();
+        run(&db, "Bug", None, Some("medium"), Some("bug"), &opts).unwrap();
+
+        let issues = db.list_issues(None, None, None).unwrap();
+        let priority = |title: &str| {
+            issues
+                .iter()
+                .find(|i| i.title == title)
+                .unwrap()
+                .priority
+                .clone()
+        };
+        assert_eq!(priority("Defaulted"), "high");
+        assert_eq!(priority("Explicit"), "low");
+        assert_eq!(priority("Research"), "low");
+        assert_eq!(priority("Bug"), "medium");
+    }
+
     #[test]
     fn test_validate_priority_valid() {
-        assert!(validate_priority("low"));
-        assert!(validate_priority("medium"));
-        assert!(validate_priority("high"));
-        assert!(validate_priority("critical"));
+        let (db, _dir) = setup_test_db();
+        for p in ["low", "medium", "high", "critical"] {
+            assert!(db.check_priority(p).is_ok());
+        }
     }
 
     #[test]
     fn test_validate_priority_invalid() {
-        assert!(!validate_priority(""));
-        assert!(!validate_priority("urgent"));
-        assert!(!validate_priority("LOW")); // Case sensitive
-        assert!(!validate_priority("MEDIUM"));
-        assert!(!validate_priority("High"));
-        assert!(!validate_priority("CRITICAL"));
-        assert!(!validate_priority(" medium"));
-        assert!(!validate_priority("medium "));
-        assert!(!validate_priority("medium\n"));
+        let (db, _dir) = setup_test_db();
+        assert!(db.check_priority("").is_err());
+        assert!(db.check_priority("urgent").is_err());
+        assert!(db.check_priority("LOW").is_err()); // Case sensitive
+        assert!(db.check_priority("MEDIUM").is_err());
+        assert!(db.check_priority("High").is_err());
+        assert!(db.check_priority("CRITICAL").is_err());
+        assert!(db.check_priority(" medium").is_err());
+        assert!(db.check_priority("medium ").is_err());
+        assert!(db.check_priority("medium\n").is_err());
     }
 
     #[test]
     fn test_validate_priority_malicious() {
         // Security: ensure no injection vectors
-        assert!(!validate_priority("'; DROP TABLE issues; --"));
-        assert!(!validate_priority("high\0medium"));
-        assert!(!validate_priority("medium; DELETE FROM issues"));
-        assert!(!validate_priority("<script>alert('xss')</script>"));
+        let (db, _dir) = setup_test_db();
+        assert!(db.check_priority("'; DROP TABLE issues; --").is_err());
+        assert!(db.check_priority("high\0medium").is_err());
+        assert!(db.check_priority("medium; DELETE FROM issues").is_err());
+        assert!(db.check_priority("<script>alert('xss')</script>").is_err());
+    }
+
+    #[test]
+    fn test_run_uses_configured_priorities() {
+        let (db, _dir) = setup_test_db();
+        let levels: Vec<String> = ["p3", "p2", "p1"].iter().map(|p| p.to_string()).collect();
+        db.set_priorities(&levels).unwrap();
+        let opts = CreateOpts {
+            labels: &[],
+            blocked_by: &[],
+            work: false,
+            quiet: true,
+            unique: false,
+        };
+
+        assert!(run(&db, "Old scheme", None, Some("medium"), None, &opts).is_err());
+        run(&db, "New scheme", None, Some("p1"), None, &opts).unwrap();
+        let issues = db.list_issues(None, None, None).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].priority, "p1");
     }
 
     #[test]
@@ -287,7 +550,7 @@ mod tests {
         // Verify all templates have required fields
         for template in TEMPLATES {
             assert!(!template.name.is_empty());
-            assert!(validate_priority(template.priority));
+            assert!(PriorityScale::default().contains(template.priority));
             assert!(!template.label.is_empty());
         }
     }
@@ -319,7 +582,7 @@ mod tests {
                     !["low", "medium", "high", "critical"].contains(&s.as_str())
                 })
         ) {
-            prop_assert!(!validate_priority(&priority));
+            prop_assert!(!PriorityScale::default().contains(&priority));
         }
 
         #[test]
@@ -0,0 +1,59 @@
+use anyhow::Result;
+
+use crate::db::Database;
+
+pub fn run(db: &Database) -> Result<()> {
+    let groups = db.find_duplicates()?;
+
+    if groups.is_empty() {
+        println!("No duplicate titles among open issues.");
+        return Ok(());
+    }
+
+    println!(
+        "Found {} group(s) of open issues with the same title:",
+        groups.len()
+    );
+    for group in &groups {
+        println!();
+        for id in group {
+            if let Some(issue) = db.get_issue(*id)? {
+                println!("  #{:<4} {:8} {}", issue.id, issue.priority, issue.title);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_dedupe_groups_case_insensitive_titles() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("Update README", None, "medium").unwrap();
+        let b = db.create_issue("update readme", None, "low").unwrap();
+
+        run(&db).unwrap();
+        assert_eq!(db.find_duplicates().unwrap(), vec![vec![a, b]]);
+    }
+
+    #[test]
+    fn test_dedupe_no_duplicates() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("Unique", None, "medium").unwrap();
+
+        run(&db).unwrap();
+        assert!(db.find_duplicates().unwrap().is_empty());
+    }
+}
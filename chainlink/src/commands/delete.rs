@@ -1,30 +1,36 @@
 use anyhow::{bail, Result};
-use std::io::{self, Write};
 
 use crate::db::Database;
+use crate::output::Reporter;
+use crate::utils::confirm;
 
-pub fn run(db: &Database, id: i64, force: bool) -> Result<()> {
+pub fn run(db: &Database, out: &Reporter, id: i64, force: bool, dry_run: bool) -> Result<()> {
     // Check if issue exists first
-    let issue = match db.get_issue(id)? {
-        Some(i) => i,
-        None => bail!("Issue #{} not found", id),
-    };
-
-    if !force {
-        print!("Delete issue #{} \"{}\"? [y/N] ", id, issue.title);
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-
-        if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Cancelled.");
-            return Ok(());
+    let issue = db.require_issue(id)?;
+
+    if dry_run {
+        // Delete for real inside a rolled-back transaction to see what cascades
+        let removed = db.dry_run(|| -> Result<usize> {
+            let before = db.list_issues(Some("all"), None, None)?.len();
+            db.delete_issue(id)?;
+            Ok(before - db.list_issues(Some("all"), None, None)?.len())
+        })?;
+        out.result(&format!("Would delete issue #{} \"{}\"", id, issue.title));
+        if removed > 1 {
+            out.result(&format!("  and {} subissue(s) under it", removed - 1));
         }
+        out.result("Dry run: nothing was changed.");
+        return Ok(());
+    }
+
+    let prompt = format!("Delete issue #{} \"{}\"?", id, issue.title);
+    if !confirm(&prompt, force)? {
+        out.info("Cancelled.");
+        return Ok(());
     }
 
     if db.delete_issue(id)? {
-        println!("Deleted issue #{}", id);
+        out.info(&format!("Deleted issue #{}", id));
     } else {
         bail!("Failed to delete issue #{}", id);
     }
@@ -35,12 +41,13 @@ pub fn run(db: &Database, id: i64, force: bool) -> Result<()> {
 /// Internal function for testing without stdin interaction
 #[cfg(test)]
 pub fn run_force(db: &Database, id: i64) -> Result<()> {
-    run(db, id, true)
+    run(db, &Reporter::default(), id, true, false)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::Verbosity;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -75,6 +82,33 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    #[test]
+    fn test_dry_run_delete_keeps_issue() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "high").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "low").unwrap();
+        let out = Reporter::capturing(Verbosity::Normal);
+
+        run(&db, &out, parent, false, true).unwrap();
+
+        assert!(db.get_issue(parent).unwrap().is_some());
+        assert!(db.get_issue(child).unwrap().is_some());
+        let output = out.output();
+        assert!(output.contains(&format!("Would delete issue #{} \"Parent\"", parent)));
+        assert!(output.contains("and 1 subissue(s)"));
+        assert!(output.contains("nothing was changed"));
+
+        let quiet = Reporter::capturing(Verbosity::Quiet);
+        run(&db, &quiet, parent, false, true).unwrap();
+        assert!(quiet.output().contains("Would delete issue"));
+    }
+
+    #[test]
+    fn test_dry_run_delete_nonexistent() {
+        let (db, _dir) = setup_test_db();
+        assert!(run(&db, &Reporter::default(), 99999, false, true).is_err());
+    }
+
     #[test]
     fn test_delete_cascades_labels() {
         let (db, _dir) = setup_test_db();
@@ -225,7 +259,7 @@ mod tests {
 
     proptest! {
         #[test]
-        fn prop_delete_force_removes_issue(title in "[a-zA-Z0-9 ]{1,50}") {
+        fn prop_delete_force_removes_issue(title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,49}") {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue(&title, None, "medium").unwrap();
 
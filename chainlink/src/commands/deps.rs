@@ -1,9 +1,16 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, Read, Write};
+use unicode_width::UnicodeWidthStr;
 
+use super::export::ExportedDependency;
 use crate::db::Database;
-use crate::utils::truncate;
+use crate::models::IssueWithBlockers;
+use crate::output::{OutputFormat, Reporter};
+use crate::utils::{pad, sanitize_display, truncate};
 
-pub fn block(db: &Database, issue_id: i64, blocker_id: i64) -> Result<()> {
+pub fn block(db: &Database, out: &Reporter, issue_id: i64, blocker_id: i64) -> Result<()> {
     // Check if both issues exist
     db.require_issue(issue_id)?;
     db.require_issue(blocker_id)?;
@@ -13,41 +20,501 @@ pub fn block(db: &Database, issue_id: i64, blocker_id: i64) -> Result<()> {
     }
 
     if db.add_dependency(issue_id, blocker_id)? {
-        println!("Issue #{} is now blocked by #{}", issue_id, blocker_id);
+        out.info(&format!(
+            "Issue #{} is now blocked by #{}",
+            issue_id, blocker_id
+        ));
+        out.detail(&format!(
+            "  dependencies: added blocked_id={} blocker_id={} kind=blocks",
+            issue_id, blocker_id
+        ));
     } else {
-        println!("Dependency already exists");
+        out.info("Dependency already exists");
     }
     Ok(())
 }
 
-pub fn unblock(db: &Database, issue_id: i64, blocker_id: i64) -> Result<()> {
+pub fn unblock(db: &Database, out: &Reporter, issue_id: i64, blocker_id: i64) -> Result<()> {
     if db.remove_dependency(issue_id, blocker_id)? {
-        println!(
+        out.info(&format!(
             "Removed: #{} no longer blocked by #{}",
             issue_id, blocker_id
-        );
+        ));
+        out.detail(&format!(
+            "  dependencies: removed blocked_id={} blocker_id={} kind=blocks",
+            issue_id, blocker_id
+        ));
+    } else {
+        out.info("No such dependency found");
+    }
+
+    // Another chain can keep the issue blocked even without the direct edge
+    if let Some(path) = db.transitive_blocker_path(issue_id, blocker_id)? {
+        let via: Vec<String> = path[1..].iter().map(|id| format!("#{}", id)).collect();
+        out.info(&format!(
+            "Note: #{} is still blocked via {}",
+            issue_id,
+            via.join(" -> ")
+        ));
+    }
+    Ok(())
+}
+
+/// Reverse the blocking edge between `a` and `b`, for a block added the wrong way round.
+pub fn swap(db: &Database, out: &Reporter, a: i64, b: i64) -> Result<()> {
+    db.require_issue(a)?;
+    db.require_issue(b)?;
+
+    let (blocked, blocker) = db.swap_dependency(a, b)?;
+    out.info(&format!(
+        "Swapped: #{} is now blocked by #{}",
+        blocked, blocker
+    ));
+    out.detail(&format!(
+        "  dependencies: removed blocked_id={} blocker_id={} kind=blocks",
+        blocker, blocked
+    ));
+    out.detail(&format!(
+        "  dependencies: added blocked_id={} blocker_id={} kind=blocks",
+        blocked, blocker
+    ));
+    Ok(())
+}
+
+/// Answer "would finishing `candidate` make `target` ready?", naming whatever
+/// else keeps `target` blocked.
+pub fn render_impact(db: &Database, candidate: i64, target: i64) -> Result<String> {
+    db.require_issue(candidate)?;
+    let issue = db.require_issue(target)?;
+
+    if db.would_unblock(candidate, target)? {
+        return Ok(format!(
+            "Finishing #{} would unblock #{}\n",
+            candidate, target
+        ));
+    }
+    if issue.status != "open" {
+        return Ok(format!("#{} is already {}\n", target, issue.status));
+    }
+
+    let others: Vec<String> = db
+        .open_blockers(target)?
+        .into_iter()
+        .filter(|&b| b != candidate)
+        .map(|b| format!("#{}", b))
+        .collect();
+    if others.is_empty() {
+        return Ok(format!("#{} is not blocked\n", target));
+    }
+
+    let mut out = format!(
+        "No: #{} would still be blocked by {}\n",
+        target,
+        others.join(", ")
+    );
+    if let Some(path) = db.transitive_blocker_path(target, candidate)? {
+        if path.len() > 2 {
+            // The path runs from target to candidate; show it in blocking order
+            let chain: Vec<String> = path.iter().rev().map(|id| format!("#{}", id)).collect();
+            out.push_str(&format!(
+                "#{} blocks it only indirectly: {}\n",
+                candidate,
+                chain.join(" -> ")
+            ));
+        }
+    }
+    Ok(out)
+}
+
+pub fn impact(db: &Database, candidate: i64, target: i64) -> Result<()> {
+    print!("{}", render_impact(db, candidate, target)?);
+    Ok(())
+}
+
+/// Remove every blocker of `issue_id` at once.
+pub fn unblock_all(db: &Database, out: &Reporter, issue_id: i64) -> Result<()> {
+    db.require_issue(issue_id)?;
+
+    let blockers = db.get_blockers(issue_id)?;
+    let removed = db.clear_dependencies(issue_id)?;
+    if removed == 0 {
+        out.info(&format!("Issue #{} has no blockers", issue_id));
+        return Ok(());
+    }
+
+    out.info(&format!(
+        "Removed {} blocker(s) from #{}",
+        removed, issue_id
+    ));
+    for blocker_id in blockers {
+        out.detail(&format!(
+            "  dependencies: removed blocked_id={} blocker_id={} kind=blocks",
+            issue_id, blocker_id
+        ));
+    }
+    Ok(())
+}
+
+/// Mark `issue_id` as a duplicate of `original_id`. Duplicate links never block.
+pub fn dup(db: &Database, out: &Reporter, issue_id: i64, original_id: i64) -> Result<()> {
+    db.require_issue(issue_id)?;
+    db.require_issue(original_id)?;
+
+    if db.add_dependency_of_kind(issue_id, original_id, "duplicates")? {
+        out.info(&format!(
+            "Issue #{} is now a duplicate of #{}",
+            issue_id, original_id
+        ));
+        out.detail(&format!(
+            "  dependencies: added blocked_id={} blocker_id={} kind=duplicates",
+            issue_id, original_id
+        ));
+    } else {
+        out.info(&format!(
+            "Issue #{} is already a duplicate of #{}",
+            issue_id, original_id
+        ));
+    }
+    Ok(())
+}
+
+/// Record that `issue_id` should be done after `earlier_id`. A soft hint:
+/// it lowers `issue_id`'s `next` ranking but never makes it unready.
+pub fn after(db: &Database, out: &Reporter, issue_id: i64, earlier_id: i64) -> Result<()> {
+    db.require_issue(issue_id)?;
+    db.require_issue(earlier_id)?;
+
+    if db.add_dependency_of_kind(issue_id, earlier_id, "after")? {
+        out.info(&format!(
+            "Issue #{} should now come after #{}",
+            issue_id, earlier_id
+        ));
+        out.detail(&format!(
+            "  dependencies: added blocked_id={} blocker_id={} kind=after",
+            issue_id, earlier_id
+        ));
     } else {
-        println!("No such dependency found");
+        out.info(&format!(
+            "Issue #{} already comes after #{}",
+            issue_id, earlier_id
+        ));
+    }
+    Ok(())
+}
+
+/// Every blocking edge, as `#blocked ← #blocker` lines, a JSON array or CSV.
+pub fn render_all(db: &Database, format: OutputFormat) -> Result<String> {
+    let edges = db.list_dependencies()?;
+
+    Ok(match format {
+        OutputFormat::Human if edges.is_empty() => "No dependencies.\n".to_string(),
+        OutputFormat::Human => edges
+            .iter()
+            .map(|(blocked, blocker)| format!("#{} ← #{}\n", blocked, blocker))
+            .collect(),
+        OutputFormat::Json => {
+            let edges: Vec<ExportedDependency> = edges
+                .into_iter()
+                .map(|(blocked_id, blocker_id)| ExportedDependency {
+                    blocked_id,
+                    blocker_id,
+                    kind: "blocks".to_string(),
+                })
+                .collect();
+            format!("{}\n", serde_json::to_string_pretty(&edges)?)
+        }
+        OutputFormat::Csv => {
+            let mut out = String::from("blocked_id,blocker_id\n");
+            for (blocked, blocker) in edges {
+                out.push_str(&format!("{},{}\n", blocked, blocker));
+            }
+            out
+        }
+    })
+}
+
+pub fn list_all(db: &Database, format: OutputFormat) -> Result<()> {
+    print!("{}", render_all(db, format)?);
+    Ok(())
+}
+
+/// One line per independent cluster of blocking relationships.
+pub fn render_components(db: &Database) -> Result<String> {
+    let components = db.dependency_components()?;
+    if components.is_empty() {
+        return Ok("No dependencies.\n".to_string());
+    }
+
+    let mut out = String::new();
+    for (n, component) in components.iter().enumerate() {
+        let ids: Vec<String> = component.iter().map(|id| format!("#{}", id)).collect();
+        out.push_str(&format!(
+            "Cluster {} ({} issues): {}\n",
+            n + 1,
+            component.len(),
+            ids.join(", ")
+        ));
+    }
+    Ok(out)
+}
+
+pub fn components(db: &Database) -> Result<()> {
+    print!("{}", render_components(db)?);
+    Ok(())
+}
+
+/// Open issues by scheduling tier: everything in a tier can start once the
+/// tiers before it are done.
+pub fn render_levels(db: &Database) -> Result<String> {
+    let levels = db.dependency_levels()?;
+    if levels.is_empty() {
+        return Ok("No open issues.\n".to_string());
+    }
+
+    let ids: Vec<i64> = levels.iter().flatten().copied().collect();
+    let issues = db.get_issues(&ids)?;
+    let mut out = String::new();
+    for (level, tier) in levels.iter().enumerate() {
+        out.push_str(&format!("Level {}:\n", level));
+        for id in tier {
+            out.push_str(&format!(
+                "  #{:<4} {}\n",
+                id,
+                sanitize_display(&issues[id].title)
+            ));
+        }
+    }
+    Ok(out)
+}
+
+pub fn levels(db: &Database) -> Result<()> {
+    print!("{}", render_levels(db)?);
+    Ok(())
+}
+
+/// Open issues holding up the most work, with how many open issues wait on
+/// each one directly or transitively.
+pub fn render_bottlenecks(db: &Database, limit: usize) -> Result<String> {
+    let top = db.top_blockers(limit)?;
+    if top.is_empty() {
+        return Ok("No open issues are blocking others.\n".to_string());
+    }
+
+    let mut out = String::new();
+    for (issue, count) in top {
+        out.push_str(&format!(
+            "#{:<4} blocks {:<3} {}\n",
+            issue.id,
+            count,
+            sanitize_display(&issue.title)
+        ));
+    }
+    Ok(out)
+}
+
+pub fn bottlenecks(db: &Database, limit: usize) -> Result<()> {
+    print!("{}", render_bottlenecks(db, limit)?);
+    Ok(())
+}
+
+/// Render every blocking edge as a DOT digraph. Edges point from blocker to
+/// blocked; each issue on an edge gets a node labelled with its id and title.
+pub fn to_dot(db: &Database) -> Result<String> {
+    let edges = db.list_dependencies()?;
+    let ids: BTreeSet<i64> = edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+    let issues = db.get_issues(&ids.iter().copied().collect::<Vec<_>>())?;
+
+    let mut dot = String::from("digraph dependencies {\n");
+    for id in &ids {
+        let title = issues.get(id).map_or(String::new(), |i| i.title.clone());
+        let label = sanitize_display(&title)
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        dot.push_str(&format!("    {} [label=\"#{} {}\"];\n", id, id, label));
+    }
+    for (blocked, blocker) in &edges {
+        dot.push_str(&format!("    {} -> {};\n", blocker, blocked));
+    }
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+pub fn export_dot(db: &Database, out: &Reporter, output_path: Option<&str>) -> Result<()> {
+    let dot = to_dot(db)?;
+    match output_path {
+        Some(path) => {
+            fs::write(path, dot).context("Failed to write DOT file")?;
+            out.info(&format!("Exported dependency graph to {}", path));
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(dot.as_bytes())?;
+        }
     }
     Ok(())
 }
 
-pub fn list_blocked(db: &Database) -> Result<()> {
-    let issues = db.list_blocked_issues()?;
+/// Split DOT source into statements, dropping comments, quotes and `[...]`
+/// attribute lists. Only the pieces `import_dot` needs survive.
+fn dot_statements(src: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut chars = src.chars().peekable();
+    let mut in_string = false;
+    let mut bracket_depth: usize = 0;
+    let mut at_line_start = true;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => in_string = false,
+                _ if bracket_depth == 0 => current.push(c),
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '[' => bracket_depth += 1,
+            // A stray `]` must not leave later statements hidden
+            ']' => bracket_depth = bracket_depth.saturating_sub(1),
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                statements.push(std::mem::take(&mut current));
+                at_line_start = true;
+                continue;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                continue;
+            }
+            // `#` lines are C preprocessor output, which DOT ignores
+            '#' if at_line_start => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            ';' | '\n' | '{' | '}' if bracket_depth == 0 => {
+                statements.push(std::mem::take(&mut current));
+            }
+            _ if bracket_depth == 0 => current.push(c),
+            _ => {}
+        }
+        at_line_start = c == '\n' || (at_line_start && c.is_whitespace());
+    }
+    statements.push(current);
+
+    statements
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn parse_dot_id(token: &str) -> Result<i64> {
+    let token = token.trim();
+    token
+        .strip_prefix('#')
+        .unwrap_or(token)
+        .parse()
+        .with_context(|| format!("Invalid node id '{}' in DOT edge", token))
+}
+
+/// Recreate blocking edges from a DOT graph, as written by `to_dot`: `a -> b`
+/// means `a` blocks `b`, and chains like `a -> b -> c` add each link. Node
+/// declarations and graph attributes are ignored. Every id must be an existing
+/// issue; otherwise nothing is imported. Returns the number of new edges.
+pub fn import_dot<R: Read>(db: &Database, mut reader: R) -> Result<usize> {
+    let mut src = String::new();
+    reader
+        .read_to_string(&mut src)
+        .context("Failed to read DOT input")?;
+
+    let mut edges = Vec::new();
+    for statement in dot_statements(&src) {
+        if !statement.contains("->") {
+            continue;
+        }
+        let ids = statement
+            .split("->")
+            .map(parse_dot_id)
+            .collect::<Result<Vec<_>>>()?;
+        for pair in ids.windows(2) {
+            edges.push((pair[1], pair[0]));
+        }
+    }
+
+    db.transaction(|| -> Result<usize> {
+        let mut added = 0;
+        for &(blocked, blocker) in &edges {
+            db.require_issue(blocked)?;
+            db.require_issue(blocker)?;
+            if db.add_dependency(blocked, blocker)? {
+                added += 1;
+            }
+        }
+        Ok(added)
+    })
+}
+
+/// Turn a `--since` value into RFC 3339. Accepts a full timestamp or a bare `YYYY-MM-DD` date.
+fn normalize_since(since: &str) -> Result<String> {
+    if chrono::DateTime::parse_from_rfc3339(since).is_ok() {
+        return Ok(since.to_string());
+    }
+    match chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        Ok(date) => Ok(date
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time")
+            .and_utc()
+            .to_rfc3339()),
+        Err(_) => bail!(
+            "Invalid --since '{}'. Use YYYY-MM-DD or an RFC 3339 timestamp",
+            since
+        ),
+    }
+}
+
+/// Print issues that have been blocked since before `since`.
+pub fn list_long_blocked(db: &Database, since: &str) -> Result<()> {
+    let issues = db.list_long_blocked(&normalize_since(since)?)?;
 
     if issues.is_empty() {
-        println!("No blocked issues.");
+        println!("No issues blocked since before {}.", since);
         return Ok(());
     }
 
-    println!("Blocked issues:");
+    let ids: Vec<i64> = issues.iter().map(|i| i.id).collect();
+    let blockers = db.get_blockers_for(&ids)?;
+
+    println!("Blocked since before {}:", since);
     for issue in issues {
-        let blockers = db.get_blockers(issue.id)?;
-        let blocker_strs: Vec<String> = blockers.iter().map(|b| format!("#{}", b)).collect();
+        let blocker_strs: Vec<String> = blockers[&issue.id]
+            .iter()
+            .map(|b| format!("#{}", b))
+            .collect();
         println!(
             "  #{:<4} {} (blocked by: {})",
             issue.id,
-            truncate(&issue.title, 40),
+            pad(&truncate(&issue.title, 40), 40),
             blocker_strs.join(", ")
         );
     }
@@ -55,27 +522,114 @@ pub fn list_blocked(db: &Database) -> Result<()> {
     Ok(())
 }
 
-pub fn list_ready(db: &Database) -> Result<()> {
-    let issues = db.list_ready_issues()?;
+/// Width of the id column: four digits, or wider if a listed id needs it.
+fn id_width(issues: &[IssueWithBlockers]) -> usize {
+    issues
+        .iter()
+        .map(|m| m.issue.id.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max(4)
+}
+
+/// Build the blocked-issues listing without printing it.
+/// Titles are clipped to `title_width`, or padded to the longest one when None.
+pub fn render_blocked(db: &Database, title_width: Option<usize>) -> Result<String> {
+    let issues = db.list_blocked_with_meta()?;
 
     if issues.is_empty() {
-        println!("No ready issues.");
-        return Ok(());
+        return Ok("No blocked issues.\n".to_string());
     }
 
-    println!("Ready issues (no blockers):");
-    for issue in issues {
-        println!("  #{:<4} {:8} {}", issue.id, issue.priority, issue.title);
+    let id_width = id_width(&issues);
+    let title_width = title_width.unwrap_or_else(|| {
+        issues
+            .iter()
+            .map(|m| m.issue.title.width())
+            .max()
+            .unwrap_or(0)
+    });
+    let mut out = String::from("Blocked issues:\n");
+    for IssueWithBlockers { issue, blockers } in issues {
+        let blocker_strs: Vec<String> = blockers.iter().map(|b| format!("#{}", b)).collect();
+        out.push_str(&format!(
+            "  #{:<id_width$} {} (blocked by: {})\n",
+            issue.id,
+            pad(&truncate(&issue.title, title_width), title_width),
+            blocker_strs.join(", ")
+        ));
+    }
+
+    Ok(out)
+}
+
+pub fn list_blocked(db: &Database, title_width: Option<usize>) -> Result<()> {
+    print!("{}", render_blocked(db, title_width)?);
+    Ok(())
+}
+
+/// Build the ready-issues listing without printing it.
+/// With `assignee`, only that person's ready issues (`UNASSIGNED` for nobody's).
+pub fn render_ready(
+    db: &Database,
+    exclude_closed_parents: bool,
+    assignee: Option<&str>,
+    color: bool,
+    title_width: Option<usize>,
+) -> Result<String> {
+    let issues = match assignee {
+        Some(assignee) => db.list_ready_for_assignee(exclude_closed_parents, assignee)?,
+        None => db.list_ready_with_meta(exclude_closed_parents)?,
+    };
+
+    if issues.is_empty() {
+        return Ok("No ready issues.\n".to_string());
+    }
+
+    let scale = db.priority_scale()?;
+    let width = scale.column_width();
+    let id_width = id_width(&issues);
+    let mut out = String::from("Ready issues (no blockers):\n");
+    for IssueWithBlockers { issue, .. } in issues {
+        out.push_str(&format!(
+            "  #{:<id_width$} {} {}\n",
+            issue.id,
+            scale.display_padded(&issue.priority, width, color),
+            match title_width {
+                Some(width) => truncate(&issue.title, width),
+                None => issue.title.clone(),
+            }
+        ));
+    }
+    if color {
+        out.push_str(&format!("\n{}\n", scale.legend()));
     }
 
+    Ok(out)
+}
+
+pub fn list_ready(
+    db: &Database,
+    exclude_closed_parents: bool,
+    assignee: Option<&str>,
+    color: bool,
+    title_width: Option<usize>,
+) -> Result<()> {
+    print!(
+        "{}",
+        render_ready(db, exclude_closed_parents, assignee, color, title_width)?
+    );
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::Verbosity;
+    use crate::utils::TITLE_WIDTH;
     use proptest::prelude::*;
     use tempfile::tempdir;
+    use unicode_width::UnicodeWidthStr;
 
     fn setup_test_db() -> (Database, tempfile::TempDir) {
         let dir = tempdir().unwrap();
@@ -84,6 +638,203 @@ mod tests {
         (db, dir)
     }
 
+    #[test]
+    fn test_dot_round_trip() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("Design \"v2\" API", None, "high").unwrap();
+        let b = db.create_issue("Build it\nnow", None, "medium").unwrap();
+        let c = db.create_issue("Ship", None, "medium").unwrap();
+        db.add_dependency(b, a).unwrap();
+        db.add_dependency(c, b).unwrap();
+        db.add_dependency(c, a).unwrap();
+        let before = db.list_dependencies().unwrap();
+
+        let dot = to_dot(&db).unwrap();
+        for (blocked, blocker) in &before {
+            db.remove_dependency(*blocked, *blocker).unwrap();
+        }
+        assert!(db.list_dependencies().unwrap().is_empty());
+
+        assert_eq!(import_dot(&db, dot.as_bytes()).unwrap(), 3);
+        assert_eq!(db.list_dependencies().unwrap(), before);
+    }
+
+    #[test]
+    fn test_import_dot_tolerates_comments_and_chains() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+
+        let src = format!(
+            "# preprocessor line\n\
+             digraph G {{ rankdir=LR; // layout\n\
+             /* multi\n line */ \"{a}\" [label=\"a; -> b\"]\n\
+             \t{a}->{b} -> {c} [color=red]\n\
+             }}\n"
+        );
+        assert_eq!(import_dot(&db, src.as_bytes()).unwrap(), 2);
+        assert_eq!(db.get_blockers(b).unwrap(), vec![a]);
+        assert_eq!(db.get_blockers(c).unwrap(), vec![b]);
+    }
+
+    #[test]
+    fn test_import_dot_stray_bracket() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+
+        let src = format!("digraph {{ ] {a} -> {b}; }}");
+        assert_eq!(import_dot(&db, src.as_bytes()).unwrap(), 1);
+        assert_eq!(db.get_blockers(b).unwrap(), vec![a]);
+    }
+
+    #[test]
+    fn test_import_dot_unknown_id_imports_nothing() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+
+        let src = format!("digraph {{ {a} -> {b}; {a} -> 999; }}");
+        let err = import_dot(&db, src.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("#999 not found"));
+        assert!(db.list_dependencies().unwrap().is_empty());
+
+        assert!(import_dot(&db, "digraph { x -> 1 }".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_render_blocked() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(
+            render_blocked(&db, Some(TITLE_WIDTH)).unwrap(),
+            "No blocked issues.\n"
+        );
+
+        let open = db.create_issue("Open blocker", None, "medium").unwrap();
+        let closed = db.create_issue("Closed blocker", None, "medium").unwrap();
+        let issue = db.create_issue("Stuck", None, "high").unwrap();
+        db.add_dependency(issue, open).unwrap();
+        db.add_dependency(issue, closed).unwrap();
+        db.close_issue(closed).unwrap();
+
+        assert_eq!(
+            render_blocked(&db, Some(TITLE_WIDTH)).unwrap(),
+            format!(
+                "Blocked issues:\n  #{:<4} {} (blocked by: #{}, #{})\n",
+                issue,
+                pad("Stuck", 40),
+                open,
+                closed
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_blocked_and_ready_respect_title_width() {
+        let (db, _dir) = setup_test_db();
+        let blocker = db.create_issue("Blocker", None, "low").unwrap();
+        let issue = db
+            .create_issue("A rather long title that needs clipping", None, "high")
+            .unwrap();
+        db.add_dependency(issue, blocker).unwrap();
+
+        assert_eq!(
+            render_blocked(&db, Some(12)).unwrap(),
+            format!(
+                "Blocked issues:\n  #{:<4} A rather ... (blocked by: #{})\n",
+                issue, blocker
+            )
+        );
+        assert!(render_blocked(&db, Some(60))
+            .unwrap()
+            .contains("A rather long title that needs clipping "));
+        assert!(render_ready(&db, false, None, false, Some(5))
+            .unwrap()
+            .contains("Bl...\n"));
+
+        // Unclipped, for output that is not going to a terminal
+        assert_eq!(
+            render_blocked(&db, None).unwrap(),
+            format!(
+                "Blocked issues:\n  #{:<4} A rather long title that needs clipping (blocked by: #{})\n",
+                issue, blocker
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_ready() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(
+            render_ready(&db, false, None, false, Some(TITLE_WIDTH)).unwrap(),
+            "No ready issues.\n"
+        );
+
+        let blocker = db.create_issue("Blocker", None, "low").unwrap();
+        let issue = db.create_issue("Unblocked", None, "high").unwrap();
+        db.add_dependency(issue, blocker).unwrap();
+        db.close_issue(blocker).unwrap();
+        let fresh = db.create_issue("Fresh", None, "medium").unwrap();
+
+        assert_eq!(
+            render_ready(&db, false, None, false, Some(TITLE_WIDTH)).unwrap(),
+            format!(
+                "Ready issues (no blockers):\n  #{:<4} high     Unblocked\n  #{:<4} medium   Fresh\n",
+                issue, fresh
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_ready_custom_priorities_align() {
+        let (db, _dir) = setup_test_db();
+        let levels = vec!["later".to_string(), "right-now".to_string()];
+        db.set_priorities(&levels).unwrap();
+        let a = db.create_issue("A", None, "right-now").unwrap();
+        let b = db.create_issue("B", None, "later").unwrap();
+
+        assert_eq!(
+            render_ready(&db, false, None, false, Some(TITLE_WIDTH)).unwrap(),
+            format!(
+                "Ready issues (no blockers):\n  #{:<4} right-now A\n  #{:<4} later     B\n",
+                a, b
+            )
+        );
+        let colored = render_ready(&db, false, None, true, Some(TITLE_WIDTH)).unwrap();
+        assert!(colored.contains("\x1b[33mlater    \x1b[0m B"));
+        assert!(colored.ends_with("Priorities: \x1b[31mright-now\x1b[0m \x1b[33mlater\x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_render_ready_wide_ids_align() {
+        let (db, dir) = setup_test_db();
+        let small = db.create_issue("Small id", None, "high").unwrap();
+        // Skip ahead in the id sequence rather than creating thousands of issues
+        rusqlite::Connection::open(dir.path().join("test.db"))
+            .unwrap()
+            .execute(
+                "UPDATE sqlite_sequence SET seq = 12344 WHERE name = 'issues'",
+                [],
+            )
+            .unwrap();
+        assert_eq!(db.create_issue("Wide id", None, "medium").unwrap(), 12345);
+
+        let out = render_ready(&db, false, None, false, Some(TITLE_WIDTH)).unwrap();
+        let rows: Vec<&str> = out.lines().skip(1).collect();
+        assert_eq!(rows.len(), 2);
+        assert!(rows
+            .iter()
+            .any(|r| r.starts_with(&format!("  #{:<5} high", small))));
+        assert!(rows.iter().any(|r| r.starts_with("  #12345 medium")));
+        // Titles start in the same column on every row
+        let columns: Vec<usize> = rows
+            .iter()
+            .map(|r| r.find("Small").or(r.find("Wide")).unwrap())
+            .collect();
+        assert_eq!(columns[0], columns[1]);
+    }
+
     // Block function tests
     #[test]
     fn test_block_success() {
@@ -91,7 +842,7 @@ mod tests {
         let issue1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        block(&db, issue1, issue2).unwrap();
+        block(&db, &Reporter::default(), issue1, issue2).unwrap();
         let blockers = db.get_blockers(issue1).unwrap();
         assert!(
             blockers.contains(&issue2),
@@ -104,7 +855,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue = db.create_issue("Issue", None, "medium").unwrap();
 
-        let result = block(&db, 99999, issue);
+        let result = block(&db, &Reporter::default(), 99999, issue);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -114,17 +865,49 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue = db.create_issue("Issue", None, "medium").unwrap();
 
-        let result = block(&db, issue, 99999);
+        let result = block(&db, &Reporter::default(), issue, 99999);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    #[test]
+    fn test_block_quiet_suppresses_already_exists() {
+        let (db, _dir) = setup_test_db();
+        let issue = db.create_issue("Issue", None, "medium").unwrap();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        block(&db, &Reporter::default(), issue, blocker).unwrap();
+
+        let normal = Reporter::capturing(Verbosity::Normal);
+        block(&db, &normal, issue, blocker).unwrap();
+        assert_eq!(normal.output(), "Dependency already exists\n");
+
+        let quiet = Reporter::capturing(Verbosity::Quiet);
+        block(&db, &quiet, issue, blocker).unwrap();
+        assert_eq!(quiet.output(), "");
+    }
+
+    #[test]
+    fn test_block_verbose_reports_change() {
+        let (db, _dir) = setup_test_db();
+        let issue = db.create_issue("Issue", None, "medium").unwrap();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+
+        let verbose = Reporter::capturing(Verbosity::Verbose);
+        block(&db, &verbose, issue, blocker).unwrap();
+        assert_eq!(
+            verbose.output(),
+            format!(
+                "Issue #{issue} is now blocked by #{blocker}\n  dependencies: added blocked_id={issue} blocker_id={blocker} kind=blocks\n"
+            )
+        );
+    }
+
     #[test]
     fn test_block_self() {
         let (db, _dir) = setup_test_db();
         let issue = db.create_issue("Issue", None, "medium").unwrap();
 
-        let result = block(&db, issue, issue);
+        let result = block(&db, &Reporter::default(), issue, issue);
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -138,8 +921,8 @@ mod tests {
         let issue1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        block(&db, issue1, issue2).unwrap();
-        block(&db, issue1, issue2).unwrap(); // Should succeed, print "already exists"
+        block(&db, &Reporter::default(), issue1, issue2).unwrap();
+        block(&db, &Reporter::default(), issue1, issue2).unwrap(); // Should succeed, print "already exists"
         let blockers = db.get_blockers(issue1).unwrap();
         assert_eq!(
             blockers.len(),
@@ -157,7 +940,7 @@ mod tests {
         let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
         db.add_dependency(issue1, issue2).unwrap();
 
-        unblock(&db, issue1, issue2).unwrap();
+        unblock(&db, &Reporter::default(), issue1, issue2).unwrap();
         let blockers = db.get_blockers(issue1).unwrap();
         assert!(
             blockers.is_empty(),
@@ -172,18 +955,355 @@ mod tests {
         let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
         // Should succeed gracefully even when no dependency exists
-        unblock(&db, issue1, issue2).unwrap();
+        unblock(&db, &Reporter::default(), issue1, issue2).unwrap();
         let blockers = db.get_blockers(issue1).unwrap();
         assert!(blockers.is_empty(), "No blockers should exist");
     }
 
+    #[test]
+    fn test_unblock_reports_transitive_blocker() {
+        let (db, _dir) = setup_test_db();
+        // Diamond: top blocks left and right, both block bottom, and top blocks bottom directly
+        let top = db.create_issue("Top", None, "medium").unwrap();
+        let left = db.create_issue("Left", None, "medium").unwrap();
+        let right = db.create_issue("Right", None, "medium").unwrap();
+        let bottom = db.create_issue("Bottom", None, "medium").unwrap();
+        db.add_dependency(left, top).unwrap();
+        db.add_dependency(right, top).unwrap();
+        db.add_dependency(bottom, left).unwrap();
+        db.add_dependency(bottom, right).unwrap();
+        db.add_dependency(bottom, top).unwrap();
+        let out = Reporter::capturing(Verbosity::Normal);
+
+        unblock(&db, &out, bottom, top).unwrap();
+        assert!(!db.get_blockers(bottom).unwrap().contains(&top));
+        let output = out.output();
+        assert!(output.contains(&format!("#{} no longer blocked by #{}", bottom, top)));
+        let via_left = format!("#{} is still blocked via #{} -> #{}", bottom, left, top);
+        let via_right = format!("#{} is still blocked via #{} -> #{}", bottom, right, top);
+        assert!(output.contains(&via_left) || output.contains(&via_right));
+
+        // Removing a direct edge with no other chain adds no note
+        let out = Reporter::capturing(Verbosity::Normal);
+        unblock(&db, &out, bottom, left).unwrap();
+        assert!(!out.output().contains("still blocked"));
+    }
+
+    #[test]
+    fn test_unblock_ignores_chain_through_closed_blocker() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let middle = db.create_issue("Middle", None, "medium").unwrap();
+        let last = db.create_issue("Last", None, "medium").unwrap();
+        db.add_dependency(middle, first).unwrap();
+        db.add_dependency(last, middle).unwrap();
+        db.add_dependency(last, first).unwrap();
+        db.close_issue(middle).unwrap();
+        db.close_issue(first).unwrap();
+
+        let out = Reporter::capturing(Verbosity::Normal);
+        unblock(&db, &out, last, first).unwrap();
+        assert!(!out.output().contains("still blocked"));
+        assert!(db
+            .list_ready_issues(false)
+            .unwrap()
+            .iter()
+            .any(|i| i.id == last));
+    }
+
+    #[test]
+    fn test_swap_reverses_edge() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        db.add_dependency(a, b).unwrap();
+        let out = Reporter::capturing(Verbosity::Normal);
+
+        swap(&db, &out, a, b).unwrap();
+        assert_eq!(db.list_dependencies().unwrap(), vec![(b, a)]);
+        assert!(out
+            .output()
+            .contains(&format!("#{} is now blocked by #{}", b, a)));
+
+        let c = db.create_issue("C", None, "medium").unwrap();
+        let err = swap(&db, &Reporter::default(), a, c).unwrap_err();
+        assert!(err.to_string().contains("No dependency"));
+        assert!(swap(&db, &Reporter::default(), a, 999).is_err());
+    }
+
+    #[test]
+    fn test_unblock_all() {
+        let (db, _dir) = setup_test_db();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        db.add_dependency(blocked, a).unwrap();
+        db.add_dependency(blocked, b).unwrap();
+        let out = Reporter::capturing(Verbosity::Verbose);
+
+        unblock_all(&db, &out, blocked).unwrap();
+        assert!(db.get_blockers(blocked).unwrap().is_empty());
+        let output = out.output();
+        assert!(output.contains(&format!("Removed 2 blocker(s) from #{}", blocked)));
+        assert!(output.contains(&format!("blocked_id={} blocker_id={}", blocked, a)));
+        assert!(output.contains(&format!("blocked_id={} blocker_id={}", blocked, b)));
+
+        assert!(unblock_all(&db, &Reporter::default(), 999).is_err());
+    }
+
     // List blocked tests
+    #[test]
+    fn test_dup_does_not_block() {
+        let (db, _dir) = setup_test_db();
+        let original = db.create_issue("Original", None, "medium").unwrap();
+        let dup_id = db.create_issue("Copy", None, "medium").unwrap();
+
+        dup(&db, &Reporter::default(), dup_id, original).unwrap();
+        // Linking again is a no-op
+        dup(&db, &Reporter::default(), dup_id, original).unwrap();
+
+        assert_eq!(db.get_duplicate_of(dup_id).unwrap(), vec![original]);
+        assert!(db.list_blocked_with_meta().unwrap().is_empty());
+        assert!(dup(&db, &Reporter::default(), dup_id, 999).is_err());
+    }
+
+    #[test]
+    fn test_render_impact() {
+        let (db, _dir) = setup_test_db();
+        let root = db.create_issue("Root", None, "medium").unwrap();
+        let middle = db.create_issue("Middle", None, "medium").unwrap();
+        let target = db.create_issue("Target", None, "medium").unwrap();
+        db.add_dependency(middle, root).unwrap();
+        db.add_dependency(target, middle).unwrap();
+
+        assert_eq!(
+            render_impact(&db, middle, target).unwrap(),
+            format!("Finishing #{} would unblock #{}\n", middle, target)
+        );
+        assert_eq!(
+            render_impact(&db, root, target).unwrap(),
+            format!(
+                "No: #{target} would still be blocked by #{middle}\n\
+                 #{root} blocks it only indirectly: #{root} -> #{middle} -> #{target}\n"
+            )
+        );
+        assert_eq!(
+            render_impact(&db, target, root).unwrap(),
+            format!("#{} is not blocked\n", root)
+        );
+        assert!(render_impact(&db, 999, target).is_err());
+    }
+
+    #[test]
+    fn test_dup_then_block_still_blocks() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+        let out = Reporter::capturing(Verbosity::Normal);
+
+        dup(&db, &out, first, second).unwrap();
+        block(&db, &out, first, second).unwrap();
+        dup(&db, &out, first, second).unwrap();
+        assert_eq!(db.get_blockers(first).unwrap(), vec![second]);
+        assert!(out.output().ends_with(&format!(
+            "Issue #{first} is already a duplicate of #{second}\n"
+        )));
+        assert!(!out.output().contains("Dependency already exists"));
+    }
+
+    #[test]
+    fn test_after_and_block_coexist() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+        let third = db.create_issue("Third", None, "medium").unwrap();
+        let out = Reporter::capturing(Verbosity::Normal);
+
+        // after then block
+        after(&db, &out, second, first).unwrap();
+        block(&db, &out, second, first).unwrap();
+        // block then after
+        block(&db, &out, third, first).unwrap();
+        after(&db, &out, third, first).unwrap();
+        assert!(!out.output().contains("already"));
+
+        for issue in [second, third] {
+            assert_eq!(db.get_blockers(issue).unwrap(), vec![first]);
+            assert_eq!(db.count_open_predecessors(issue).unwrap(), 1);
+        }
+    }
+
+    #[test]
+    fn test_after_does_not_block() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+        let out = Reporter::capturing(Verbosity::Normal);
+
+        after(&db, &out, second, first).unwrap();
+        after(&db, &out, second, first).unwrap();
+        assert_eq!(
+            out.output(),
+            format!(
+                "Issue #{second} should now come after #{first}\n\
+                 Issue #{second} already comes after #{first}\n"
+            )
+        );
+
+        assert_eq!(db.count_open_predecessors(second).unwrap(), 1);
+        assert!(db.list_blocked_with_meta().unwrap().is_empty());
+        assert!(after(&db, &out, second, 999).is_err());
+    }
+
+    #[test]
+    fn test_list_all() {
+        let (db, _dir) = setup_test_db();
+        list_all(&db, OutputFormat::Human).unwrap();
+
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        db.add_dependency(c, b).unwrap();
+        db.add_dependency(b, a).unwrap();
+        db.add_dependency(c, a).unwrap();
+        // Only blocking edges are listed
+        db.add_dependency_of_kind(a, c, "after").unwrap();
+        list_all(&db, OutputFormat::Human).unwrap();
+
+        assert_eq!(
+            render_all(&db, OutputFormat::Human).unwrap(),
+            format!(
+                "#{b} ← #{a}\n#{c} ← #{a}\n#{c} ← #{b}\n",
+                a = a,
+                b = b,
+                c = c
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_components() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(render_components(&db).unwrap(), "No dependencies.\n");
+
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        let d = db.create_issue("D", None, "medium").unwrap();
+        db.add_dependency(b, a).unwrap();
+        db.add_dependency(d, c).unwrap();
+
+        assert_eq!(
+            render_components(&db).unwrap(),
+            format!(
+                "Cluster 1 (2 issues): #{a}, #{b}\n\
+                 Cluster 2 (2 issues): #{c}, #{d}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_levels() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(render_levels(&db).unwrap(), "No open issues.\n");
+
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+        db.add_dependency(second, first).unwrap();
+
+        assert_eq!(
+            render_levels(&db).unwrap(),
+            format!(
+                "Level 0:\n  #{:<4} First\nLevel 1:\n  #{:<4} Second\n",
+                first, second
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_bottlenecks() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(
+            render_bottlenecks(&db, 10).unwrap(),
+            "No open issues are blocking others.\n"
+        );
+
+        let hub = db.create_issue("Hub", None, "medium").unwrap();
+        let mid = db.create_issue("Mid", None, "medium").unwrap();
+        let last = db.create_issue("Last", None, "medium").unwrap();
+        db.add_dependency(mid, hub).unwrap();
+        db.add_dependency(last, mid).unwrap();
+
+        assert_eq!(
+            render_bottlenecks(&db, 10).unwrap(),
+            format!("#{:<4} blocks 2   Hub\n#{:<4} blocks 1   Mid\n", hub, mid)
+        );
+        assert_eq!(render_bottlenecks(&db, 1).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn test_render_all_formats() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(
+            render_all(&db, OutputFormat::Human).unwrap(),
+            "No dependencies.\n"
+        );
+        assert_eq!(render_all(&db, OutputFormat::Json).unwrap(), "[]\n");
+
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        db.add_dependency(b, a).unwrap();
+
+        assert_eq!(
+            render_all(&db, OutputFormat::Human).unwrap(),
+            format!("#{} ← #{}\n", b, a)
+        );
+        let json: Vec<ExportedDependency> =
+            serde_json::from_str(&render_all(&db, OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            vec![ExportedDependency {
+                blocked_id: b,
+                blocker_id: a,
+                kind: "blocks".to_string(),
+            }]
+        );
+        assert_eq!(
+            render_all(&db, OutputFormat::Csv).unwrap(),
+            format!("blocked_id,blocker_id\n{},{}\n", b, a)
+        );
+    }
+
+    #[test]
+    fn test_normalize_since() {
+        assert_eq!(
+            normalize_since("2024-03-01").unwrap(),
+            "2024-03-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            normalize_since("2024-03-01T12:00:00Z").unwrap(),
+            "2024-03-01T12:00:00Z"
+        );
+        assert!(normalize_since("last week").is_err());
+    }
+
+    #[test]
+    fn test_list_long_blocked() {
+        let (db, _dir) = setup_test_db();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        db.add_dependency(blocked, blocker).unwrap();
+
+        list_long_blocked(&db, "2000-01-01").unwrap();
+        assert!(list_long_blocked(&db, "soon").is_err());
+    }
+
     #[test]
     fn test_list_blocked_empty() {
         let (db, _dir) = setup_test_db();
 
-        list_blocked(&db).unwrap();
-        let blocked = db.list_blocked_issues().unwrap();
+        list_blocked(&db, Some(TITLE_WIDTH)).unwrap();
+        let blocked = db.list_blocked_with_meta().unwrap();
         assert!(blocked.is_empty());
     }
 
@@ -194,10 +1314,10 @@ mod tests {
         let issue2 = db.create_issue("Blocker", None, "medium").unwrap();
         db.add_dependency(issue1, issue2).unwrap();
 
-        list_blocked(&db).unwrap();
-        let blocked = db.list_blocked_issues().unwrap();
+        list_blocked(&db, Some(TITLE_WIDTH)).unwrap();
+        let blocked = db.list_blocked_with_meta().unwrap();
         assert_eq!(blocked.len(), 1);
-        assert_eq!(blocked[0].id, issue1);
+        assert_eq!(blocked[0].issue.id, issue1);
     }
 
     #[test]
@@ -209,7 +1329,7 @@ mod tests {
         db.add_dependency(blocked, blocker1).unwrap();
         db.add_dependency(blocked, blocker2).unwrap();
 
-        list_blocked(&db).unwrap();
+        list_blocked(&db, Some(TITLE_WIDTH)).unwrap();
         let blockers = db.get_blockers(blocked).unwrap();
         assert_eq!(blockers.len(), 2);
         assert!(blockers.contains(&blocker1));
@@ -221,8 +1341,8 @@ mod tests {
     fn test_list_ready_empty() {
         let (db, _dir) = setup_test_db();
 
-        list_ready(&db).unwrap();
-        let ready = db.list_ready_issues().unwrap();
+        list_ready(&db, false, None, false, Some(TITLE_WIDTH)).unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert!(ready.is_empty());
     }
 
@@ -231,8 +1351,8 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Ready issue", None, "medium").unwrap();
 
-        list_ready(&db).unwrap();
-        let ready = db.list_ready_issues().unwrap();
+        list_ready(&db, false, None, false, Some(TITLE_WIDTH)).unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert_eq!(ready.len(), 1);
         assert_eq!(ready[0].id, id);
     }
@@ -244,7 +1364,7 @@ mod tests {
         let blocker = db.create_issue("Blocker", None, "medium").unwrap();
         db.add_dependency(blocked, blocker).unwrap();
 
-        let ready = db.list_ready_issues().unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert!(!ready.iter().any(|i| i.id == blocked));
         assert!(ready.iter().any(|i| i.id == blocker));
     }
@@ -255,10 +1375,32 @@ mod tests {
         let issue = db.create_issue("Closed issue", None, "medium").unwrap();
         db.close_issue(issue).unwrap();
 
-        let ready = db.list_ready_issues().unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert!(!ready.iter().any(|i| i.id == issue));
     }
 
+    #[test]
+    fn test_list_ready_exclude_closed_parents() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        let open_parent = db.create_issue("Open parent", None, "medium").unwrap();
+        let other_child = db
+            .create_subissue(open_parent, "Other child", None, "medium")
+            .unwrap();
+        db.close_issue(parent).unwrap();
+
+        list_ready(&db, true, None, false, Some(TITLE_WIDTH)).unwrap();
+        let ready = db.list_ready_issues(true).unwrap();
+        assert!(!ready.iter().any(|i| i.id == child));
+        assert!(ready.iter().any(|i| i.id == other_child));
+        assert!(ready.iter().any(|i| i.id == open_parent));
+
+        // Without the option the orphaned child is still ready
+        let ready = db.list_ready_issues(false).unwrap();
+        assert!(ready.iter().any(|i| i.id == child));
+    }
+
     // Integration tests
     #[test]
     fn test_block_unblock_roundtrip() {
@@ -266,13 +1408,13 @@ mod tests {
         let issue1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        block(&db, issue1, issue2).unwrap();
-        let blocked = db.list_blocked_issues().unwrap();
-        assert!(blocked.iter().any(|i| i.id == issue1));
+        block(&db, &Reporter::default(), issue1, issue2).unwrap();
+        let blocked = db.list_blocked_with_meta().unwrap();
+        assert!(blocked.iter().any(|m| m.issue.id == issue1));
 
-        unblock(&db, issue1, issue2).unwrap();
-        let blocked = db.list_blocked_issues().unwrap();
-        assert!(!blocked.iter().any(|i| i.id == issue1));
+        unblock(&db, &Reporter::default(), issue1, issue2).unwrap();
+        let blocked = db.list_blocked_with_meta().unwrap();
+        assert!(!blocked.iter().any(|m| m.issue.id == issue1));
     }
 
     #[test]
@@ -283,35 +1425,35 @@ mod tests {
         db.add_dependency(blocked, blocker).unwrap();
 
         // Blocked issue should not be ready
-        let ready = db.list_ready_issues().unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert!(!ready.iter().any(|i| i.id == blocked));
 
         // Close the blocker
         db.close_issue(blocker).unwrap();
 
         // Now blocked issue should be ready
-        let ready = db.list_ready_issues().unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert!(ready.iter().any(|i| i.id == blocked));
     }
 
     proptest! {
         #[test]
-        fn truncate_respects_limit(s in ".{10,100}", max_chars in 5usize..50) {
-            let result = truncate(&s, max_chars);
-            assert!(result.chars().count() <= max_chars);
+        fn truncate_respects_limit(s in ".{10,100}", max_width in 5usize..50) {
+            let result = truncate(&s, max_width);
+            assert!(result.width() <= max_width);
         }
 
         #[test]
-        fn prop_block_creates_dependency(title1 in "[a-zA-Z ]{1,20}", title2 in "[a-zA-Z ]{1,20}") {
+        fn prop_block_creates_dependency(title1 in "[a-zA-Z][a-zA-Z ]{0,19}", title2 in "[a-zA-Z][a-zA-Z ]{0,19}") {
             let (db, _dir) = setup_test_db();
             let issue1 = db.create_issue(&title1, None, "medium").unwrap();
             let issue2 = db.create_issue(&title2, None, "medium").unwrap();
 
-            block(&db, issue1, issue2).unwrap();
+            block(&db, &Reporter::default(), issue1, issue2).unwrap();
             let blockers = db.get_blockers(issue1).unwrap();
             prop_assert!(blockers.contains(&issue2));
-            let blocked = db.list_blocked_issues().unwrap();
-            prop_assert!(blocked.iter().any(|i| i.id == issue1));
+            let blocked = db.list_blocked_with_meta().unwrap();
+            prop_assert!(blocked.iter().any(|m| m.issue.id == issue1));
         }
     }
 }
@@ -0,0 +1,182 @@
+use anyhow::Result;
+
+use crate::db::Database;
+
+/// Collapse sorted ids into "2, 5-7" style ranges.
+fn format_id_ranges(ids: &[i64]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < ids.len() {
+        let start = ids[i];
+        while i + 1 < ids.len() && ids[i + 1] == ids[i] + 1 {
+            i += 1;
+        }
+        if ids[i] == start {
+            ranges.push(format!("#{}", start));
+        } else {
+            ranges.push(format!("#{}-#{}", start, ids[i]));
+        }
+        i += 1;
+    }
+    ranges.join(", ")
+}
+
+pub fn run(db: &Database, fix: bool) -> Result<()> {
+    // Ids of deleted issues are never reused; listing them is informational only
+    let missing = db.missing_ids(db.last_issue_id()?)?;
+    if !missing.is_empty() {
+        println!(
+            "Unused issue ids (deleted, never reused): {}",
+            format_id_ranges(&missing)
+        );
+    }
+
+    let dangling = db.find_dangling_dependencies()?;
+    let stale_flags = db.dry_run(|| db.recompute_blocked())?;
+
+    if dangling.is_empty() && stale_flags == 0 {
+        println!("No problems found.");
+        return Ok(());
+    }
+
+    if !dangling.is_empty() {
+        println!("Found {} dangling dependency reference(s):", dangling.len());
+        for (blocked, blocker) in &dangling {
+            println!("  #{} blocked by #{}", blocked, blocker);
+        }
+
+        if fix {
+            let removed = db.delete_dangling_dependencies()?;
+            println!("Removed {} dangling dependency reference(s).", removed);
+        } else {
+            println!("Run 'chainlink doctor --fix' to remove them.");
+        }
+    }
+
+    if stale_flags > 0 {
+        println!(
+            "Found {} issue(s) whose cached blocked flag disagrees with their dependencies.",
+            stale_flags
+        );
+        if fix {
+            let fixed = db.recompute_blocked()?;
+            println!("Recomputed {} blocked flag(s).", fixed);
+        } else {
+            println!("Run 'chainlink doctor --fix' to recompute them.");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    /// Insert a dependency row behind the foreign key checks, as a corrupted DB would have.
+    fn insert_dangling_edge(dir: &tempfile::TempDir, blocked: i64, blocker: i64) {
+        let conn = rusqlite::Connection::open(dir.path().join("test.db")).unwrap();
+        conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+        conn.execute(
+            "INSERT INTO dependencies (blocker_id, blocked_id) VALUES (?1, ?2)",
+            [blocker, blocked],
+        )
+        .unwrap();
+    }
+
+    /// Overwrite an issue's cached blocked flag, as a crash between writes could leave it.
+    fn corrupt_blocked_flag(dir: &tempfile::TempDir, id: i64, blocked: bool) {
+        let conn = rusqlite::Connection::open(dir.path().join("test.db")).unwrap();
+        conn.execute(
+            "UPDATE issues SET is_blocked = ?1 WHERE id = ?2",
+            rusqlite::params![blocked, id],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_format_id_ranges() {
+        assert_eq!(format_id_ranges(&[]), "");
+        assert_eq!(format_id_ranges(&[2]), "#2");
+        assert_eq!(format_id_ranges(&[2, 5, 6, 7, 9]), "#2, #5-#7, #9");
+    }
+
+    #[test]
+    fn test_doctor_reports_missing_ids() {
+        let (db, _dir) = setup_test_db();
+        for title in ["One", "Two", "Three"] {
+            db.create_issue(title, None, "medium").unwrap();
+        }
+        db.delete_issue(2).unwrap();
+
+        assert_eq!(
+            db.missing_ids(db.last_issue_id().unwrap()).unwrap(),
+            vec![2]
+        );
+        run(&db, false).unwrap();
+    }
+
+    #[test]
+    fn test_doctor_clean_db() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("Issue", None, "medium").unwrap();
+
+        run(&db, false).unwrap();
+        assert!(db.find_dangling_dependencies().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_doctor_reports_without_fix() {
+        let (db, dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+        insert_dangling_edge(&dir, id, 4242);
+
+        run(&db, false).unwrap();
+        let dangling = db.find_dangling_dependencies().unwrap();
+        assert_eq!(dangling, vec![(id, 4242)]);
+    }
+
+    #[test]
+    fn test_doctor_fix_removes_dangling() {
+        let (db, dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        db.add_dependency(id, blocker).unwrap();
+        insert_dangling_edge(&dir, 4242, id);
+
+        run(&db, true).unwrap();
+        assert!(db.find_dangling_dependencies().unwrap().is_empty());
+        assert_eq!(db.get_blockers(id).unwrap(), vec![blocker]);
+    }
+
+    #[test]
+    fn test_doctor_fix_recomputes_blocked_flags() {
+        let (db, dir) = setup_test_db();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        db.add_dependency(blocked, blocker).unwrap();
+        corrupt_blocked_flag(&dir, blocked, false);
+        corrupt_blocked_flag(&dir, blocker, true);
+
+        run(&db, false).unwrap();
+        assert_eq!(db.dry_run(|| db.recompute_blocked()).unwrap(), 2);
+
+        run(&db, true).unwrap();
+        assert_eq!(db.recompute_blocked().unwrap(), 0);
+        let blocked_ids: Vec<i64> = db
+            .list_blocked_with_meta()
+            .unwrap()
+            .iter()
+            .map(|m| m.issue.id)
+            .collect();
+        assert_eq!(blocked_ids, vec![blocked]);
+    }
+}
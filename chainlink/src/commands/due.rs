@@ -0,0 +1,70 @@
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+
+use crate::db::Database;
+use crate::output::Reporter;
+
+pub fn run(db: &Database, out: &Reporter, id: i64, date: Option<&str>) -> Result<()> {
+    let due = match date {
+        Some(d) => match NaiveDate::parse_from_str(d, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => bail!("Invalid due date '{}'. Use YYYY-MM-DD", d),
+        },
+        None => None,
+    };
+
+    if !db.set_due_date(id, due)? {
+        bail!("Issue #{} not found", id);
+    }
+
+    match due {
+        Some(d) => out.info(&format!("Issue #{} is due {}", id, d)),
+        None => out.info(&format!("Cleared due date for issue #{}", id)),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_set_and_clear_due_date() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        run(&db, &Reporter::default(), id, Some("2026-03-01")).unwrap();
+        assert_eq!(
+            db.get_due_date(id).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+        );
+
+        run(&db, &Reporter::default(), id, None).unwrap();
+        assert_eq!(db.get_due_date(id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_due_date_rejected() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        assert!(run(&db, &Reporter::default(), id, Some("next week")).is_err());
+        assert!(run(&db, &Reporter::default(), id, Some("2026-02-30")).is_err());
+        assert_eq!(db.get_due_date(id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_due_nonexistent_issue() {
+        let (db, _dir) = setup_test_db();
+        assert!(run(&db, &Reporter::default(), 99999, Some("2026-03-01")).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::{bail, Result};
+
+use crate::db::Database;
+use crate::output::Reporter;
+
+pub fn run(db: &Database, out: &Reporter, id: i64, estimate: Option<i64>) -> Result<()> {
+    if let Some(e) = estimate {
+        if e < 0 {
+            bail!("Estimate must not be negative");
+        }
+    }
+
+    if !db.set_estimate(id, estimate)? {
+        bail!("Issue #{} not found", id);
+    }
+
+    match estimate {
+        Some(e) => out.info(&format!("Set estimate for issue #{} to {}", id, e)),
+        None => out.info(&format!("Cleared estimate for issue #{}", id)),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_set_estimate() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        run(&db, &Reporter::default(), id, Some(3)).unwrap();
+        assert_eq!(db.get_estimate(id).unwrap(), Some(3));
+
+        run(&db, &Reporter::default(), id, None).unwrap();
+        assert_eq!(db.get_estimate(id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_negative_estimate_rejected() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        assert!(run(&db, &Reporter::default(), id, Some(-1)).is_err());
+        assert_eq!(db.get_estimate(id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_estimate_nonexistent_issue() {
+        let (db, _dir) = setup_test_db();
+        let result = run(&db, &Reporter::default(), 99999, Some(1));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+}
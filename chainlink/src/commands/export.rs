@@ -1,10 +1,16 @@
 use anyhow::{Context, Result};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{self, Write};
 
-use crate::db::Database;
+use crate::db::{Database, IssueFilter};
 use crate::models::Issue;
+use crate::utils::csv_field;
+
+/// Format version written to `ExportData::version`. Version 2 added assignee,
+/// due date, estimate and dependency kinds; importers accept anything up to this.
+pub const EXPORT_VERSION: i32 = 2;
 
 #[derive(Serialize, Deserialize)]
 pub struct ExportedIssue {
@@ -16,6 +22,14 @@ pub struct ExportedIssue {
     pub parent_id: Option<i64>,
     pub labels: Vec<String>,
     pub comments: Vec<ExportedComment>,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub estimate: Option<i64>,
+    #[serde(default)]
+    pub url: Option<String>,
     pub created_at: String,
     pub updated_at: String,
     pub closed_at: Option<String>,
@@ -27,11 +41,28 @@ pub struct ExportedComment {
     pub created_at: String,
 }
 
+/// A dependency edge between two exported issues, by their exported ids.
+/// `kind` is one of `DEPENDENCY_KINDS`; exports before version 2 only had "blocks".
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExportedDependency {
+    pub blocked_id: i64,
+    pub blocker_id: i64,
+    #[serde(default = "blocks_kind")]
+    pub kind: String,
+}
+
+fn blocks_kind() -> String {
+    "blocks".to_string()
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ExportData {
     pub version: i32,
+    /// Newest `updated_at` among the exported issues, so unchanged data exports identically
     pub exported_at: String,
     pub issues: Vec<ExportedIssue>,
+    #[serde(default)]
+    pub dependencies: Vec<ExportedDependency>,
 }
 
 fn export_issue(db: &Database, issue: &Issue) -> Result<ExportedIssue> {
@@ -53,29 +84,103 @@ fn export_issue(db: &Database, issue: &Issue) -> Result<ExportedIssue> {
                 created_at: c.created_at.to_rfc3339(),
             })
             .collect(),
+        assignee: db.get_assignee(issue.id)?,
+        due_date: db.get_due_date(issue.id)?,
+        estimate: db.get_estimate(issue.id)?,
+        url: db.get_url(issue.id)?,
         created_at: issue.created_at.to_rfc3339(),
         updated_at: issue.updated_at.to_rfc3339(),
         closed_at: issue.closed_at.map(|dt| dt.to_rfc3339()),
     })
 }
 
-pub fn run_json(db: &Database, output_path: Option<&str>) -> Result<()> {
-    let issues = db.list_issues(Some("all"), None, None)?;
+/// Collect `root` and every issue beneath it, parents before children.
+fn collect_subtree(db: &Database, root: i64, out: &mut Vec<Issue>) -> Result<()> {
+    out.push(db.require_issue(root)?);
+    let mut index = out.len() - 1;
+    while index < out.len() {
+        let children = db.get_subissues(out[index].id)?;
+        out.extend(children);
+        index += 1;
+    }
+    Ok(())
+}
 
-    let exported: Vec<ExportedIssue> = issues
-        .iter()
-        .map(|i| export_issue(db, i))
-        .collect::<Result<Vec<_>>>()?;
+/// Build export data for the whole database, or only the subtree under `root`,
+/// keeping only issues that match `filter` (a `None` status exports every status).
+/// Dependency edges of every kind are kept only when both ends are part of the export.
+/// Issues are sorted by id and edges by (blocked, blocker) so the output is diffable.
+pub fn collect(db: &Database, root: Option<i64>, filter: &IssueFilter<'_>) -> Result<ExportData> {
+    let filter = IssueFilter {
+        status: filter.status.or(Some("all")),
+        ..*filter
+    };
+    let mut exported: Vec<ExportedIssue> = Vec::new();
+    match root {
+        Some(root) => {
+            let mut issues = Vec::new();
+            collect_subtree(db, root, &mut issues)?;
+            let matching = db
+                .iter_issues(filter)
+                .map(|issue| issue.map(|i| i.id))
+                .collect::<std::result::Result<std::collections::HashSet<i64>, _>>()?;
+            for issue in issues.iter().filter(|i| matching.contains(&i.id)) {
+                exported.push(export_issue(db, issue)?);
+            }
+        }
+        None => {
+            for issue in db.iter_issues(filter) {
+                exported.push(export_issue(db, &issue?)?);
+            }
+        }
+    }
 
-    let data = ExportData {
-        version: 1,
-        exported_at: chrono::Utc::now().to_rfc3339(),
+    exported.sort_by_key(|i| i.id);
+
+    let ids: std::collections::HashSet<i64> = exported.iter().map(|i| i.id).collect();
+    // list_dependencies_of_all_kinds already orders by (blocked, blocker, kind)
+    let dependencies = db
+        .list_dependencies_of_all_kinds()?
+        .into_iter()
+        .filter(|(blocked, blocker, _)| ids.contains(blocked) && ids.contains(blocker))
+        .map(|(blocked_id, blocker_id, kind)| ExportedDependency {
+            blocked_id,
+            blocker_id,
+            kind,
+        })
+        .collect();
+
+    let exported_at = exported
+        .iter()
+        .map(|i| i.updated_at.as_str())
+        .max()
+        .unwrap_or("1970-01-01T00:00:00+00:00")
+        .to_string();
+
+    Ok(ExportData {
+        version: EXPORT_VERSION,
+        exported_at,
         issues: exported,
-    };
+        dependencies,
+    })
+}
+
+/// The file an export goes to; `None` and "-" both mean stdout.
+fn to_file(output_path: Option<&str>) -> Option<&str> {
+    output_path.filter(|path| *path != "-")
+}
+
+pub fn run_json(
+    db: &Database,
+    output_path: Option<&str>,
+    root: Option<i64>,
+    filter: &IssueFilter<'_>,
+) -> Result<()> {
+    let data = collect(db, root, filter)?;
 
     let json = serde_json::to_string_pretty(&data)?;
 
-    match output_path {
+    match to_file(output_path) {
         Some(path) => {
             fs::write(path, json).context("Failed to write export file")?;
             eprintln!("Exported {} issues to {}", data.issues.len(), path);
@@ -88,8 +193,69 @@ pub fn run_json(db: &Database, output_path: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-pub fn run_markdown(db: &Database, output_path: Option<&str>) -> Result<()> {
-    let issues = db.list_issues(Some("all"), None, None)?;
+const CSV_HEADER: &str =
+    "id,title,status,priority,parent_id,labels,blocked_by,created_at,updated_at,closed_at";
+
+/// One row per issue; labels and blockers are `;`-separated within their column.
+pub fn render_csv(data: &ExportData) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for issue in &data.issues {
+        let blocked_by: Vec<String> = data
+            .dependencies
+            .iter()
+            .filter(|d| d.blocked_id == issue.id && d.kind == "blocks")
+            .map(|d| d.blocker_id.to_string())
+            .collect();
+        let fields = [
+            issue.id.to_string(),
+            csv_field(&issue.title),
+            csv_field(&issue.status),
+            csv_field(&issue.priority),
+            issue.parent_id.map(|p| p.to_string()).unwrap_or_default(),
+            csv_field(&issue.labels.join(";")),
+            blocked_by.join(";"),
+            issue.created_at.clone(),
+            issue.updated_at.clone(),
+            issue.closed_at.clone().unwrap_or_default(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+pub fn run_csv(
+    db: &Database,
+    output_path: Option<&str>,
+    root: Option<i64>,
+    filter: &IssueFilter<'_>,
+) -> Result<()> {
+    let data = collect(db, root, filter)?;
+    let csv = render_csv(&data);
+
+    match to_file(output_path) {
+        Some(path) => {
+            fs::write(path, csv).context("Failed to write export file")?;
+            eprintln!("Exported {} issues to {}", data.issues.len(), path);
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            write!(stdout, "{}", csv)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn run_markdown(
+    db: &Database,
+    output_path: Option<&str>,
+    filter: &IssueFilter<'_>,
+) -> Result<()> {
+    let issues = db.list_issues_filtered(&IssueFilter {
+        status: filter.status.or(Some("all")),
+        ..*filter
+    })?;
     let mut md = String::new();
 
     md.push_str("# Chainlink Issues Export\n\n");
@@ -124,7 +290,7 @@ pub fn run_markdown(db: &Database, output_path: Option<&str>) -> Result<()> {
         }
     }
 
-    match output_path {
+    match to_file(output_path) {
         Some(path) => {
             fs::write(path, md).context("Failed to write export file")?;
             eprintln!("Exported {} issues to {}", issues.len(), path);
@@ -252,11 +418,16 @@ mod tests {
         db.create_issue("Issue 2", Some("Description"), "low")
             .unwrap();
         let output_path = dir.path().join("export.json");
-        let result = run_json(&db, Some(output_path.to_str().unwrap()));
+        let result = run_json(
+            &db,
+            Some(output_path.to_str().unwrap()),
+            None,
+            &IssueFilter::default(),
+        );
         assert!(result.is_ok());
         let content = fs::read_to_string(&output_path).unwrap();
         let data: ExportData = serde_json::from_str(&content).unwrap();
-        assert_eq!(data.version, 1);
+        assert_eq!(data.version, EXPORT_VERSION);
         assert_eq!(data.issues.len(), 2);
     }
 
@@ -264,7 +435,12 @@ mod tests {
     fn test_run_json_empty_database() {
         let (db, dir) = setup_test_db();
         let output_path = dir.path().join("export.json");
-        let result = run_json(&db, Some(output_path.to_str().unwrap()));
+        let result = run_json(
+            &db,
+            Some(output_path.to_str().unwrap()),
+            None,
+            &IssueFilter::default(),
+        );
         assert!(result.is_ok());
         let content = fs::read_to_string(&output_path).unwrap();
         let data: ExportData = serde_json::from_str(&content).unwrap();
@@ -276,7 +452,11 @@ mod tests {
         let (db, dir) = setup_test_db();
         db.create_issue("Issue 1", None, "high").unwrap();
         let output_path = dir.path().join("export.md");
-        let result = run_markdown(&db, Some(output_path.to_str().unwrap()));
+        let result = run_markdown(
+            &db,
+            Some(output_path.to_str().unwrap()),
+            &IssueFilter::default(),
+        );
         assert!(result.is_ok());
         let content = fs::read_to_string(&output_path).unwrap();
         assert!(content.contains("# Chainlink Issues Export"));
@@ -289,7 +469,12 @@ mod tests {
         let closed_id = db.create_issue("Closed issue", None, "medium").unwrap();
         db.close_issue(closed_id).unwrap();
         let output_path = dir.path().join("export.md");
-        run_markdown(&db, Some(output_path.to_str().unwrap())).unwrap();
+        run_markdown(
+            &db,
+            Some(output_path.to_str().unwrap()),
+            &IssueFilter::default(),
+        )
+        .unwrap();
         let content = fs::read_to_string(&output_path).unwrap();
         assert!(content.contains("## Open Issues"));
         assert!(content.contains("## Closed Issues"));
@@ -303,12 +488,260 @@ mod tests {
             .unwrap();
         db.add_label(id, "バグ").unwrap();
         let output_path = dir.path().join("export.json");
-        run_json(&db, Some(output_path.to_str().unwrap())).unwrap();
+        run_json(
+            &db,
+            Some(output_path.to_str().unwrap()),
+            None,
+            &IssueFilter::default(),
+        )
+        .unwrap();
         let content = fs::read_to_string(&output_path).unwrap();
         let data: ExportData = serde_json::from_str(&content).unwrap();
         assert_eq!(data.issues[0].title, "Test 🐛");
     }
 
+    #[test]
+    fn test_subtree_export_roundtrip() {
+        let (db, dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let child = db.create_subissue(epic, "Child", None, "medium").unwrap();
+        let grandchild = db
+            .create_subissue(child, "Grandchild", None, "low")
+            .unwrap();
+        let sibling = db.create_subissue(epic, "Sibling", None, "medium").unwrap();
+        let outside = db.create_issue("Outside", None, "medium").unwrap();
+        db.add_label(child, "core").unwrap();
+        db.add_dependency(grandchild, sibling).unwrap();
+        // Edge crossing the subtree boundary is dropped
+        db.add_dependency(child, outside).unwrap();
+        db.close_issue(sibling).unwrap();
+
+        let output_path = dir.path().join("subtree.json");
+        run_json(
+            &db,
+            Some(output_path.to_str().unwrap()),
+            Some(epic),
+            &IssueFilter::default(),
+        )
+        .unwrap();
+
+        let data: ExportData =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        let ids: Vec<i64> = data.issues.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![epic, child, grandchild, sibling]);
+        assert_eq!(
+            data.dependencies,
+            vec![ExportedDependency {
+                blocked_id: grandchild,
+                blocker_id: sibling,
+                kind: "blocks".to_string(),
+            }]
+        );
+
+        let (fresh, _fresh_dir) = setup_test_db();
+        super::super::import::run_json(&fresh, &output_path, false).unwrap();
+
+        let imported = fresh.list_issues(Some("all"), None, None).unwrap();
+        assert_eq!(imported.len(), 4);
+        let find = |title: &str| imported.iter().find(|i| i.title == title).unwrap();
+        let (new_epic, new_child, new_grandchild, new_sibling) = (
+            find("Epic"),
+            find("Child"),
+            find("Grandchild"),
+            find("Sibling"),
+        );
+        assert_eq!(new_epic.parent_id, None);
+        assert_eq!(new_child.parent_id, Some(new_epic.id));
+        assert_eq!(new_grandchild.parent_id, Some(new_child.id));
+        assert_eq!(new_sibling.status, "closed");
+        assert_eq!(fresh.get_labels(new_child.id).unwrap(), vec!["core"]);
+        assert_eq!(
+            fresh.list_dependencies().unwrap(),
+            vec![(new_grandchild.id, new_sibling.id)]
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_keeps_metadata_and_dependency_kinds() {
+        let (db, dir) = setup_test_db();
+        let first = db.create_issue("First", None, "high").unwrap();
+        let later = db.create_issue("Later", None, "medium").unwrap();
+        let copy = db.create_issue("Copy of first", None, "low").unwrap();
+        db.add_label(later, "ui").unwrap();
+        db.add_comment(later, "needs a design pass").unwrap();
+        db.set_assignee(later, Some("sam")).unwrap();
+        db.set_due_date(later, NaiveDate::from_ymd_opt(2030, 5, 1))
+            .unwrap();
+        db.set_estimate(later, Some(3)).unwrap();
+        db.set_url(later, Some("https://example.com/tickets/7"))
+            .unwrap();
+        db.add_dependency_of_kind(later, first, "after").unwrap();
+        db.add_dependency_of_kind(copy, first, "duplicates")
+            .unwrap();
+        let comment_at = db.get_comments(later).unwrap()[0].created_at;
+
+        let data = collect(&db, None, &IssueFilter::default()).unwrap();
+        assert_eq!(data.version, EXPORT_VERSION);
+        let output_path = dir.path().join("export.json");
+        fs::write(&output_path, serde_json::to_string(&data).unwrap()).unwrap();
+
+        // Occupy the low ids so imported ids have to be remapped
+        let (fresh, _fresh_dir) = setup_test_db();
+        for _ in 0..5 {
+            fresh.create_issue("Existing", None, "medium").unwrap();
+        }
+        super::super::import::run_json(&fresh, &output_path, false).unwrap();
+
+        let imported = fresh.list_issues(Some("all"), None, None).unwrap();
+        let find = |title: &str| imported.iter().find(|i| i.title == title).unwrap().id;
+        let (new_first, new_later, new_copy) =
+            (find("First"), find("Later"), find("Copy of first"));
+
+        assert_eq!(fresh.get_labels(new_later).unwrap(), vec!["ui"]);
+        let comments = fresh.get_comments(new_later).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].content, "needs a design pass");
+        assert_eq!(comments[0].created_at, comment_at);
+        assert_eq!(
+            fresh.get_assignee(new_later).unwrap().as_deref(),
+            Some("sam")
+        );
+        assert_eq!(
+            fresh.get_due_date(new_later).unwrap(),
+            NaiveDate::from_ymd_opt(2030, 5, 1)
+        );
+        assert_eq!(fresh.get_estimate(new_later).unwrap(), Some(3));
+        assert_eq!(
+            fresh.get_url(new_later).unwrap().as_deref(),
+            Some("https://example.com/tickets/7")
+        );
+
+        let mut edges = fresh.list_dependencies_of_all_kinds().unwrap();
+        edges.sort();
+        let mut expected = vec![
+            (new_later, new_first, "after".to_string()),
+            (new_copy, new_first, "duplicates".to_string()),
+        ];
+        expected.sort();
+        assert_eq!(edges, expected);
+        assert!(fresh.list_dependencies().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_export_is_byte_stable() {
+        let (db, dir) = setup_test_db();
+        let a = db.create_issue("First", Some("desc"), "high").unwrap();
+        let b = db.create_issue("Second", None, "low").unwrap();
+        let c = db.create_subissue(a, "Child", None, "medium").unwrap();
+        db.add_label(a, "zeta").unwrap();
+        db.add_label(a, "alpha").unwrap();
+        db.add_comment(b, "note").unwrap();
+        db.add_dependency(c, b).unwrap();
+        db.add_dependency(b, a).unwrap();
+
+        let first = dir.path().join("first.json");
+        let second = dir.path().join("second.json");
+        run_json(
+            &db,
+            Some(first.to_str().unwrap()),
+            None,
+            &IssueFilter::default(),
+        )
+        .unwrap();
+        run_json(
+            &db,
+            Some(second.to_str().unwrap()),
+            None,
+            &IssueFilter::default(),
+        )
+        .unwrap();
+        let first = fs::read_to_string(first).unwrap();
+        assert_eq!(first, fs::read_to_string(second).unwrap());
+
+        let data: ExportData = serde_json::from_str(&first).unwrap();
+        let ids: Vec<i64> = data.issues.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![a, b, c]);
+        let edges: Vec<(i64, i64)> = data
+            .dependencies
+            .iter()
+            .map(|d| (d.blocked_id, d.blocker_id))
+            .collect();
+        assert_eq!(edges, vec![(b, a), (c, b)]);
+        assert_eq!(data.issues[0].labels, vec!["alpha", "zeta"]);
+    }
+
+    #[test]
+    fn test_export_filtered_by_priority() {
+        let (db, _dir) = setup_test_db();
+        let high = db.create_issue("Urgent", None, "high").unwrap();
+        let high_blocker = db.create_issue("Also urgent", None, "high").unwrap();
+        let low = db.create_issue("Someday", None, "low").unwrap();
+        let closed = db.create_issue("Done", None, "high").unwrap();
+        db.close_issue(closed).unwrap();
+        db.add_dependency(high, high_blocker).unwrap();
+        // Edge to an issue outside the export is dropped
+        db.add_dependency(high, low).unwrap();
+
+        let filter = IssueFilter {
+            status: Some("open"),
+            priority: Some("high"),
+            ..Default::default()
+        };
+        let data = collect(&db, None, &filter).unwrap();
+        let ids: Vec<i64> = data.issues.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![high, high_blocker]);
+        assert_eq!(
+            data.dependencies,
+            vec![ExportedDependency {
+                blocked_id: high,
+                blocker_id: high_blocker,
+                kind: "blocks".to_string(),
+            }]
+        );
+
+        // No status filter exports every status
+        let all_high = IssueFilter {
+            priority: Some("high"),
+            ..Default::default()
+        };
+        assert_eq!(collect(&db, None, &all_high).unwrap().issues.len(), 3);
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("Plain", None, "high").unwrap();
+        let b = db
+            .create_issue("Has, comma and \"quotes\"", None, "high")
+            .unwrap();
+        db.add_label(b, "api").unwrap();
+        db.add_label(b, "ui").unwrap();
+        db.add_dependency(b, a).unwrap();
+
+        let csv = render_csv(&collect(&db, None, &IssueFilter::default()).unwrap());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with(&format!("{},Plain,open,high,,,,", a)));
+        assert!(lines[2].starts_with(&format!(
+            "{},\"Has, comma and \"\"quotes\"\"\",open,high,,api;ui,{},",
+            b, a
+        )));
+    }
+
+    #[test]
+    fn test_subtree_export_unknown_root() {
+        let (db, dir) = setup_test_db();
+        let output_path = dir.path().join("subtree.json");
+        assert!(run_json(
+            &db,
+            Some(output_path.to_str().unwrap()),
+            Some(42),
+            &IssueFilter::default()
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_export_data_roundtrip() {
         let data = ExportData {
@@ -326,10 +759,15 @@ mod tests {
                     content: "Comment".to_string(),
                     created_at: "2024-01-01T00:00:00Z".to_string(),
                 }],
+                assignee: None,
+                due_date: None,
+                estimate: None,
+                url: None,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-01T00:00:00Z".to_string(),
                 closed_at: None,
             }],
+            dependencies: vec![],
         };
         let json = serde_json::to_string(&data).unwrap();
         let parsed: ExportData = serde_json::from_str(&json).unwrap();
@@ -339,20 +777,20 @@ mod tests {
 
     proptest! {
         #[test]
-        fn prop_export_never_panics(title in "[a-zA-Z0-9 ]{1,50}") {
+        fn prop_export_never_panics(title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,49}") {
             let (db, dir) = setup_test_db();
             db.create_issue(&title, None, "medium").unwrap();
             let output_path = dir.path().join("export.json");
-            let result = run_json(&db, Some(output_path.to_str().unwrap()));
+            let result = run_json(&db, Some(output_path.to_str().unwrap()), None, &IssueFilter::default());
             prop_assert!(result.is_ok());
         }
 
         #[test]
-        fn prop_json_is_valid(title in "[a-zA-Z0-9 ]{1,30}") {
+        fn prop_json_is_valid(title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,29}") {
             let (db, dir) = setup_test_db();
             db.create_issue(&title, None, "medium").unwrap();
             let output_path = dir.path().join("export.json");
-            run_json(&db, Some(output_path.to_str().unwrap())).unwrap();
+            run_json(&db, Some(output_path.to_str().unwrap()), None, &IssueFilter::default()).unwrap();
             let content = fs::read_to_string(&output_path).unwrap();
             let result: Result<ExportData, _> = serde_json::from_str(&content);
             prop_assert!(result.is_ok());
@@ -1,53 +1,275 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
+use std::io::{BufRead, Read};
 use std::path::Path;
 
-use super::export::{ExportData, ExportedIssue};
+use super::export::{ExportData, ExportedIssue, EXPORT_VERSION};
 use crate::db::Database;
+use crate::output::Reporter;
 
-pub fn run_json(db: &Database, input_path: &Path) -> Result<()> {
+/// Import a chainlink JSON export from a file; see `from_json`.
+pub fn run_json(db: &Database, input_path: &Path, continue_on_error: bool) -> Result<()> {
     let content = fs::read_to_string(input_path).context("Failed to read import file")?;
+    from_json(
+        db,
+        content.as_bytes(),
+        &input_path.display().to_string(),
+        continue_on_error,
+    )
+}
+
+/// Import a chainlink JSON export read from `reader`, such as stdin; `source`
+/// names it in the progress message. By default the whole import is one
+/// transaction, so a single bad issue leaves the database untouched. With
+/// `continue_on_error`, issues and edges that fail are skipped and reported.
+pub fn from_json<R: Read>(
+    db: &Database,
+    mut reader: R,
+    source: &str,
+    continue_on_error: bool,
+) -> Result<()> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .with_context(|| format!("Failed to read {}", source))?;
 
     let data: ExportData = serde_json::from_str(&content).context("Failed to parse JSON")?;
+    if data.version > EXPORT_VERSION {
+        bail!(
+            "Export format version {} is newer than this chainlink supports ({})",
+            data.version,
+            EXPORT_VERSION
+        );
+    }
+
+    println!("Importing {} issues from {}", data.issues.len(), source);
 
-    println!(
-        "Importing {} issues from {}",
-        data.issues.len(),
-        input_path.display()
-    );
+    // Each step runs in its own nested transaction, so a skipped step leaves nothing behind
+    let (count, failures) = db.transaction(|| -> Result<(usize, Vec<String>)> {
+        let mut failures = Vec::new();
+        let mut step = |result: Result<()>| -> Result<()> {
+            match result {
+                Err(e) if continue_on_error => {
+                    failures.push(format!("{:#}", e));
+                    Ok(())
+                }
+                other => other,
+            }
+        };
 
-    // Wrap entire import in a transaction for atomicity
-    // If any part fails, all changes are rolled back
-    let count = db.transaction(|| {
         // Map old IDs to new IDs for parent relationships
         let mut id_map: HashMap<i64, i64> = HashMap::new();
 
         // First pass: create all issues without parent relationships
         for issue in &data.issues {
-            let new_id = import_issue(db, issue, None)?;
-            id_map.insert(issue.id, new_id);
+            step(
+                db.transaction(|| import_issue(db, issue, None))
+                    .map(|new_id| {
+                        id_map.insert(issue.id, new_id);
+                    })
+                    .with_context(|| format!("Issue #{} ({})", issue.id, issue.title)),
+            )?;
         }
 
         // Second pass: update parent relationships
         for issue in &data.issues {
-            if let Some(old_parent_id) = issue.parent_id {
-                if let Some(&new_parent_id) = id_map.get(&old_parent_id) {
-                    if let Some(&new_id) = id_map.get(&issue.id) {
-                        // Update parent_id for this issue
-                        db.update_parent(new_id, Some(new_parent_id))?;
-                    }
-                }
+            if let (Some(&new_id), Some(&new_parent_id)) = (
+                id_map.get(&issue.id),
+                issue.parent_id.and_then(|p| id_map.get(&p)),
+            ) {
+                step(
+                    db.transaction(|| db.update_parent(new_id, Some(new_parent_id)))
+                        .map(|_| ())
+                        .with_context(|| {
+                            format!("Issue #{} under #{}", issue.id, issue.parent_id.unwrap())
+                        }),
+                )?;
             }
         }
 
-        Ok(data.issues.len())
+        // Third pass: restore dependency edges between imported issues
+        for dep in &data.dependencies {
+            if let (Some(&blocked), Some(&blocker)) =
+                (id_map.get(&dep.blocked_id), id_map.get(&dep.blocker_id))
+            {
+                step(
+                    db.transaction(|| db.add_dependency_of_kind(blocked, blocker, &dep.kind))
+                        .map(|_| ())
+                        .with_context(|| {
+                            format!(
+                                "Dependency #{} {} #{}",
+                                dep.blocked_id, dep.kind, dep.blocker_id
+                            )
+                        }),
+                )?;
+            }
+        }
+
+        Ok((id_map.len(), failures))
     })?;
 
     println!("Successfully imported {} issues", count);
+    if !failures.is_empty() {
+        println!("Skipped {} failed step(s):", failures.len());
+        for failure in &failures {
+            println!("  {}", failure);
+        }
+    }
     Ok(())
 }
 
+/// Create one issue per non-blank line read from `reader`.
+///
+/// Lines starting with `> ` become subissues of the closest top-level line above them.
+/// With `unique`, a line matching an open issue at the same level reuses it.
+/// Returns the number of issues created.
+pub fn from_lines<R: BufRead>(
+    db: &Database,
+    out: &Reporter,
+    reader: R,
+    priority: &str,
+    unique: bool,
+) -> Result<usize> {
+    db.transaction(|| -> Result<usize> {
+        let mut count = 0;
+        let mut last_parent: Option<i64> = None;
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line.context("Failed to read input")?;
+            if let Some(sub_title) = line.trim_start().strip_prefix("> ") {
+                let sub_title = sub_title.trim();
+                if sub_title.is_empty() {
+                    continue;
+                }
+                let Some(parent) = last_parent else {
+                    bail!("Line {}: subissue has no top-level issue above it", idx + 1);
+                };
+                if let Some(id) = find_existing(db, sub_title, Some(parent), unique)? {
+                    out.info(&format!("  Subissue #{} already exists: {}", id, sub_title));
+                    continue;
+                }
+                let id = db.create_subissue(parent, sub_title, None, priority)?;
+                out.info(&format!(
+                    "  Created subissue #{} under #{}: {}",
+                    id, parent, sub_title
+                ));
+            } else {
+                let title = line.trim();
+                if title.is_empty() {
+                    continue;
+                }
+                if let Some(id) = find_existing(db, title, None, unique)? {
+                    out.info(&format!("  Issue #{} already exists: {}", id, title));
+                    last_parent = Some(id);
+                    continue;
+                }
+                let id = db.create_issue(title, None, priority)?;
+                out.info(&format!("  Created issue #{}: {}", id, title));
+                last_parent = Some(id);
+            }
+            count += 1;
+        }
+
+        Ok(count)
+    })
+}
+
+/// The issue `from_lines` should reuse for `title`, if `unique` is set.
+fn find_existing(
+    db: &Database,
+    title: &str,
+    parent: Option<i64>,
+    unique: bool,
+) -> Result<Option<i64>> {
+    if !unique {
+        return Ok(None);
+    }
+    Ok(db.find_issue_by_title(title, parent)?)
+}
+
+/// Counts reported by `from_github`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub issues: usize,
+    pub closed: usize,
+    pub labels: usize,
+    /// Pull requests in the dump, which are not imported
+    pub skipped: usize,
+}
+
+/// One entry of a GitHub issues dump, from the REST API or `gh issue list --json`.
+/// Fields chainlink has no use for are ignored.
+#[derive(Deserialize)]
+struct GithubIssue {
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    labels: Vec<GithubLabel>,
+    /// Present only on pull requests in REST API dumps
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+/// The API gives label objects; some tools flatten them to names.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GithubLabel {
+    Object { name: String },
+    Name(String),
+}
+
+impl GithubLabel {
+    fn name(&self) -> &str {
+        match self {
+            GithubLabel::Object { name } | GithubLabel::Name(name) => name,
+        }
+    }
+}
+
+/// Import a JSON array of GitHub issues: `title`, `body` becomes the description,
+/// `state` (open/closed, any case) the status and `labels` chainlink labels.
+/// New issues get the default priority. Everything is rolled back on error.
+pub fn from_github<R: Read>(db: &Database, reader: R) -> Result<ImportSummary> {
+    let issues: Vec<GithubIssue> =
+        serde_json::from_reader(reader).context("Failed to parse GitHub issues JSON")?;
+
+    db.transaction(|| -> Result<ImportSummary> {
+        let mut summary = ImportSummary::default();
+        for (idx, gh) in issues.iter().enumerate() {
+            if gh.pull_request.is_some() {
+                summary.skipped += 1;
+                continue;
+            }
+            let closed = match gh.state.as_deref().map(str::to_ascii_lowercase).as_deref() {
+                None | Some("open") => false,
+                Some("closed") => true,
+                Some(other) => bail!("Issue {}: unknown state '{}'", idx + 1, other),
+            };
+            let body = gh.body.as_deref().filter(|b| !b.trim().is_empty());
+
+            let id = db
+                .create_issue(&gh.title, body, db.default_priority())
+                .with_context(|| format!("Issue {}: {:?}", idx + 1, gh.title))?;
+            for label in &gh.labels {
+                if db.add_label(id, label.name())? {
+                    summary.labels += 1;
+                }
+            }
+            if closed {
+                db.close_issue(id)?;
+                summary.closed += 1;
+            }
+            summary.issues += 1;
+        }
+        Ok(summary)
+    })
+}
+
 fn import_issue(db: &Database, issue: &ExportedIssue, parent_id: Option<i64>) -> Result<i64> {
     let id = if let Some(pid) = parent_id {
         db.create_subissue(
@@ -65,9 +287,22 @@ fn import_issue(db: &Database, issue: &ExportedIssue, parent_id: Option<i64>) ->
         db.add_label(id, label)?;
     }
 
-    // Add comments
+    // Add comments, keeping their original timestamps
     for comment in &issue.comments {
-        db.add_comment(id, &comment.content)?;
+        db.add_comment_at(id, &comment.content, &comment.created_at)?;
+    }
+
+    if issue.assignee.is_some() {
+        db.set_assignee(id, issue.assignee.as_deref())?;
+    }
+    if issue.due_date.is_some() {
+        db.set_due_date(id, issue.due_date)?;
+    }
+    if issue.estimate.is_some() {
+        db.set_estimate(id, issue.estimate)?;
+    }
+    if issue.url.is_some() {
+        db.set_url(id, issue.url.as_deref())?;
     }
 
     // Close if needed
@@ -83,6 +318,7 @@ fn import_issue(db: &Database, issue: &ExportedIssue, parent_id: Option<i64>) ->
 mod tests {
     use super::super::export::{ExportData, ExportedIssue};
     use super::*;
+    use crate::output::Verbosity;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -93,11 +329,171 @@ mod tests {
         (db, dir)
     }
 
+    #[test]
+    fn test_from_lines_skips_blank_lines() {
+        let (db, _dir) = setup_test_db();
+        let input = "First\n\n   \n  Second  \n\nThird\n";
+
+        let count = from_lines(&db, &Reporter::default(), input.as_bytes(), "high", false).unwrap();
+        assert_eq!(count, 3);
+
+        let mut titles: Vec<String> = db
+            .list_issues(None, None, None)
+            .unwrap()
+            .into_iter()
+            .map(|i| {
+                assert_eq!(i.priority, "high");
+                i.title
+            })
+            .collect();
+        titles.sort();
+        assert_eq!(titles, vec!["First", "Second", "Third"]);
+    }
+
+    #[test]
+    fn test_from_lines_subissue_prefix() {
+        let (db, _dir) = setup_test_db();
+        let input = "Parent A\n> Child 1\n> Child 2\nParent B\n> Child 3\n";
+
+        let count =
+            from_lines(&db, &Reporter::default(), input.as_bytes(), "medium", false).unwrap();
+        assert_eq!(count, 5);
+
+        let issues = db.list_issues(None, None, None).unwrap();
+        let parent_a = issues.iter().find(|i| i.title == "Parent A").unwrap();
+        let parent_b = issues.iter().find(|i| i.title == "Parent B").unwrap();
+        assert_eq!(parent_a.parent_id, None);
+
+        let a_children: Vec<String> = db
+            .get_subissues(parent_a.id)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.title)
+            .collect();
+        assert_eq!(a_children, vec!["Child 1", "Child 2"]);
+        assert_eq!(db.get_subissues(parent_b.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_from_lines_unique_reuses_existing() {
+        let (db, _dir) = setup_test_db();
+        let input = "Epic\n> Step one\n";
+        assert_eq!(
+            from_lines(&db, &Reporter::default(), input.as_bytes(), "medium", true).unwrap(),
+            2
+        );
+
+        let input = "epic\n> STEP one\n> Step two\nOther\n";
+        let out = Reporter::capturing(Verbosity::Normal);
+        assert_eq!(
+            from_lines(&db, &out, input.as_bytes(), "medium", true).unwrap(),
+            2
+        );
+        let output = out.output();
+        assert!(output.contains("Issue #1 already exists: epic"));
+        assert!(output.contains("Subissue #2 already exists: STEP one"));
+        assert!(output.contains("Created subissue #3 under #1: Step two"));
+        let issues = db.list_issues(None, None, None).unwrap();
+        assert_eq!(issues.len(), 4);
+        let epic = issues.iter().find(|i| i.title == "Epic").unwrap();
+        assert_eq!(db.get_subissues(epic.id).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_from_lines_orphan_subissue_rolls_back() {
+        let (db, _dir) = setup_test_db();
+        let input = "> Orphan\nParent\n";
+
+        assert!(from_lines(&db, &Reporter::default(), input.as_bytes(), "medium", false).is_err());
+        assert!(db.list_issues(None, None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_from_lines_invalid_priority_rolls_back() {
+        let (db, _dir) = setup_test_db();
+        assert!(from_lines(
+            &db,
+            &Reporter::default(),
+            "One\nTwo\n".as_bytes(),
+            "urgent",
+            false
+        )
+        .is_err());
+        assert!(db.list_issues(None, None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_from_github_maps_fields() {
+        let (db, _dir) = setup_test_db();
+        let dump = r#"[
+            {
+                "number": 12,
+                "title": "Crash on startup",
+                "body": "Steps:\r\n1. run it",
+                "state": "open",
+                "labels": [{"id": 1, "name": "bug", "color": "d73a4a"}, {"name": "p1"}],
+                "user": {"login": "someone"}
+            },
+            {
+                "number": 13,
+                "title": "Old request",
+                "body": null,
+                "state": "CLOSED",
+                "labels": ["wontfix"]
+            },
+            {
+                "number": 14,
+                "title": "Fix crash",
+                "state": "open",
+                "pull_request": {"url": "https://example.invalid/pulls/14"}
+            }
+        ]"#;
+
+        let summary = from_github(&db, dump.as_bytes()).unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                issues: 2,
+                closed: 1,
+                labels: 3,
+                skipped: 1,
+            }
+        );
+
+        let issues = db.list_issues(Some("all"), None, None).unwrap();
+        assert_eq!(issues.len(), 2);
+        let crash = issues
+            .iter()
+            .find(|i| i.title == "Crash on startup")
+            .unwrap();
+        assert_eq!(crash.status, "open");
+        assert_eq!(crash.priority, "medium");
+        assert_eq!(crash.description.as_deref(), Some("Steps:\n1. run it"));
+        assert_eq!(db.get_labels(crash.id).unwrap(), vec!["bug", "p1"]);
+
+        let old = issues.iter().find(|i| i.title == "Old request").unwrap();
+        assert_eq!(old.status, "closed");
+        assert_eq!(old.description, None);
+        assert_eq!(db.get_labels(old.id).unwrap(), vec!["wontfix"]);
+    }
+
+    #[test]
+    fn test_from_github_bad_state_rolls_back() {
+        let (db, _dir) = setup_test_db();
+        let dump = r#"[{"title": "Fine"}, {"title": "Odd", "state": "merged"}]"#;
+
+        let err = from_github(&db, dump.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("unknown state 'merged'"));
+        assert!(db.list_issues(Some("all"), None, None).unwrap().is_empty());
+        assert!(from_github(&db, "{}".as_bytes()).is_err());
+    }
+
     fn create_test_export(issues: Vec<ExportedIssue>) -> String {
         let data = ExportData {
             version: 1,
             exported_at: "2024-01-01T00:00:00Z".to_string(),
             issues,
+            dependencies: vec![],
         };
         serde_json::to_string_pretty(&data).unwrap()
     }
@@ -112,6 +508,10 @@ mod tests {
             parent_id,
             labels: vec![],
             comments: vec![],
+            assignee: None,
+            due_date: None,
+            estimate: None,
+            url: None,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
             closed_at: None,
@@ -124,12 +524,36 @@ mod tests {
         let json = create_test_export(vec![make_issue(1, "Test issue", None, "open")]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        let result = run_json(&db, &import_path);
+        let result = run_json(&db, &import_path, false);
         assert!(result.is_ok());
         let issues = db.list_issues(Some("all"), None, None).unwrap();
         assert_eq!(issues.len(), 1);
     }
 
+    #[test]
+    fn test_export_piped_into_import_round_trips() {
+        let (source, _source_dir) = setup_test_db();
+        let parent = source.create_issue("Parent", None, "high").unwrap();
+        let child = source
+            .create_subissue(parent, "Child", None, "low")
+            .unwrap();
+        source.add_label(child, "ui").unwrap();
+        let data = super::super::export::collect(&source, None, &crate::db::IssueFilter::default())
+            .unwrap();
+        let piped = serde_json::to_string_pretty(&data).unwrap();
+
+        let (db, _dir) = setup_test_db();
+        from_json(&db, piped.as_bytes(), "stdin", false).unwrap();
+
+        let issues = db.list_issues(Some("all"), None, None).unwrap();
+        assert_eq!(issues.len(), 2);
+        let new_parent = issues.iter().find(|i| i.title == "Parent").unwrap();
+        let new_child = issues.iter().find(|i| i.title == "Child").unwrap();
+        assert_eq!(new_parent.priority, "high");
+        assert_eq!(new_child.parent_id, Some(new_parent.id));
+        assert_eq!(db.get_labels(new_child.id).unwrap(), vec!["ui"]);
+    }
+
     #[test]
     fn test_import_multiple_issues() {
         let (db, dir) = setup_test_db();
@@ -139,7 +563,7 @@ mod tests {
         ]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        run_json(&db, &import_path).unwrap();
+        run_json(&db, &import_path, false).unwrap();
         let issues = db.list_issues(Some("all"), None, None).unwrap();
         assert_eq!(issues.len(), 2);
     }
@@ -150,7 +574,7 @@ mod tests {
         let json = create_test_export(vec![make_issue(1, "Closed", None, "closed")]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        run_json(&db, &import_path).unwrap();
+        run_json(&db, &import_path, false).unwrap();
         let issues = db.list_issues(Some("closed"), None, None).unwrap();
         assert_eq!(issues.len(), 1);
     }
@@ -163,26 +587,120 @@ mod tests {
         let json = create_test_export(vec![issue]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        run_json(&db, &import_path).unwrap();
+        run_json(&db, &import_path, false).unwrap();
         let issues = db.list_issues(Some("all"), None, None).unwrap();
         let labels = db.get_labels(issues[0].id).unwrap();
         assert!(labels.contains(&"bug".to_string()));
     }
 
+    #[test]
+    fn test_import_respects_max_depth() {
+        let (mut db, _dir) = setup_test_db();
+        db.set_max_depth(1);
+        let json = create_test_export(vec![
+            make_issue(1, "Root", None, "open"),
+            make_issue(2, "Child", Some(1), "open"),
+            make_issue(3, "Grandchild", Some(2), "open"),
+        ]);
+        assert!(from_json(&db, json.as_bytes(), "test", false).is_err());
+        assert!(db.list_issues(Some("all"), None, None).unwrap().is_empty());
+    }
+
     #[test]
     fn test_import_invalid_json() {
         let (db, dir) = setup_test_db();
         let import_path = dir.path().join("invalid.json");
         fs::write(&import_path, "not valid json").unwrap();
-        let result = run_json(&db, &import_path);
+        let result = run_json(&db, &import_path, false);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_import_rejects_newer_version() {
+        let (db, dir) = setup_test_db();
+        let json = create_test_export(vec![make_issue(1, "Test issue", None, "open")]).replace(
+            "\"version\": 1",
+            &format!("\"version\": {}", EXPORT_VERSION + 1),
+        );
+        let import_path = dir.path().join("import.json");
+        fs::write(&import_path, json).unwrap();
+
+        let err = run_json(&db, &import_path, false).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("newer than this chainlink supports"));
+        assert!(db.list_issues(Some("all"), None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_version_1_edges_default_to_blocks() {
+        let (db, dir) = setup_test_db();
+        let json = create_test_export(vec![
+            make_issue(1, "Blocker", None, "open"),
+            make_issue(2, "Blocked", None, "open"),
+        ])
+        .replace(
+            "\"dependencies\": []",
+            "\"dependencies\": [{\"blocked_id\": 2, \"blocker_id\": 1}]",
+        );
+        let import_path = dir.path().join("import.json");
+        fs::write(&import_path, json).unwrap();
+
+        run_json(&db, &import_path, false).unwrap();
+        assert_eq!(db.list_blocked_with_meta().unwrap().len(), 1);
+    }
+
+    /// Three issues where the middle one has a priority the database rejects,
+    /// and #3 is blocked by it
+    fn write_batch_with_invalid_issue(dir: &tempfile::TempDir) -> std::path::PathBuf {
+        let mut bad = make_issue(2, "Bad priority", None, "open");
+        bad.priority = "bogus".to_string();
+        let json = create_test_export(vec![
+            make_issue(1, "Good one", None, "open"),
+            bad,
+            make_issue(3, "Good two", None, "open"),
+        ])
+        .replace(
+            "\"dependencies\": []",
+            "\"dependencies\": [{\"blocked_id\": 3, \"blocker_id\": 2}]",
+        );
+        let import_path = dir.path().join("import.json");
+        fs::write(&import_path, json).unwrap();
+        import_path
+    }
+
+    #[test]
+    fn test_import_invalid_issue_rolls_back_everything() {
+        let (db, dir) = setup_test_db();
+        let import_path = write_batch_with_invalid_issue(&dir);
+
+        let err = run_json(&db, &import_path, false).unwrap_err();
+        assert!(format!("{:#}", err).contains("Issue #2 (Bad priority)"));
+        assert!(db.list_issues(Some("all"), None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_continue_on_error_skips_invalid_issue() {
+        let (db, dir) = setup_test_db();
+        let import_path = write_batch_with_invalid_issue(&dir);
+
+        run_json(&db, &import_path, true).unwrap();
+        let mut titles: Vec<String> = db
+            .list_issues(Some("all"), None, None)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.title)
+            .collect();
+        titles.sort();
+        assert_eq!(titles, vec!["Good one", "Good two"]);
+        assert!(db.list_dependencies().unwrap().is_empty());
+    }
+
     #[test]
     fn test_import_missing_file() {
         let (db, dir) = setup_test_db();
         let import_path = dir.path().join("nonexistent.json");
-        let result = run_json(&db, &import_path);
+        let result = run_json(&db, &import_path, false);
         assert!(result.is_err());
     }
 
@@ -192,18 +710,18 @@ mod tests {
         let json = create_test_export(vec![]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        let result = run_json(&db, &import_path);
+        let result = run_json(&db, &import_path, false);
         assert!(result.is_ok());
     }
 
     proptest! {
         #[test]
-        fn prop_import_never_panics(title in "[a-zA-Z0-9 ]{1,50}") {
+        fn prop_import_never_panics(title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,49}") {
             let (db, dir) = setup_test_db();
             let json = create_test_export(vec![make_issue(1, &title, None, "open")]);
             let import_path = dir.path().join("import.json");
             fs::write(&import_path, json).unwrap();
-            let result = run_json(&db, &import_path);
+            let result = run_json(&db, &import_path, false);
             prop_assert!(result.is_ok());
         }
     }
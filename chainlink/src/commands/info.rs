@@ -0,0 +1,50 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::db::Database;
+
+/// Where the database lives and what state it is in, for debugging a setup.
+pub fn render(db: &Database, db_path: &Path) -> Result<String> {
+    let info = db.info()?;
+    let size = std::fs::metadata(db_path)?.len();
+    Ok(format!(
+        "Database:       {}\n\
+         File size:      {} bytes\n\
+         SQLite version: {}\n\
+         Schema version: {}\n\
+         Journal mode:   {}\n\
+         Issues:         {}\n",
+        db_path.display(),
+        size,
+        info.sqlite_version,
+        info.schema_version,
+        info.journal_mode,
+        info.issue_count
+    ))
+}
+
+pub fn run(db: &Database, db_path: &Path) -> Result<()> {
+    print!("{}", render(db, db_path)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_render_reports_path_and_issue_count() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        for title in ["One", "Two", "Three"] {
+            db.create_issue(title, None, "medium").unwrap();
+        }
+
+        let out = render(&db, &db_path).unwrap();
+        assert!(out.contains(&format!("Database:       {}\n", db_path.display())));
+        assert!(out.contains("Issues:         3\n"));
+        assert!(!out.contains("File size:      0 bytes"));
+    }
+}
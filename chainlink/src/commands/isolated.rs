@@ -0,0 +1,52 @@
+use anyhow::Result;
+
+use crate::db::Database;
+use crate::utils::{pad, truncate};
+
+pub fn run(db: &Database) -> Result<()> {
+    let issues = db.list_isolated()?;
+
+    if issues.is_empty() {
+        println!("No isolated issues.");
+        return Ok(());
+    }
+
+    println!("Isolated issues (no parent, no blockers, not blocking):");
+    for issue in issues {
+        println!(
+            "  #{:<4} {} {}",
+            issue.id,
+            pad(&truncate(&issue.title, 40), 40),
+            issue.priority
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_run() {
+        let (db, _dir) = setup_test_db();
+        run(&db).unwrap();
+        let a = db.create_issue("Connected", None, "medium").unwrap();
+        let b = db.create_issue("Also connected", None, "medium").unwrap();
+        db.add_dependency(a, b).unwrap();
+        let lone = db.create_issue("Lone", None, "low").unwrap();
+        run(&db).unwrap();
+
+        let ids: Vec<i64> = db.list_isolated().unwrap().iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![lone]);
+    }
+}
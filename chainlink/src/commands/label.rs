@@ -1,25 +1,43 @@
 use anyhow::Result;
 
 use crate::db::Database;
+use crate::output::Reporter;
 
-pub fn add(db: &Database, issue_id: i64, label: &str) -> Result<()> {
+pub fn add(db: &Database, out: &Reporter, issue_id: i64, label: &str) -> Result<()> {
     db.require_issue(issue_id)?;
 
     if db.add_label(issue_id, label)? {
-        println!("Added label '{}' to issue #{}", label, issue_id);
+        out.info(&format!("Added label '{}' to issue #{}", label, issue_id));
+        out.detail(&format!(
+            "  labels: added issue_id={} label={:?}",
+            issue_id, label
+        ));
     } else {
-        println!("Label '{}' already exists on issue #{}", label, issue_id);
+        out.info(&format!(
+            "Label '{}' already exists on issue #{}",
+            label, issue_id
+        ));
     }
     Ok(())
 }
 
-pub fn remove(db: &Database, issue_id: i64, label: &str) -> Result<()> {
+pub fn remove(db: &Database, out: &Reporter, issue_id: i64, label: &str) -> Result<()> {
     db.require_issue(issue_id)?;
 
     if db.remove_label(issue_id, label)? {
-        println!("Removed label '{}' from issue #{}", label, issue_id);
+        out.info(&format!(
+            "Removed label '{}' from issue #{}",
+            label, issue_id
+        ));
+        out.detail(&format!(
+            "  labels: removed issue_id={} label={:?}",
+            issue_id, label
+        ));
     } else {
-        println!("Label '{}' not found on issue #{}", label, issue_id);
+        out.info(&format!(
+            "Label '{}' not found on issue #{}",
+            label, issue_id
+        ));
     }
     Ok(())
 }
@@ -44,7 +62,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = add(&db, issue_id, "bug");
+        let result = add(&db, &Reporter::default(), issue_id, "bug");
         assert!(result.is_ok());
 
         let labels = db.get_labels(issue_id).unwrap();
@@ -55,7 +73,7 @@ mod tests {
     fn test_add_label_to_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
 
-        let result = add(&db, 99999, "bug");
+        let result = add(&db, &Reporter::default(), 99999, "bug");
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -65,8 +83,8 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        add(&db, issue_id, "bug").unwrap();
-        let result = add(&db, issue_id, "bug"); // Duplicate
+        add(&db, &Reporter::default(), issue_id, "bug").unwrap();
+        let result = add(&db, &Reporter::default(), issue_id, "bug"); // Duplicate
         assert!(result.is_ok()); // Should succeed but not add duplicate
 
         let labels = db.get_labels(issue_id).unwrap();
@@ -78,9 +96,9 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        add(&db, issue_id, "bug").unwrap();
-        add(&db, issue_id, "urgent").unwrap();
-        add(&db, issue_id, "backend").unwrap();
+        add(&db, &Reporter::default(), issue_id, "bug").unwrap();
+        add(&db, &Reporter::default(), issue_id, "urgent").unwrap();
+        add(&db, &Reporter::default(), issue_id, "backend").unwrap();
 
         let labels = db.get_labels(issue_id).unwrap();
         assert_eq!(labels.len(), 3);
@@ -94,7 +112,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = add(&db, issue_id, "");
+        let result = add(&db, &Reporter::default(), issue_id, "");
         assert!(result.is_ok());
 
         let labels = db.get_labels(issue_id).unwrap();
@@ -106,7 +124,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = add(&db, issue_id, "バグ");
+        let result = add(&db, &Reporter::default(), issue_id, "バグ");
         assert!(result.is_ok());
 
         let labels = db.get_labels(issue_id).unwrap();
@@ -118,13 +136,13 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = add(&db, issue_id, "high-priority");
+        let result = add(&db, &Reporter::default(), issue_id, "high-priority");
         assert!(result.is_ok());
 
-        let result = add(&db, issue_id, "v2.0");
+        let result = add(&db, &Reporter::default(), issue_id, "v2.0");
         assert!(result.is_ok());
 
-        let result = add(&db, issue_id, "team:backend");
+        let result = add(&db, &Reporter::default(), issue_id, "team:backend");
         assert!(result.is_ok());
 
         let labels = db.get_labels(issue_id).unwrap();
@@ -137,7 +155,7 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         let malicious = "'; DROP TABLE labels; --";
-        let result = add(&db, issue_id, malicious);
+        let result = add(&db, &Reporter::default(), issue_id, malicious);
         assert!(result.is_ok());
 
         // Verify label was stored literally
@@ -156,8 +174,8 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        add(&db, issue_id, "bug").unwrap();
-        let result = remove(&db, issue_id, "bug");
+        add(&db, &Reporter::default(), issue_id, "bug").unwrap();
+        let result = remove(&db, &Reporter::default(), issue_id, "bug");
         assert!(result.is_ok());
 
         let labels = db.get_labels(issue_id).unwrap();
@@ -169,7 +187,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = remove(&db, issue_id, "nonexistent");
+        let result = remove(&db, &Reporter::default(), issue_id, "nonexistent");
         assert!(result.is_ok()); // Should succeed but report not found
     }
 
@@ -177,7 +195,7 @@ mod tests {
     fn test_remove_label_from_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
 
-        let result = remove(&db, 99999, "bug");
+        let result = remove(&db, &Reporter::default(), 99999, "bug");
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -187,11 +205,11 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        add(&db, issue_id, "bug").unwrap();
-        add(&db, issue_id, "urgent").unwrap();
-        add(&db, issue_id, "backend").unwrap();
+        add(&db, &Reporter::default(), issue_id, "bug").unwrap();
+        add(&db, &Reporter::default(), issue_id, "urgent").unwrap();
+        add(&db, &Reporter::default(), issue_id, "backend").unwrap();
 
-        remove(&db, issue_id, "urgent").unwrap();
+        remove(&db, &Reporter::default(), issue_id, "urgent").unwrap();
 
         let labels = db.get_labels(issue_id).unwrap();
         assert_eq!(labels.len(), 2);
@@ -206,7 +224,7 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
         db.close_issue(issue_id).unwrap();
 
-        let result = add(&db, issue_id, "bug");
+        let result = add(&db, &Reporter::default(), issue_id, "bug");
         assert!(result.is_ok());
 
         let labels = db.get_labels(issue_id).unwrap();
@@ -221,7 +239,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            add(&db, issue_id, &label).unwrap();
+            add(&db, &Reporter::default(), issue_id, &label).unwrap();
 
             let labels = db.get_labels(issue_id).unwrap();
             prop_assert!(labels.contains(&label));
@@ -232,8 +250,8 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            add(&db, issue_id, &label).unwrap();
-            remove(&db, issue_id, &label).unwrap();
+            add(&db, &Reporter::default(), issue_id, &label).unwrap();
+            remove(&db, &Reporter::default(), issue_id, &label).unwrap();
 
             let labels = db.get_labels(issue_id).unwrap();
             prop_assert!(!labels.contains(&label));
@@ -243,10 +261,10 @@ mod tests {
         fn prop_nonexistent_issue_fails(issue_id in 1000i64..10000) {
             let (db, _dir) = setup_test_db();
 
-            let add_result = add(&db, issue_id, "label");
+            let add_result = add(&db, &Reporter::default(), issue_id, "label");
             prop_assert!(add_result.is_err());
 
-            let remove_result = remove(&db, issue_id, "label");
+            let remove_result = remove(&db, &Reporter::default(), issue_id, "label");
             prop_assert!(remove_result.is_err());
         }
 
@@ -259,12 +277,12 @@ mod tests {
 
             // Add all labels
             for label in &labels {
-                add(&db, issue_id, label).unwrap();
+                add(&db, &Reporter::default(), issue_id, label).unwrap();
             }
 
             // Remove first label
             if !labels.is_empty() {
-                remove(&db, issue_id, &labels[0]).unwrap();
+                remove(&db, &Reporter::default(), issue_id, &labels[0]).unwrap();
 
                 let remaining = db.get_labels(issue_id).unwrap();
                 prop_assert!(!remaining.contains(&labels[0]));
@@ -285,7 +303,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            let result = add(&db, issue_id, &label);
+            let result = add(&db, &Reporter::default(), issue_id, &label);
             prop_assert!(result.is_ok());
 
             let labels = db.get_labels(issue_id).unwrap();
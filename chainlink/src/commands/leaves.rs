@@ -0,0 +1,82 @@
+use anyhow::Result;
+use std::collections::HashSet;
+
+use crate::db::Database;
+use crate::utils::{pad, sanitize_display, truncate};
+
+/// List issues without subissues. With `ready`, only open leaves with no open
+/// blockers are shown, most urgent first.
+pub fn render(db: &Database, status_filter: Option<&str>, ready: bool) -> Result<String> {
+    let leaves = if ready {
+        let open: HashSet<i64> = db.list_leaves(Some("open"))?.iter().map(|i| i.id).collect();
+        db.list_ready_issues(false)?
+            .into_iter()
+            .filter(|i| open.contains(&i.id))
+            .collect()
+    } else {
+        db.list_leaves(status_filter)?
+    };
+
+    if leaves.is_empty() {
+        return Ok("No leaf issues found.\n".to_string());
+    }
+
+    let scale = db.priority_scale()?;
+    let mut out = String::new();
+    for issue in leaves {
+        out.push_str(&format!(
+            "#{:<4} {:10} {} {}\n",
+            issue.id,
+            format!("[{}]", issue.status),
+            pad(&truncate(&sanitize_display(&issue.title), 40), 40),
+            scale.display_padded(&issue.priority, scale.column_width(), false)
+        ));
+    }
+    Ok(out)
+}
+
+pub fn run(db: &Database, status_filter: Option<&str>, ready: bool) -> Result<()> {
+    print!("{}", render(db, status_filter, ready)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_render_ready_leaves() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "critical").unwrap();
+        let blocked = db.create_subissue(epic, "Blocked", None, "high").unwrap();
+        let free = db.create_subissue(epic, "Free", None, "low").unwrap();
+        let urgent = db.create_issue("Urgent", None, "high").unwrap();
+        db.add_dependency(blocked, urgent).unwrap();
+
+        let all = render(&db, Some("open"), false).unwrap();
+        assert!(!all.contains("Epic"));
+        assert!(all.contains("Blocked") && all.contains("Free") && all.contains("Urgent"));
+
+        let ready = render(&db, None, true).unwrap();
+        let ids: Vec<&str> = ready
+            .lines()
+            .map(|l| l.split_whitespace().next().unwrap())
+            .collect();
+        assert_eq!(ids, vec![format!("#{}", urgent), format!("#{}", free)]);
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(render(&db, None, false).unwrap(), "No leaf issues found.\n");
+        run(&db, None, true).unwrap();
+    }
+}
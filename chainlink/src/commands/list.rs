@@ -1,52 +1,160 @@
 use anyhow::Result;
 use serde_json;
+use std::collections::HashMap;
+use unicode_width::UnicodeWidthStr;
 
-use crate::db::Database;
-use crate::utils::truncate;
+use crate::db::{Database, IssueFilter};
+use crate::models::{Issue, PriorityScale};
+use crate::utils::{csv_field, pad, sanitize_display, truncate};
 
-pub fn run_json(
-    db: &Database,
-    status: Option<&str>,
-    label: Option<&str>,
-    priority: Option<&str>,
-) -> Result<()> {
-    let issues = db.list_issues(status, label, priority)?;
+pub fn run_json(db: &Database, filter: &IssueFilter<'_>) -> Result<()> {
+    let issues = db.list_issues_filtered(filter)?;
     println!("{}", serde_json::to_string_pretty(&issues)?);
     Ok(())
 }
 
-pub fn run(
-    db: &Database,
-    status: Option<&str>,
-    label: Option<&str>,
-    priority: Option<&str>,
-) -> Result<()> {
-    let issues = db.list_issues(status, label, priority)?;
+/// The same rows as the human list, one CSV record per issue under a header line.
+pub fn render_csv(db: &Database, filter: &IssueFilter<'_>) -> Result<String> {
+    let mut out = String::from("id,status,priority,title,parent_id,created_at\n");
+    for issue in db.list_issues_filtered(filter)? {
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            issue.id,
+            csv_field(&issue.status),
+            csv_field(&issue.priority),
+            csv_field(&issue.title),
+            issue.parent_id.map(|p| p.to_string()).unwrap_or_default(),
+            issue.created_at.to_rfc3339()
+        ));
+    }
+    Ok(out)
+}
+
+pub fn run_csv(db: &Database, filter: &IssueFilter<'_>) -> Result<()> {
+    print!("{}", render_csv(db, filter)?);
+    Ok(())
+}
+
+/// Display options for the flat list.
+#[derive(Default)]
+pub struct ListOpts {
+    /// End each row with its blocker and dependent counts
+    pub show_deps: bool,
+    /// Add a parent column and mark subissues so the hierarchy shows in flat mode
+    pub show_parent: bool,
+    /// Color priorities and add a priority legend after the rows
+    pub color: bool,
+    /// Title column width; None sizes the column to the longest title unclipped
+    pub title_width: Option<usize>,
+    /// End with a footer counting the listed issues by open, blocked and ready
+    pub summary: bool,
+}
+
+/// Build the flat issue list without printing it.
+pub fn render(db: &Database, filter: &IssueFilter<'_>, opts: &ListOpts) -> Result<String> {
+    let issues = db.list_issues_filtered(filter)?;
 
     if issues.is_empty() {
-        println!("No issues found.");
-        return Ok(());
+        return Ok("No issues found.\n".to_string());
     }
 
-    for issue in issues {
-        let status_display = format!("[{}]", issue.status);
-        let date = issue.created_at.format("%Y-%m-%d");
-        println!(
-            "#{:<4} {:8} {:<40} {:8} {}",
-            issue.id,
-            status_display,
-            truncate(&issue.title, 40),
-            issue.priority,
-            date
-        );
+    let counts = if opts.show_deps {
+        db.dependency_counts()?
+    } else {
+        HashMap::new()
+    };
+
+    let scale = db.priority_scale()?;
+    let width = opts.title_width.unwrap_or_else(|| {
+        issues
+            .iter()
+            .map(|i| row_title(i, opts).width())
+            .max()
+            .unwrap_or(0)
+    });
+    let mut out = String::new();
+    for issue in &issues {
+        out.push_str(&format_row(issue, &scale, opts, width));
+        if opts.show_deps {
+            out.push_str(&deps_column(counts.get(&issue.id).copied()));
+        }
+        out.push('\n');
+    }
+    if opts.summary {
+        out.push_str(&format!("\n{}\n", summary_line(db, &issues)?));
+    }
+    if opts.color {
+        out.push_str(&format!("\n{}\n", scale.legend()));
     }
 
+    Ok(out)
+}
+
+pub fn run(db: &Database, filter: &IssueFilter<'_>, opts: &ListOpts) -> Result<()> {
+    print!("{}", render(db, filter, opts)?);
     Ok(())
 }
 
+/// One aligned row for an issue. Control characters in the title are escaped so
+/// the row stays on one line.
+fn format_row(issue: &Issue, scale: &PriorityScale, opts: &ListOpts, width: usize) -> String {
+    let status_display = format!("[{}]", issue.status);
+    let date = issue.created_at.format("%Y-%m-%d");
+    let title = row_title(issue, opts);
+    let mut parent = String::new();
+    if opts.show_parent {
+        let parent_display = issue.parent_id.map(|p| format!("#{}", p));
+        parent = format!("{:<6}", parent_display.as_deref().unwrap_or(""));
+    }
+    format!(
+        "#{:<4} {:8} {}{} {} {}",
+        issue.id,
+        status_display,
+        parent,
+        pad(&truncate(&title, width), width),
+        scale.display_padded(&issue.priority, scale.column_width(), opts.color),
+        date
+    )
+}
+
+/// The title as shown in a row: escaped, and marked when it is a subissue.
+fn row_title(issue: &Issue, opts: &ListOpts) -> String {
+    let title = sanitize_display(&issue.title);
+    if opts.show_parent && issue.parent_id.is_some() {
+        format!("\u{21b3} {}", title)
+    } else {
+        title
+    }
+}
+
+/// "12 issues (8 open, 3 blocked, 5 ready)" for the listed issues. Blocked and
+/// ready split the open ones.
+fn summary_line(db: &Database, issues: &[Issue]) -> Result<String> {
+    let open: Vec<i64> = issues
+        .iter()
+        .filter(|i| i.status == "open")
+        .map(|i| i.id)
+        .collect();
+    let blocked = db.blocked_among(&open)?.len();
+    Ok(format!(
+        "{} issue{} ({} open, {} blocked, {} ready)",
+        issues.len(),
+        if issues.len() == 1 { "" } else { "s" },
+        open.len(),
+        blocked,
+        open.len() - blocked
+    ))
+}
+
+fn deps_column(counts: Option<(i64, i64)>) -> String {
+    let (blockers, dependents) = counts.unwrap_or((0, 0));
+    format!("  blockers: {:<3} dependents: {}", blockers, dependents)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::TITLE_WIDTH;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -79,21 +187,26 @@ mod tests {
         assert_eq!(truncate("← → ↑ ↓", 10), "← → ↑ ↓");
         let result = truncate("←←←←←←←←←←←←", 5);
         assert!(result.ends_with("..."));
-        assert_eq!(result.chars().count(), 5);
+        assert_eq!(result.width(), 5);
     }
 
     #[test]
     fn test_truncate_emoji() {
-        // Emoji are multi-byte
+        // Emoji are two columns wide, so only one fits before the ellipsis
         let result = truncate("🎉🎊🎈🎁🎂🎄🎃🎇🎆", 6);
-        assert!(result.ends_with("..."));
-        assert_eq!(result.chars().count(), 6);
+        assert_eq!(result, "🎉...");
+        assert!(result.width() <= 6);
     }
 
     #[test]
     fn test_truncate_mixed_unicode() {
         let result = truncate("Hello 世界! 🌍", 8);
         assert!(result.ends_with("..."));
+        assert_eq!(result, "Hello...");
+
+        let result = truncate("Hello 世界! 🌍", 10);
+        assert_eq!(result, "Hello ...");
+        assert!(result.width() <= 10);
     }
 
     #[test]
@@ -103,11 +216,199 @@ mod tests {
         assert_eq!(truncate("abcd", 3), "...");
     }
 
+    #[test]
+    fn test_format_row_escapes_control_chars() {
+        let (db, _dir) = setup_test_db();
+        let newline = db.create_issue("two\nlines", None, "medium").unwrap();
+        let nul = db.create_issue("nul\0here", None, "medium").unwrap();
+
+        for (id, escaped) in [(newline, "two\\nlines"), (nul, "nul\\u{0}here")] {
+            let issue = db.get_issue(id).unwrap().unwrap();
+            let scale = PriorityScale::default();
+            let row = format_row(&issue, &scale, &ListOpts::default(), TITLE_WIDTH);
+            assert!(row.contains(escaped));
+            assert!(!row.contains('\n') && !row.contains('\0'));
+            // Padding is computed on the escaped text, so columns stay aligned
+            assert_eq!(
+                row.width(),
+                format_row(
+                    &Issue {
+                        title: "x".into(),
+                        ..issue.clone()
+                    },
+                    &scale,
+                    &ListOpts::default(),
+                    TITLE_WIDTH
+                )
+                .width()
+            );
+        }
+        assert_eq!(db.get_issue(nul).unwrap().unwrap().title, "nul\0here");
+    }
+
+    #[test]
+    fn test_render() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(
+            render(&db, &IssueFilter::default(), &ListOpts::default()).unwrap(),
+            "No issues found.\n"
+        );
+
+        let a = db.create_issue("First", None, "high").unwrap();
+        let b = db.create_issue("Second", None, "low").unwrap();
+        db.add_dependency(b, a).unwrap();
+        let issues = db.list_issues(None, None, None).unwrap();
+        let scale = PriorityScale::default();
+        let row = |id: i64| {
+            format_row(
+                issues.iter().find(|i| i.id == id).unwrap(),
+                &scale,
+                &ListOpts::default(),
+                "Second".len(),
+            )
+        };
+
+        assert_eq!(
+            render(&db, &IssueFilter::default(), &ListOpts::default()).unwrap(),
+            format!("{}\n{}\n", row(b), row(a))
+        );
+        assert_eq!(
+            render(
+                &db,
+                &IssueFilter::default(),
+                &ListOpts {
+                    show_deps: true,
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+            format!(
+                "{}  blockers: 1   dependents: 0\n{}  blockers: 0   dependents: 1\n",
+                row(b),
+                row(a)
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_summary_footer() {
+        let (db, _dir) = setup_test_db();
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let also_blocked = db.create_issue("Also blocked", None, "medium").unwrap();
+        db.create_issue("Free", None, "low").unwrap();
+        let done = db.create_issue("Done", None, "low").unwrap();
+        db.add_dependency(blocked, blocker).unwrap();
+        db.add_dependency(also_blocked, blocker).unwrap();
+        db.close_issue(done).unwrap();
+
+        let opts = ListOpts {
+            summary: true,
+            ..Default::default()
+        };
+        let all = IssueFilter {
+            status: Some("all"),
+            ..Default::default()
+        };
+        let out = render(&db, &all, &opts).unwrap();
+        assert!(out.ends_with("\n5 issues (4 open, 2 blocked, 2 ready)\n"));
+
+        // The footer counts only the listed issues
+        let low = IssueFilter {
+            status: Some("all"),
+            priority: Some("low"),
+            ..Default::default()
+        };
+        let out = render(&db, &low, &opts).unwrap();
+        assert!(out.ends_with("\n2 issues (1 open, 0 blocked, 1 ready)\n"));
+
+        let closed = IssueFilter {
+            status: Some("closed"),
+            ..Default::default()
+        };
+        let out = render(&db, &closed, &opts).unwrap();
+        assert!(out.ends_with("\n1 issue (0 open, 0 blocked, 0 ready)\n"));
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Plain", None, "high").unwrap();
+        let child = db
+            .create_subissue(parent, "Comma, \"quoted\"", None, "low")
+            .unwrap();
+
+        let csv = render_csv(&db, &IssueFilter::default()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "id,status,priority,title,parent_id,created_at");
+        assert!(lines[1].starts_with(&format!(
+            "{},open,low,\"Comma, \"\"quoted\"\"\",{},",
+            child, parent
+        )));
+        assert!(lines[2].starts_with(&format!("{},open,high,Plain,,", parent)));
+    }
+
+    #[test]
+    fn test_render_show_parent() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "high").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "low").unwrap();
+        let opts = ListOpts {
+            show_parent: true,
+            ..Default::default()
+        };
+
+        let out = render(&db, &IssueFilter::default(), &opts).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(&format!(
+            "#{:<4} [open]   {:<6}\u{21b3} Child",
+            child,
+            format!("#{}", parent)
+        )));
+        assert!(lines[1].starts_with(&format!("#{:<4} [open]         Parent", parent)));
+        // Both rows stay aligned
+        assert_eq!(lines[0].width(), lines[1].width());
+
+        let plain = render(&db, &IssueFilter::default(), &ListOpts::default()).unwrap();
+        assert!(!plain.contains('\u{21b3}'));
+    }
+
+    #[test]
+    fn test_deps_column() {
+        let (db, _dir) = setup_test_db();
+        let root = db.create_issue("Root", None, "medium").unwrap();
+        let middle = db.create_issue("Middle", None, "medium").unwrap();
+        let leaf = db.create_issue("Leaf", None, "medium").unwrap();
+        let lone = db.create_issue("Lone", None, "medium").unwrap();
+        db.add_dependency(middle, root).unwrap();
+        db.add_dependency(leaf, middle).unwrap();
+
+        let counts = db.dependency_counts().unwrap();
+        assert_eq!(
+            deps_column(counts.get(&middle).copied()),
+            "  blockers: 1   dependents: 1"
+        );
+        assert_eq!(
+            deps_column(counts.get(&lone).copied()),
+            "  blockers: 0   dependents: 0"
+        );
+        run(
+            &db,
+            &IssueFilter::default(),
+            &ListOpts {
+                show_deps: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    }
+
     // Run function tests
     #[test]
     fn test_run_empty() {
         let (db, _dir) = setup_test_db();
-        run(&db, None, None, None).unwrap();
+        run(&db, &IssueFilter::default(), &ListOpts::default()).unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert!(issues.is_empty());
     }
@@ -119,7 +420,7 @@ mod tests {
         db.create_issue("Issue 2", None, "medium").unwrap();
         db.create_issue("Issue 3", None, "low").unwrap();
 
-        run(&db, None, None, None).unwrap();
+        run(&db, &IssueFilter::default(), &ListOpts::default()).unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert_eq!(issues.len(), 3);
     }
@@ -135,7 +436,14 @@ mod tests {
         assert!(issues.iter().any(|i| i.id == id1));
         assert!(!issues.iter().any(|i| i.id == id2));
 
-        let result = run(&db, Some("open"), None, None);
+        let result = run(
+            &db,
+            &IssueFilter {
+                status: Some("open"),
+                ..Default::default()
+            },
+            &ListOpts::default(),
+        );
         assert!(result.is_ok());
     }
 
@@ -150,7 +458,14 @@ mod tests {
         assert!(!issues.iter().any(|i| i.id == id1));
         assert!(issues.iter().any(|i| i.id == id2));
 
-        let result = run(&db, Some("closed"), None, None);
+        let result = run(
+            &db,
+            &IssueFilter {
+                status: Some("closed"),
+                ..Default::default()
+            },
+            &ListOpts::default(),
+        );
         assert!(result.is_ok());
     }
 
@@ -161,7 +476,15 @@ mod tests {
         let id2 = db.create_issue("Closed issue", None, "medium").unwrap();
         db.close_issue(id2).unwrap();
 
-        run(&db, Some("all"), None, None).unwrap();
+        run(
+            &db,
+            &IssueFilter {
+                status: Some("all"),
+                ..Default::default()
+            },
+            &ListOpts::default(),
+        )
+        .unwrap();
         let issues = db.list_issues(Some("all"), None, None).unwrap();
         assert_eq!(issues.len(), 2);
         assert!(issues.iter().any(|i| i.id == id1));
@@ -180,7 +503,14 @@ mod tests {
         assert!(issues.iter().any(|i| i.id == id1));
         assert!(!issues.iter().any(|i| i.id == id2));
 
-        let result = run(&db, None, Some("bug"), None);
+        let result = run(
+            &db,
+            &IssueFilter {
+                label: Some("bug"),
+                ..Default::default()
+            },
+            &ListOpts::default(),
+        );
         assert!(result.is_ok());
     }
 
@@ -194,7 +524,14 @@ mod tests {
         assert!(issues.iter().any(|i| i.id == id1));
         assert!(!issues.iter().any(|i| i.id == id2));
 
-        let result = run(&db, None, None, Some("high"));
+        let result = run(
+            &db,
+            &IssueFilter {
+                priority: Some("high"),
+                ..Default::default()
+            },
+            &ListOpts::default(),
+        );
         assert!(result.is_ok());
     }
 
@@ -215,7 +552,16 @@ mod tests {
         assert!(!issues.iter().any(|i| i.id == id2));
         assert!(!issues.iter().any(|i| i.id == id3));
 
-        let result = run(&db, Some("open"), Some("bug"), Some("high"));
+        let result = run(
+            &db,
+            &IssueFilter {
+                status: Some("open"),
+                label: Some("bug"),
+                priority: Some("high"),
+                ..Default::default()
+            },
+            &ListOpts::default(),
+        );
         assert!(result.is_ok());
     }
 
@@ -225,7 +571,7 @@ mod tests {
         let long_title = "A".repeat(100);
         db.create_issue(&long_title, None, "medium").unwrap();
 
-        let result = run(&db, None, None, None);
+        let result = run(&db, &IssueFilter::default(), &ListOpts::default());
         assert!(result.is_ok());
     }
 
@@ -235,7 +581,7 @@ mod tests {
         db.create_issue("日本語タイトル 🎉", None, "medium")
             .unwrap();
 
-        let result = run(&db, None, None, None);
+        let result = run(&db, &IssueFilter::default(), &ListOpts::default());
         assert!(result.is_ok());
     }
 
@@ -244,7 +590,15 @@ mod tests {
         let (db, _dir) = setup_test_db();
         db.create_issue("Issue", None, "medium").unwrap();
 
-        run(&db, None, Some("nonexistent-label"), None).unwrap();
+        run(
+            &db,
+            &IssueFilter {
+                label: Some("nonexistent-label"),
+                ..Default::default()
+            },
+            &ListOpts::default(),
+        )
+        .unwrap();
         let issues = db
             .list_issues(None, Some("nonexistent-label"), None)
             .unwrap();
@@ -256,9 +610,9 @@ mod tests {
 
     proptest! {
         #[test]
-        fn truncate_respects_max_chars(s in ".{10,100}", max_chars in 5usize..50) {
-            let result = truncate(&s, max_chars);
-            assert!(result.chars().count() <= max_chars);
+        fn truncate_respects_max_width(s in ".{10,100}", max_width in 5usize..50) {
+            let result = truncate(&s, max_width);
+            assert!(result.width() <= max_width);
         }
 
         #[test]
@@ -270,7 +624,7 @@ mod tests {
         #[test]
         fn truncate_adds_ellipsis_for_long_strings(s in ".{20,50}", max_chars in 5usize..15) {
             let result = truncate(&s, max_chars);
-            if s.chars().count() > max_chars {
+            if s.width() > max_chars {
                 assert!(result.ends_with("..."));
             }
         }
@@ -281,7 +635,15 @@ mod tests {
             db.create_issue("Match", None, &priority).unwrap();
             db.create_issue("Other", None, "low").unwrap();
 
-            run(&db, None, None, Some(&priority)).unwrap();
+            run(
+                &db,
+                &IssueFilter {
+                    priority: Some(&priority),
+                    ..Default::default()
+                },
+                &ListOpts::default(),
+            )
+            .unwrap();
             let filtered = db.list_issues(None, None, Some(&priority)).unwrap();
             prop_assert!(filtered.iter().all(|i| i.priority == priority));
         }
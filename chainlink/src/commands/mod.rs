@@ -1,21 +1,39 @@
 pub mod archive;
+pub mod assign;
+pub mod children;
 pub mod comment;
+pub mod count;
 pub mod create;
+pub mod dedupe;
 pub mod delete;
 pub mod deps;
+pub mod doctor;
+pub mod due;
+pub mod estimate;
 pub mod export;
 pub mod import;
+pub mod info;
 pub mod init;
+pub mod isolated;
 pub mod label;
+pub mod leaves;
 pub mod list;
 pub mod milestone;
 pub mod next;
+pub mod priorities;
+pub mod promote;
 pub mod relate;
 pub mod search;
 pub mod session;
 pub mod show;
+pub mod slug;
+pub mod stale;
+pub mod stats;
 pub mod status;
+pub mod template;
 pub mod tested;
 pub mod timer;
 pub mod tree;
 pub mod update;
+pub mod url;
+pub mod why;
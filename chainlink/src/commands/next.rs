@@ -1,24 +1,14 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 
 use crate::db::Database;
-use crate::models::Issue;
+use crate::models::{Issue, ReadyScore};
 
 /// Progress tuple: (completed subissues, total subissues)
 type Progress = Option<(i32, i32)>;
 
-/// Scored issue with priority score and progress
-type ScoredIssue = (Issue, i32, Progress);
-
-/// Priority order for sorting (higher = more important)
-fn priority_weight(priority: &str) -> i32 {
-    match priority {
-        "critical" => 4,
-        "high" => 3,
-        "medium" => 2,
-        "low" => 1,
-        _ => 0,
-    }
-}
+/// Ready issue with its recommendation score, progress and open dependents count
+type ScoredIssue = (Issue, f64, Progress, i64);
 
 /// Calculate progress for issues with subissues
 fn calculate_progress(db: &Database, issue: &Issue) -> Result<Progress> {
@@ -32,45 +22,55 @@ fn calculate_progress(db: &Database, issue: &Issue) -> Result<Progress> {
     Ok(Some((closed, total)))
 }
 
-pub fn run(db: &Database) -> Result<()> {
-    let ready = db.list_ready_issues()?;
-
-    if ready.is_empty() {
-        println!("No issues ready to work on.");
-        println!(
-            "Use 'chainlink list' to see all issues or 'chainlink blocked' to see blocked issues."
-        );
-        return Ok(());
-    }
-
-    // Score and sort issues
+/// Ready top-level issues ranked best-first by `Database::rank_ready`.
+fn rank_ready(db: &Database, now: DateTime<Utc>) -> Result<Vec<ScoredIssue>> {
     let mut scored: Vec<ScoredIssue> = Vec::new();
 
-    for issue in ready {
+    for (issue, score) in db.rank_ready(now)? {
         // Skip subissues - we want to recommend parent issues or standalone issues
         if issue.parent_id.is_some() {
             continue;
         }
 
-        let priority_score = priority_weight(&issue.priority) * 100;
         let progress = calculate_progress(db, &issue)?;
+        let dependents = db.count_dependents(issue.id)?;
+        scored.push((issue, score, progress, dependents));
+    }
 
-        // Boost score for issues that are partially complete (finish what you started)
-        let progress_bonus = match &progress {
-            Some((closed, total)) if *closed > 0 && *closed < *total => 50,
-            _ => 0,
-        };
+    Ok(scored)
+}
+
+/// Score breakdown line, e.g. "score 45.0 = priority 30.0 + due 10.0 + unblocks 5.0 + ...".
+fn explain(score: &ReadyScore) -> String {
+    format!(
+        "score {:.1} = priority {:.1} + due {:.1} + unblocks {:.1} + progress {:.1} + effort {:.1} + order {:.1}",
+        score.total(),
+        score.priority,
+        score.due,
+        score.unblocks,
+        score.progress,
+        score.effort,
+        score.order
+    )
+}
+
+pub fn run(db: &Database, explain_scores: bool) -> Result<()> {
+    let ready = db.list_ready_issues(false)?;
 
-        let score = priority_score + progress_bonus;
-        scored.push((issue, score, progress));
+    if ready.is_empty() {
+        println!("No issues ready to work on.");
+        println!(
+            "Use 'chainlink list' to see all issues or 'chainlink blocked' to see blocked issues."
+        );
+        return Ok(());
     }
 
-    // Sort by score descending
-    scored.sort_by(|a, b| b.1.cmp(&a.1));
+    let now = Utc::now();
+    let scored = rank_ready(db, now)?;
 
     if scored.is_empty() {
         // All ready issues are subissues, show them instead
-        let ready = db.list_ready_issues()?;
+        let ready = db.list_ready_issues(false)?;
         if let Some(issue) = ready.first() {
             println!("Next: #{} [{}] {}", issue.id, issue.priority, issue.title);
             if let Some(parent_id) = issue.parent_id {
@@ -83,13 +83,25 @@ pub fn run(db: &Database) -> Result<()> {
     }
 
     // Recommend the top issue
-    let (top, _score, progress) = &scored[0];
+    let (top, _score, progress, dependents) = &scored[0];
     println!("Next: #{} [{}] {}", top.id, top.priority, top.title);
 
     if let Some((closed, total)) = progress {
         println!("       Progress: {}/{} subissues complete", closed, total);
     }
 
+    if *dependents > 0 {
+        println!("       Unblocks {} issue(s)", dependents);
+    }
+
+    if let Some(due) = db.get_due_date(top.id)? {
+        println!("       Due {}", due);
+    }
+
+    if explain_scores {
+        println!("       {}", explain(&db.score_ready(top, now)?));
+    }
+
     if let Some(desc) = &top.description {
         if !desc.is_empty() {
             let preview: String = desc.chars().take(80).collect();
@@ -105,7 +117,7 @@ pub fn run(db: &Database) -> Result<()> {
     if scored.len() > 1 {
         println!();
         println!("Also ready:");
-        for (issue, _score, progress) in scored.iter().skip(1).take(3) {
+        for (issue, _score, progress, _dependents) in scored.iter().skip(1).take(3) {
             let progress_str = match progress {
                 Some((c, t)) => format!(" ({}/{})", c, t),
                 None => String::new(),
@@ -114,6 +126,9 @@ pub fn run(db: &Database) -> Result<()> {
                 "  #{} [{}] {}{}",
                 issue.id, issue.priority, issue.title, progress_str
             );
+            if explain_scores {
+                println!("       {}", explain(&db.score_ready(issue, now)?));
+            }
         }
     }
 
@@ -123,6 +138,7 @@ pub fn run(db: &Database) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::PriorityScale;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -135,34 +151,34 @@ mod tests {
 
     #[test]
     fn test_priority_weight_critical() {
-        assert_eq!(priority_weight("critical"), 4);
+        assert_eq!(PriorityScale::default().rank("critical"), 4);
     }
 
     #[test]
     fn test_priority_weight_high() {
-        assert_eq!(priority_weight("high"), 3);
+        assert_eq!(PriorityScale::default().rank("high"), 3);
     }
 
     #[test]
     fn test_priority_weight_medium() {
-        assert_eq!(priority_weight("medium"), 2);
+        assert_eq!(PriorityScale::default().rank("medium"), 2);
     }
 
     #[test]
     fn test_priority_weight_low() {
-        assert_eq!(priority_weight("low"), 1);
+        assert_eq!(PriorityScale::default().rank("low"), 1);
     }
 
     #[test]
     fn test_priority_weight_unknown() {
-        assert_eq!(priority_weight("unknown"), 0);
+        assert_eq!(PriorityScale::default().rank("unknown"), 0);
     }
 
     #[test]
     fn test_run_no_issues() {
         let (db, _dir) = setup_test_db();
-        run(&db).unwrap();
-        let ready = db.list_ready_issues().unwrap();
+        run(&db, false).unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert!(ready.is_empty());
     }
 
@@ -171,8 +187,8 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Issue 1", None, "high").unwrap();
 
-        run(&db).unwrap();
-        let ready = db.list_ready_issues().unwrap();
+        run(&db, false).unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert_eq!(ready.len(), 1);
         assert_eq!(ready[0].id, id);
     }
@@ -186,16 +202,112 @@ mod tests {
             .unwrap();
         db.create_issue("Medium priority", None, "medium").unwrap();
 
-        run(&db).unwrap();
+        run(&db, false).unwrap();
         // Verify the critical issue has the highest weight via the scoring function
-        let ready = db.list_ready_issues().unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert_eq!(ready.len(), 3);
         let critical = ready.iter().find(|i| i.id == critical_id).unwrap();
         assert_eq!(critical.priority, "critical");
         // Critical should have highest weight
-        assert_eq!(priority_weight("critical"), 4);
-        assert!(priority_weight("critical") > priority_weight("low"));
-        assert!(priority_weight("critical") > priority_weight("medium"));
+        assert_eq!(PriorityScale::default().rank("critical"), 4);
+        assert!(PriorityScale::default().rank("critical") > PriorityScale::default().rank("low"));
+        assert!(
+            PriorityScale::default().rank("critical") > PriorityScale::default().rank("medium")
+        );
+    }
+
+    #[test]
+    fn test_rank_ready_orders_by_priority() {
+        let (db, _dir) = setup_test_db();
+        let low = db.create_issue("Low", None, "low").unwrap();
+        let critical = db.create_issue("Critical", None, "critical").unwrap();
+        let medium = db.create_issue("Medium", None, "medium").unwrap();
+
+        let ranked = rank_ready(&db, Utc::now()).unwrap();
+        let ids: Vec<i64> = ranked.iter().map(|s| s.0.id).collect();
+        assert_eq!(ids, vec![critical, medium, low]);
+    }
+
+    #[test]
+    fn test_rank_ready_uses_configured_priorities() {
+        let (db, _dir) = setup_test_db();
+        let levels: Vec<String> = ["p5", "p4", "p3", "p2", "p1"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        db.set_priorities(&levels).unwrap();
+        let p4 = db.create_issue("P4", None, "p4").unwrap();
+        let p1 = db.create_issue("P1", None, "p1").unwrap();
+        let p5 = db.create_issue("P5", None, "p5").unwrap();
+        let p2 = db.create_issue("P2", None, "p2").unwrap();
+
+        let ranked = rank_ready(&db, Utc::now()).unwrap();
+        let ids: Vec<i64> = ranked.iter().map(|s| s.0.id).collect();
+        assert_eq!(ids, vec![p1, p2, p4, p5]);
+    }
+
+    #[test]
+    fn test_rank_ready_prefers_more_dependents() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "high").unwrap();
+        let second = db.create_issue("Second", None, "high").unwrap();
+        let a = db.create_issue("Waits on second", None, "low").unwrap();
+        let b = db
+            .create_issue("Also waits on second", None, "low")
+            .unwrap();
+        db.add_dependency(a, second).unwrap();
+        db.add_dependency(b, second).unwrap();
+
+        let ranked = rank_ready(&db, Utc::now()).unwrap();
+        assert_eq!(ranked[0].0.id, second);
+        assert_eq!(ranked[0].3, 2);
+        assert_eq!(ranked[1].0.id, first);
+    }
+
+    #[test]
+    fn test_rank_ready_ties_broken_by_id() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+
+        let ranked = rank_ready(&db, Utc::now()).unwrap();
+        let ids: Vec<i64> = ranked.iter().map(|s| s.0.id).collect();
+        assert_eq!(ids, vec![first, second]);
+    }
+
+    #[test]
+    fn test_ready_subissue_of_closed_parent_still_ranked() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        db.close_issue(parent).unwrap();
+
+        let ranked = db.rank_ready(Utc::now()).unwrap();
+        assert!(ranked.iter().any(|(issue, _)| issue.id == child));
+    }
+
+    #[test]
+    fn test_explain_lists_components() {
+        let score = ReadyScore {
+            priority: 30.0,
+            due: 20.0,
+            unblocks: 5.0,
+            progress: 0.0,
+            effort: 0.5,
+            order: -5.0,
+        };
+        assert_eq!(
+            explain(&score),
+            "score 50.5 = priority 30.0 + due 20.0 + unblocks 5.0 + progress 0.0 + effort 0.5 + order -5.0"
+        );
+    }
+
+    #[test]
+    fn test_run_explain() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("First", None, "high").unwrap();
+        db.create_issue("Second", None, "low").unwrap();
+        run(&db, true).unwrap();
     }
 
     #[test]
@@ -235,8 +347,8 @@ mod tests {
         let blocked = db.create_issue("Blocked", None, "critical").unwrap();
         db.add_dependency(blocked, blocker).unwrap();
 
-        run(&db).unwrap();
-        let ready = db.list_ready_issues().unwrap();
+        run(&db, false).unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert!(
             !ready.iter().any(|i| i.id == blocked),
             "Blocked issue should not be in ready list"
@@ -253,8 +365,8 @@ mod tests {
         let id = db.create_issue("Done", None, "medium").unwrap();
         db.close_issue(id).unwrap();
 
-        run(&db).unwrap();
-        let ready = db.list_ready_issues().unwrap();
+        run(&db, false).unwrap();
+        let ready = db.list_ready_issues(false).unwrap();
         assert!(
             ready.is_empty(),
             "Closed issues should not appear in ready list"
@@ -264,7 +376,7 @@ mod tests {
     proptest! {
         #[test]
         fn prop_priority_weight_valid(priority in "low|medium|high|critical") {
-            let weight = priority_weight(&priority);
+            let weight = PriorityScale::default().rank(&priority);
             prop_assert!((1..=4).contains(&weight));
         }
 
@@ -274,7 +386,7 @@ mod tests {
             for i in 0..count {
                 db.create_issue(&format!("Issue {}", i), None, "medium").unwrap();
             }
-            let result = run(&db);
+            let result = run(&db, false);
             prop_assert!(result.is_ok());
         }
     }
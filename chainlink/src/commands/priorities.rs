@@ -0,0 +1,51 @@
+use anyhow::Result;
+
+use crate::db::Database;
+use crate::output::Reporter;
+
+pub fn list(db: &Database) -> Result<()> {
+    let scale = db.priority_scale()?;
+    // Most urgent first, matching how issues are sorted
+    for (i, level) in scale.levels().iter().enumerate().rev() {
+        println!("{:>3}  {}", i + 1, level);
+    }
+    Ok(())
+}
+
+pub fn set(db: &Database, out: &Reporter, levels: &[String]) -> Result<()> {
+    db.set_priorities(levels)?;
+    out.info(&format!("Priority levels set: {}", levels.join(" < ")));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_set_and_list() {
+        let (db, _dir) = setup_test_db();
+        let levels: Vec<String> = ["p5", "p4", "p3", "p2", "p1"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        set(&db, &Reporter::default(), &levels).unwrap();
+        list(&db).unwrap();
+        assert_eq!(db.priority_scale().unwrap().levels(), levels.as_slice());
+    }
+
+    #[test]
+    fn test_set_rejects_levels_in_use() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("Issue", None, "high").unwrap();
+        assert!(set(&db, &Reporter::default(), &["low".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use anyhow::Result;
+
+use crate::db::Database;
+use crate::output::Reporter;
+
+/// Detach a subissue from its parent so it becomes top-level.
+/// Its own subissues stay attached to it.
+pub fn run(db: &Database, out: &Reporter, id: i64) -> Result<()> {
+    let issue = db.require_issue(id)?;
+
+    let Some(parent_id) = issue.parent_id else {
+        out.info(&format!("Issue #{} is already top-level", id));
+        return Ok(());
+    };
+
+    db.update_parent(id, None)?;
+    out.info(&format!("Promoted #{} (was under #{})", id, parent_id));
+    out.detail(&format!(
+        "  issues: #{} parent_id={} -> NULL",
+        id, parent_id
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_promote_keeps_subtree() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let grown = db.create_subissue(epic, "Grown", None, "medium").unwrap();
+        let child = db.create_subissue(grown, "Child", None, "low").unwrap();
+
+        run(&db, &Reporter::default(), grown).unwrap();
+
+        let mut top: Vec<i64> = db
+            .list_issues(None, None, None)
+            .unwrap()
+            .iter()
+            .filter(|i| i.parent_id.is_none())
+            .map(|i| i.id)
+            .collect();
+        top.sort();
+        assert_eq!(top, vec![epic, grown]);
+        assert_eq!(db.get_issue(child).unwrap().unwrap().parent_id, Some(grown));
+        assert!(db.get_subissues(epic).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_promote_top_level_is_noop() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Top", None, "medium").unwrap();
+        let out = Reporter::capturing(crate::output::Verbosity::Normal);
+
+        run(&db, &out, id).unwrap();
+        assert!(out.output().contains("already top-level"));
+        assert!(db
+            .get_history(id)
+            .unwrap()
+            .iter()
+            .all(|e| e.kind != "parent"));
+    }
+
+    #[test]
+    fn test_promote_missing_issue() {
+        let (db, _dir) = setup_test_db();
+        assert!(run(&db, &Reporter::default(), 99).is_err());
+    }
+}
@@ -1,31 +1,42 @@
 use anyhow::Result;
 
 use crate::db::Database;
+use crate::output::Reporter;
 
-pub fn add(db: &Database, issue_id: i64, related_id: i64) -> Result<()> {
+pub fn add(db: &Database, out: &Reporter, issue_id: i64, related_id: i64) -> Result<()> {
     db.require_issue(issue_id)?;
     db.require_issue(related_id)?;
 
     if db.add_relation(issue_id, related_id)? {
-        println!("Linked #{} ↔ #{}", issue_id, related_id);
+        out.info(&format!("Linked #{} ↔ #{}", issue_id, related_id));
+        out.detail(&format!(
+            "  relations: added issue_id_1={} issue_id_2={}",
+            issue_id.min(related_id),
+            issue_id.max(related_id)
+        ));
     } else {
-        println!(
+        out.info(&format!(
             "Issues #{} and #{} are already related",
             issue_id, related_id
-        );
+        ));
     }
 
     Ok(())
 }
 
-pub fn remove(db: &Database, issue_id: i64, related_id: i64) -> Result<()> {
+pub fn remove(db: &Database, out: &Reporter, issue_id: i64, related_id: i64) -> Result<()> {
     if db.remove_relation(issue_id, related_id)? {
-        println!("Unlinked #{} ↔ #{}", issue_id, related_id);
+        out.info(&format!("Unlinked #{} ↔ #{}", issue_id, related_id));
+        out.detail(&format!(
+            "  relations: removed issue_id_1={} issue_id_2={}",
+            issue_id.min(related_id),
+            issue_id.max(related_id)
+        ));
     } else {
-        println!(
+        out.info(&format!(
             "No relation found between #{} and #{}",
             issue_id, related_id
-        );
+        ));
     }
 
     Ok(())
@@ -72,7 +83,7 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        let result = add(&db, id1, id2);
+        let result = add(&db, &Reporter::default(), id1, id2);
         assert!(result.is_ok());
 
         let related = db.get_related_issues(id1).unwrap();
@@ -86,7 +97,7 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        add(&db, id1, id2).unwrap();
+        add(&db, &Reporter::default(), id1, id2).unwrap();
 
         let related1 = db.get_related_issues(id1).unwrap();
         let related2 = db.get_related_issues(id2).unwrap();
@@ -99,7 +110,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Issue 1", None, "medium").unwrap();
 
-        let result = add(&db, id, 99999);
+        let result = add(&db, &Reporter::default(), id, 99999);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -110,8 +121,8 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        add(&db, id1, id2).unwrap();
-        let result = add(&db, id1, id2);
+        add(&db, &Reporter::default(), id1, id2).unwrap();
+        let result = add(&db, &Reporter::default(), id1, id2);
         assert!(result.is_ok());
 
         let related = db.get_related_issues(id1).unwrap();
@@ -124,8 +135,8 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        add(&db, id1, id2).unwrap();
-        let result = remove(&db, id1, id2);
+        add(&db, &Reporter::default(), id1, id2).unwrap();
+        let result = remove(&db, &Reporter::default(), id1, id2);
         assert!(result.is_ok());
 
         let related = db.get_related_issues(id1).unwrap();
@@ -138,7 +149,7 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        let result = remove(&db, id1, id2);
+        let result = remove(&db, &Reporter::default(), id1, id2);
         assert!(result.is_ok());
     }
 
@@ -149,8 +160,8 @@ mod tests {
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
         let id3 = db.create_issue("Issue 3", None, "medium").unwrap();
 
-        add(&db, id1, id2).unwrap();
-        add(&db, id1, id3).unwrap();
+        add(&db, &Reporter::default(), id1, id2).unwrap();
+        add(&db, &Reporter::default(), id1, id3).unwrap();
 
         let result = list(&db, id1);
         assert!(result.is_ok());
@@ -183,11 +194,11 @@ mod tests {
                 let id1 = ids[a as usize % ids.len()];
                 let id2 = ids[b as usize % ids.len()];
 
-                add(&db, id1, id2).unwrap();
+                add(&db, &Reporter::default(), id1, id2).unwrap();
                 let related = db.get_related_issues(id1).unwrap();
                 prop_assert!(!related.is_empty());
 
-                remove(&db, id1, id2).unwrap();
+                remove(&db, &Reporter::default(), id1, id2).unwrap();
                 let related = db.get_related_issues(id1).unwrap();
                 prop_assert!(related.is_empty());
             }
@@ -1,23 +1,73 @@
 use anyhow::Result;
 use serde_json;
 
-use crate::db::Database;
+use crate::db::{Database, SearchOptions};
+use crate::models::Issue;
+use crate::utils::bold;
 
-pub fn run_json(db: &Database, query: &str) -> Result<()> {
-    let results = db.search_issues(query)?;
+fn find(db: &Database, query: &str, opts: &SearchOptions) -> Result<Vec<Issue>> {
+    if let Some(root) = opts.under {
+        db.require_issue(root)?;
+    }
+    Ok(db.search_issues(query, opts)?)
+}
+
+pub fn run_json(db: &Database, query: &str, opts: &SearchOptions) -> Result<()> {
+    let results = find(db, query, opts)?;
     println!("{}", serde_json::to_string_pretty(&results)?);
     Ok(())
 }
 
-pub fn run(db: &Database, query: &str) -> Result<()> {
-    let results = db.search_issues(query)?;
+/// Emphasize every case-insensitive occurrence of `query` in `text`:
+/// bold when `color` is set, otherwise wrapped in `*...*`.
+fn highlight(text: &str, query: &str, color: bool) -> String {
+    if query.is_empty() {
+        return text.to_string();
+    }
+
+    // ASCII folding keeps byte offsets aligned with the original text,
+    // and matches SQLite's NOCASE semantics used by search_issues.
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+
+    let mut out = String::new();
+    let mut last = 0;
+    for (start, _) in haystack.match_indices(&needle) {
+        if start < last {
+            continue;
+        }
+        let end = start + needle.len();
+        out.push_str(&text[last..start]);
+        let matched = &text[start..end];
+        if color {
+            out.push_str(&bold(matched));
+        } else {
+            out.push_str(&format!("*{}*", matched));
+        }
+        last = end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+pub fn run(db: &Database, query: &str, opts: &SearchOptions, color: bool) -> Result<()> {
+    let results = find(db, query, opts)?;
+    let scope = opts
+        .under
+        .map(|r| format!(" under #{}", r))
+        .unwrap_or_default();
 
     if results.is_empty() {
-        println!("No issues found matching '{}'", query);
+        println!("No issues found matching '{}'{}", query, scope);
         return Ok(());
     }
 
-    println!("Found {} issue(s) matching '{}':\n", results.len(), query);
+    println!(
+        "Found {} issue(s) matching '{}'{}:\n",
+        results.len(),
+        query,
+        scope
+    );
 
     for issue in results {
         let status_marker = if issue.status == "closed" { "✓" } else { " " };
@@ -31,7 +81,7 @@ pub fn run(db: &Database, query: &str) -> Result<()> {
             issue.id,
             status_marker,
             issue.priority,
-            issue.title,
+            highlight(&issue.title, query, color),
             parent_str,
             if issue.status == "closed" {
                 "(closed)"
@@ -45,7 +95,11 @@ pub fn run(db: &Database, query: &str) -> Result<()> {
             if desc.to_lowercase().contains(&query.to_lowercase()) {
                 let preview: String = desc.chars().take(60).collect();
                 let suffix = if desc.chars().count() > 60 { "..." } else { "" };
-                println!("      └─ {}{}", preview.replace('\n', " "), suffix);
+                println!(
+                    "      └─ {}{}",
+                    highlight(&preview.replace('\n', " "), query, color),
+                    suffix
+                );
             }
         }
     }
@@ -66,8 +120,90 @@ mod tests {
         (db, dir)
     }
 
+    fn under(root: i64) -> SearchOptions {
+        SearchOptions {
+            under: Some(root),
+            ..Default::default()
+        }
+    }
+
+    fn prefix() -> SearchOptions {
+        SearchOptions {
+            prefix: true,
+            ..Default::default()
+        }
+    }
+
     // ==================== Unit Tests ====================
 
+    #[test]
+    fn test_prefix_vs_substring() {
+        let (db, _dir) = setup_test_db();
+        let word_start = db
+            .create_issue("Update Description field", None, "medium")
+            .unwrap();
+        let mid_word = db
+            .create_issue("Fix undescribed crash", None, "medium")
+            .unwrap();
+        let in_desc = db
+            .create_issue("Docs", Some("See the design-desc notes"), "low")
+            .unwrap();
+        let in_comment = db.create_issue("Other", None, "low").unwrap();
+        db.add_comment(in_comment, "desc is wrong").unwrap();
+
+        let ids = |opts: &SearchOptions| -> Vec<i64> {
+            let mut ids: Vec<i64> = db
+                .search_issues("desc", opts)
+                .unwrap()
+                .iter()
+                .map(|i| i.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        // Substring finds every occurrence, including mid-word and in comments
+        assert_eq!(
+            ids(&SearchOptions::default()),
+            vec![word_start, mid_word, in_desc, in_comment]
+        );
+        // Prefix only matches at word starts in title or description
+        assert_eq!(ids(&prefix()), vec![word_start, in_desc]);
+    }
+
+    #[test]
+    fn test_prefix_combines_with_under() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let child = db
+            .create_subissue(epic, "Render pass", None, "medium")
+            .unwrap();
+        db.create_subissue(epic, "Prerender cache", None, "medium")
+            .unwrap();
+        db.create_issue("Render outside", None, "medium").unwrap();
+
+        let opts = SearchOptions {
+            under: Some(epic),
+            prefix: true,
+            ..Default::default()
+        };
+        let results = db.search_issues("render", &opts).unwrap();
+        let ids: Vec<i64> = results.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![child]);
+    }
+
+    #[test]
+    fn test_prefix_is_injection_safe() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("100% done", None, "medium").unwrap();
+        assert_eq!(db.search_issues("100%", &prefix()).unwrap().len(), 1);
+        // "%" is matched literally, and here it never starts a word
+        assert!(db.search_issues("%", &prefix()).unwrap().is_empty());
+        db.search_issues("'; DROP TABLE issues; --", &prefix())
+            .unwrap();
+        assert_eq!(db.list_issues(None, None, None).unwrap().len(), 1);
+    }
+
     #[test]
     fn test_search_finds_by_title() {
         let (db, _dir) = setup_test_db();
@@ -76,8 +212,10 @@ mod tests {
             .unwrap();
         db.create_issue("Add dark mode", None, "medium").unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("authentication", &SearchOptions::default())
+            .unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, id);
     }
@@ -89,8 +227,10 @@ mod tests {
             .create_issue("Feature A", Some("This relates to user login"), "medium")
             .unwrap();
 
-        run(&db, "login").unwrap();
-        let results = db.search_issues("login").unwrap();
+        run(&db, "login", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("login", &SearchOptions::default())
+            .unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, id);
     }
@@ -102,8 +242,10 @@ mod tests {
             .create_issue("Fix AUTHENTICATION Bug", None, "high")
             .unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("authentication", &SearchOptions::default())
+            .unwrap();
         assert_eq!(
             results.len(),
             1,
@@ -117,8 +259,10 @@ mod tests {
         let (db, _dir) = setup_test_db();
         db.create_issue("Some issue", None, "medium").unwrap();
 
-        run(&db, "nonexistent").unwrap();
-        let results = db.search_issues("nonexistent").unwrap();
+        run(&db, "nonexistent", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("nonexistent", &SearchOptions::default())
+            .unwrap();
         assert!(
             results.is_empty(),
             "Search for nonexistent term should return empty"
@@ -129,8 +273,10 @@ mod tests {
     fn test_search_empty_database() {
         let (db, _dir) = setup_test_db();
 
-        run(&db, "anything").unwrap();
-        let results = db.search_issues("anything").unwrap();
+        run(&db, "anything", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("anything", &SearchOptions::default())
+            .unwrap();
         assert!(results.is_empty());
     }
 
@@ -139,8 +285,8 @@ mod tests {
         let (db, _dir) = setup_test_db();
         db.create_issue("Test issue", None, "medium").unwrap();
 
-        run(&db, "").unwrap();
-        let _results = db.search_issues("").unwrap();
+        run(&db, "", &SearchOptions::default(), false).unwrap();
+        let _results = db.search_issues("", &SearchOptions::default()).unwrap();
         // Empty query behavior: may match all or none depending on implementation
         // Just verify it doesn't error
     }
@@ -152,8 +298,10 @@ mod tests {
             .create_issue("Fix bug with @mentions", None, "medium")
             .unwrap();
 
-        run(&db, "@mentions").unwrap();
-        let results = db.search_issues("@mentions").unwrap();
+        run(&db, "@mentions", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("@mentions", &SearchOptions::default())
+            .unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, id);
     }
@@ -165,8 +313,10 @@ mod tests {
             .create_issue("Fix 日本語 support", None, "medium")
             .unwrap();
 
-        run(&db, "日本語").unwrap();
-        let results = db.search_issues("日本語").unwrap();
+        run(&db, "日本語", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("日本語", &SearchOptions::default())
+            .unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, id);
     }
@@ -176,7 +326,13 @@ mod tests {
         let (db, _dir) = setup_test_db();
         db.create_issue("Normal issue", None, "medium").unwrap();
 
-        run(&db, "'; DROP TABLE issues; --").unwrap();
+        run(
+            &db,
+            "'; DROP TABLE issues; --",
+            &SearchOptions::default(),
+            false,
+        )
+        .unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert_eq!(
             issues.len(),
@@ -191,8 +347,10 @@ mod tests {
         db.create_issue("Test issue with pattern", None, "medium")
             .unwrap();
 
-        run(&db, "%pattern%").unwrap();
-        let results = db.search_issues("%pattern%").unwrap();
+        run(&db, "%pattern%", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("%pattern%", &SearchOptions::default())
+            .unwrap();
         // SQL wildcards should be escaped -- literal "%pattern%" should NOT match "pattern"
         assert!(
             results.is_empty(),
@@ -207,8 +365,10 @@ mod tests {
         db.add_comment(id, "Found the root cause in authentication module")
             .unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("authentication", &SearchOptions::default())
+            .unwrap();
         assert_eq!(
             results.len(),
             1,
@@ -225,8 +385,10 @@ mod tests {
             .create_subissue(parent_id, "Sub task authentication", None, "medium")
             .unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("authentication", &SearchOptions::default())
+            .unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, sub_id);
         assert_eq!(results[0].parent_id, Some(parent_id));
@@ -240,41 +402,110 @@ mod tests {
             .unwrap();
         db.close_issue(id).unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", &SearchOptions::default(), false).unwrap();
+        let results = db
+            .search_issues("authentication", &SearchOptions::default())
+            .unwrap();
         assert_eq!(results.len(), 1, "Search should find closed issues too");
         assert_eq!(results[0].status, "closed");
     }
 
+    #[test]
+    fn test_search_under_excludes_outside_subtree() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Storage epic", None, "high").unwrap();
+        let child = db
+            .create_subissue(epic, "Tune db indexes", None, "medium")
+            .unwrap();
+        let grandchild = db
+            .create_subissue(child, "Benchmark db writes", None, "low")
+            .unwrap();
+        db.create_issue("Migrate db driver", None, "medium")
+            .unwrap();
+
+        run(&db, "db", &under(epic), false).unwrap();
+        let results = db.search_issues("db", &under(epic)).unwrap();
+        let ids: Vec<i64> = results.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![grandchild, child]);
+    }
+
+    #[test]
+    fn test_search_under_excludes_root_and_escapes_wildcards() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("100% epic", None, "high").unwrap();
+        db.create_subissue(epic, "Plain child", None, "medium")
+            .unwrap();
+
+        assert!(db.search_issues("100%", &under(epic)).unwrap().is_empty());
+        assert!(db.search_issues("%", &under(epic)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_under_nonexistent_root() {
+        let (db, _dir) = setup_test_db();
+        let result = run(&db, "anything", &under(99999), false);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_highlight_plain_wraps_match() {
+        assert_eq!(
+            highlight("Fix DB migration", "db", false),
+            "Fix *DB* migration"
+        );
+        assert_eq!(highlight("db and db", "db", false), "*db* and *db*");
+        assert_eq!(highlight("no match", "db", false), "no match");
+    }
+
+    #[test]
+    fn test_highlight_color_uses_bold() {
+        assert_eq!(
+            highlight("Fix db", "db", true),
+            format!("Fix {}", bold("db"))
+        );
+    }
+
+    #[test]
+    fn test_highlight_unicode_text() {
+        assert_eq!(highlight("日本語 db", "db", false), "日本語 *db*");
+        assert_eq!(highlight("日本語", "本", false), "日*本*語");
+    }
+
     // ==================== Property-Based Tests ====================
 
     proptest! {
+        #[test]
+        fn prop_highlight_preserves_text(text in ".{0,50}", query in ".{0,5}") {
+            let plain = highlight(&text, &query, false);
+            prop_assert_eq!(plain.replace('*', ""), text.replace('*', ""));
+        }
+
         #[test]
         fn prop_search_never_panics(query in ".*") {
             let (db, _dir) = setup_test_db();
             db.create_issue("Test issue", None, "medium").unwrap();
-            let _ = run(&db, &query);
+            let _ = run(&db, &query, &SearchOptions::default(), false);
         }
 
         #[test]
         fn prop_search_with_issues_never_panics(
-            title in "[a-zA-Z0-9 ]{1,50}",
+            title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,49}",
             query in "[a-zA-Z0-9]{1,20}"
         ) {
             let (db, _dir) = setup_test_db();
             db.create_issue(&title, None, "medium").unwrap();
-            let result = run(&db, &query);
+            let result = run(&db, &query, &SearchOptions::default(), false);
             prop_assert!(result.is_ok());
         }
 
         #[test]
         fn prop_search_unicode_never_panics(
-            title in "[\\p{L}\\p{N} ]{1,30}",
+            title in "[\\p{L}\\p{N}][\\p{L}\\p{N} ]{0,29}",
             query in "[\\p{L}\\p{N}]{1,10}"
         ) {
             let (db, _dir) = setup_test_db();
             db.create_issue(&title, None, "medium").unwrap();
-            let result = run(&db, &query);
+            let result = run(&db, &query, &SearchOptions::default(), false);
             prop_assert!(result.is_ok());
         }
     }
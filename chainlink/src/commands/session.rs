@@ -91,10 +91,7 @@ pub fn work(db: &Database, issue_id: i64) -> Result<()> {
         None => bail!("No active session. Use 'chainlink session start' first."),
     };
 
-    let issue = match db.get_issue(issue_id)? {
-        Some(i) => i,
-        None => bail!("Issue #{} not found", issue_id),
-    };
+    let issue = db.require_issue(issue_id)?;
 
     db.set_session_issue(session.id, issue_id)?;
     println!("Now working on: #{} {}", issue.id, issue.title);
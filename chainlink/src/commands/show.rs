@@ -1,35 +1,52 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
 use serde::Serialize;
 use serde_json;
 
 use crate::db::Database;
+use crate::models::Event;
+use crate::utils::format_duration;
 
 #[derive(Serialize)]
 struct IssueDetail {
     #[serde(flatten)]
     issue: crate::models::Issue,
+    slug: Option<String>,
     labels: Vec<String>,
     milestone: Option<crate::models::Milestone>,
     comments: Vec<crate::models::Comment>,
+    history: Vec<crate::models::Event>,
     blocked_by: Vec<i64>,
     blocking: Vec<i64>,
+    duplicate_of: Vec<i64>,
+    duplicated_by: Vec<i64>,
+    assignee: Option<String>,
+    estimate: Option<i64>,
+    due_date: Option<chrono::NaiveDate>,
+    url: Option<String>,
+    resolution: Option<String>,
     subissues: Vec<crate::models::Issue>,
     related: Vec<crate::models::Issue>,
 }
 
 pub fn run_json(db: &Database, id: i64) -> Result<()> {
-    let issue = match db.get_issue(id)? {
-        Some(i) => i,
-        None => bail!("Issue #{} not found", id),
-    };
+    let issue = db.require_issue(id)?;
 
     let detail = IssueDetail {
         issue,
+        slug: db.get_slug(id)?,
         labels: db.get_labels(id)?,
         milestone: db.get_issue_milestone(id)?,
         comments: db.get_comments(id)?,
+        history: db.get_history(id)?,
         blocked_by: db.get_blockers(id)?,
         blocking: db.get_blocking(id)?,
+        duplicate_of: db.get_duplicate_of(id)?,
+        duplicated_by: db.get_duplicated_by(id)?,
+        assignee: db.get_assignee(id)?,
+        estimate: db.get_estimate(id)?,
+        due_date: db.get_due_date(id)?,
+        url: db.get_url(id)?,
+        resolution: db.get_resolution(id)?,
         subissues: db.get_subissues(id)?,
         related: db.get_related_issues(id)?,
     };
@@ -38,13 +55,34 @@ pub fn run_json(db: &Database, id: i64) -> Result<()> {
     Ok(())
 }
 
+/// One-line summary of a history event, e.g. "priority medium → high".
+fn describe_event(event: &Event) -> String {
+    let old = event.old_value.as_deref();
+    let new = event.new_value.as_deref();
+    match (event.kind.as_str(), old, new) {
+        ("created", _, _) => "created".to_string(),
+        ("status", Some("closed"), Some("open")) => "reopened".to_string(),
+        ("status", Some("archived"), _) => "unarchived".to_string(),
+        ("status", _, Some("closed")) => "closed".to_string(),
+        ("status", _, Some("archived")) => "archived".to_string(),
+        ("parent", _, Some(parent)) => format!("moved under #{}", parent),
+        ("parent", _, None) => "moved to top level".to_string(),
+        (kind, old, new) => format!(
+            "{} {} → {}",
+            kind,
+            old.unwrap_or("(none)"),
+            new.unwrap_or("(none)")
+        ),
+    }
+}
+
 pub fn run(db: &Database, id: i64) -> Result<()> {
-    let issue = match db.get_issue(id)? {
-        Some(i) => i,
-        None => bail!("Issue #{} not found", id),
-    };
+    let issue = db.require_issue(id)?;
 
     println!("Issue #{}: {}", issue.id, issue.title);
+    if let Some(slug) = db.get_slug(id)? {
+        println!("Slug: {}", slug);
+    }
     println!("Status: {}", issue.status);
     println!("Priority: {}", issue.priority);
     if let Some(parent_id) = issue.parent_id {
@@ -56,6 +94,28 @@ pub fn run(db: &Database, id: i64) -> Result<()> {
     if let Some(closed) = issue.closed_at {
         println!("Closed: {}", closed.format("%Y-%m-%d %H:%M:%S"));
     }
+    if let Some(resolution) = db.get_resolution(id)? {
+        println!("Resolution: {}", resolution);
+    }
+    if let Some(took) = db.time_to_close(id)? {
+        println!("Time to close: {}", format_duration(took));
+    }
+
+    if let Some(assignee) = db.get_assignee(id)? {
+        println!("Assignee: {}", assignee);
+    }
+
+    if let Some(estimate) = db.get_estimate(id)? {
+        println!("Estimate: {}", estimate);
+    }
+
+    if let Some(due) = db.get_due_date(id)? {
+        println!("Due: {}", due);
+    }
+
+    if let Some(url) = db.get_url(id)? {
+        println!("URL: {}", url);
+    }
 
     // Labels
     let labels = db.get_labels(id)?;
@@ -91,6 +151,19 @@ pub fn run(db: &Database, id: i64) -> Result<()> {
         }
     }
 
+    // History
+    let history = db.get_history(id)?;
+    if !history.is_empty() {
+        println!("\nHistory:");
+        for event in &history {
+            println!(
+                "  [{}] {}",
+                event.created_at.format("%Y-%m-%d %H:%M"),
+                describe_event(event)
+            );
+        }
+    }
+
     // Dependencies
     let blockers = db.get_blockers(id)?;
     let blocking = db.get_blocking(id)?;
@@ -104,10 +177,22 @@ pub fn run(db: &Database, id: i64) -> Result<()> {
     }
 
     if blocking.is_empty() {
-        println!("Blocking: (none)");
+        println!("Blocks: (none)");
     } else {
         let blocking_strs: Vec<String> = blocking.iter().map(|b| format!("#{}", b)).collect();
-        println!("Blocking: {}", blocking_strs.join(", "));
+        println!("Blocks: {}", blocking_strs.join(", "));
+    }
+
+    let duplicate_of = db.get_duplicate_of(id)?;
+    if !duplicate_of.is_empty() {
+        let strs: Vec<String> = duplicate_of.iter().map(|d| format!("#{}", d)).collect();
+        println!("Duplicate of: {}", strs.join(", "));
+    }
+
+    let duplicated_by = db.get_duplicated_by(id)?;
+    if !duplicated_by.is_empty() {
+        let strs: Vec<String> = duplicated_by.iter().map(|d| format!("#{}", d)).collect();
+        println!("Duplicated by: {}", strs.join(", "));
     }
 
     // Subissues
@@ -217,6 +302,41 @@ mod tests {
         assert_eq!(comments[1].content, "Second comment");
     }
 
+    #[test]
+    fn test_show_issue_history() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        db.update_issue(id, None, None, Some("high")).unwrap();
+        db.update_parent(id, Some(parent)).unwrap();
+        db.close_issue(id).unwrap();
+        db.archive_issue(id).unwrap();
+        db.unarchive_issue(id).unwrap();
+        db.reopen_issue(id).unwrap();
+        db.update_parent(id, None).unwrap();
+
+        run(&db, id).unwrap();
+        let described: Vec<String> = db
+            .get_history(id)
+            .unwrap()
+            .iter()
+            .map(describe_event)
+            .collect();
+        assert_eq!(
+            described,
+            vec![
+                "created".to_string(),
+                "priority medium → high".to_string(),
+                format!("moved under #{}", parent),
+                "closed".to_string(),
+                "archived".to_string(),
+                "unarchived".to_string(),
+                "reopened".to_string(),
+                "moved to top level".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn test_show_issue_with_blockers() {
         let (db, _dir) = setup_test_db();
@@ -341,7 +461,7 @@ mod tests {
 
     proptest! {
         #[test]
-        fn prop_show_never_panics(title in "[a-zA-Z0-9 ]{1,50}") {
+        fn prop_show_never_panics(title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,49}") {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue(&title, None, "medium").unwrap();
             let result = run(&db, issue_id);
@@ -357,7 +477,7 @@ mod tests {
 
         #[test]
         fn prop_show_with_description_never_panics(
-            title in "[a-zA-Z0-9 ]{1,30}",
+            title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,29}",
             desc in "[a-zA-Z0-9 \n]{0,200}"
         ) {
             let (db, _dir) = setup_test_db();
@@ -368,7 +488,7 @@ mod tests {
 
         #[test]
         fn prop_show_unicode_never_panics(
-            title in "[\\p{L}\\p{N} ]{1,30}"
+            title in "[\\p{L}\\p{N}][\\p{L}\\p{N} ]{0,29}"
         ) {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue(&title, None, "medium").unwrap();
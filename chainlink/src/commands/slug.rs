@@ -0,0 +1,63 @@
+use anyhow::{bail, Result};
+
+use crate::db::Database;
+use crate::output::Reporter;
+
+pub fn run(db: &Database, out: &Reporter, id: i64, slug: Option<&str>) -> Result<()> {
+    let found = match slug {
+        Some(slug) => db.set_slug(id, slug)?,
+        None => db.clear_slug(id)?,
+    };
+    if !found {
+        bail!("Issue #{} not found", id);
+    }
+
+    match slug {
+        Some(slug) => out.info(&format!("Issue #{} is now '{}'", id, slug.trim())),
+        None => out.info(&format!("Cleared slug of issue #{}", id)),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_set_and_clear_slug() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Login fails", None, "medium").unwrap();
+
+        run(&db, &Reporter::default(), id, Some("login-bug")).unwrap();
+        assert_eq!(db.get_slug(id).unwrap(), Some("login-bug".to_string()));
+
+        run(&db, &Reporter::default(), id, None).unwrap();
+        assert_eq!(db.get_slug(id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_duplicate_slug_rejected() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+
+        run(&db, &Reporter::default(), first, Some("login-bug")).unwrap();
+        assert!(run(&db, &Reporter::default(), second, Some("login-bug")).is_err());
+        assert_eq!(db.get_slug(second).unwrap(), None);
+    }
+
+    #[test]
+    fn test_slug_nonexistent_issue() {
+        let (db, _dir) = setup_test_db();
+        assert!(run(&db, &Reporter::default(), 99999, Some("ghost")).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+
+use crate::db::Database;
+use crate::utils::{pad, truncate};
+
+/// Build the stale issue listing as of `now`, without printing it.
+pub fn render(db: &Database, days: i64, now: DateTime<Utc>) -> Result<String> {
+    if days < 0 {
+        bail!("--days must not be negative");
+    }
+
+    let issues = db.list_stale(days, &now.to_rfc3339())?;
+
+    if issues.is_empty() {
+        return Ok(format!("No open issues untouched for {} days.\n", days));
+    }
+
+    let mut out = format!("Open issues untouched for {}+ days (oldest first):\n", days);
+    for issue in issues {
+        let age = (now - issue.updated_at).num_days();
+        out.push_str(&format!(
+            "  #{:<4} {} {:>4}d  {}\n",
+            issue.id,
+            pad(&truncate(&issue.title, 40), 40),
+            age,
+            issue.priority
+        ));
+    }
+
+    Ok(out)
+}
+
+pub fn run(db: &Database, days: i64) -> Result<()> {
+    print!("{}", render(db, days, Utc::now())?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_stale_run() {
+        let (db, _dir) = setup_test_db();
+        run(&db, 30).unwrap();
+        assert!(render(&db, 30, Utc::now())
+            .unwrap()
+            .starts_with("No open issues untouched for 30 days."));
+
+        let old = db.create_issue("Old", None, "low").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let cutoff = Utc::now();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let fresh = db.create_issue("Fresh", None, "medium").unwrap();
+
+        // Seen from 30 days on, "Old" was last touched before the cutoff and "Fresh" after it
+        let out = render(&db, 30, cutoff + chrono::Duration::days(30)).unwrap();
+        assert!(out.starts_with("Open issues untouched for 30+ days"));
+        assert!(out.contains(&format!("#{:<4} Old", old)), "{}", out);
+        assert!(out.contains("  30d  low"), "{}", out);
+        assert!(!out.contains(&format!("#{:<4} Fresh", fresh)), "{}", out);
+    }
+
+    #[test]
+    fn test_stale_negative_days() {
+        let (db, _dir) = setup_test_db();
+        assert!(run(&db, -1).is_err());
+    }
+}
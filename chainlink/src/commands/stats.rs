@@ -0,0 +1,62 @@
+use crate::db::Database;
+use crate::utils::format_duration;
+use anyhow::Result;
+
+/// Issue counts by status, plus the average time to close with `cycle_time`.
+pub fn render(db: &Database, cycle_time: bool) -> Result<String> {
+    let mut out = String::new();
+    for status in ["open", "closed", "archived"] {
+        let count = db.list_issues(Some(status), None, None)?.len();
+        out.push_str(&format!("{:<9} {}\n", format!("{}:", status), count));
+    }
+
+    if cycle_time {
+        match db.average_time_to_close()? {
+            Some(avg) => out.push_str(&format!(
+                "Average time to close: {}\n",
+                format_duration(avg)
+            )),
+            None => out.push_str("Average time to close: n/a (no closed issues)\n"),
+        }
+    }
+    Ok(out)
+}
+
+pub fn run(db: &Database, cycle_time: bool) -> Result<()> {
+    print!("{}", render(db, cycle_time)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_render_counts_and_cycle_time() {
+        let (db, _dir) = setup_test_db();
+        let closed = db.create_issue("Done", None, "medium").unwrap();
+        db.create_issue("Pending", None, "medium").unwrap();
+
+        let out = render(&db, true).unwrap();
+        assert!(out.contains("open:     2"));
+        assert!(out.contains("closed:   0"));
+        assert!(out.contains("n/a (no closed issues)"));
+
+        db.close_issue(closed).unwrap();
+        let out = render(&db, false).unwrap();
+        assert!(out.contains("open:     1"));
+        assert!(out.contains("closed:   1"));
+        assert!(!out.contains("Average time to close"));
+        assert!(render(&db, true)
+            .unwrap()
+            .contains("Average time to close: 0m"));
+    }
+}
@@ -1,47 +1,40 @@
-use anyhow::{bail, Context, Result};
+use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
 
 use crate::db::Database;
+use crate::output::Reporter;
+use crate::utils::confirm;
 
-pub fn close(db: &Database, id: i64, update_changelog: bool, chainlink_dir: &Path) -> Result<()> {
-    close_inner(db, id, update_changelog, chainlink_dir, false)
-}
-
-pub fn close_quiet(
-    db: &Database,
-    id: i64,
-    update_changelog: bool,
-    chainlink_dir: &Path,
-) -> Result<()> {
-    close_inner(db, id, update_changelog, chainlink_dir, true)
-}
-
-fn close_inner(
+/// Close an issue. With `changelog_dir` (the `.chainlink` directory), the
+/// issue is also added to the project's CHANGELOG.md.
+pub fn close(
     db: &Database,
+    out: &Reporter,
     id: i64,
-    update_changelog: bool,
-    chainlink_dir: &Path,
-    quiet: bool,
+    note: Option<&str>,
+    changelog_dir: Option<&Path>,
 ) -> Result<()> {
     // Get issue details before closing
-    let issue = db.get_issue(id)?;
-    let issue = match issue {
-        Some(i) => i,
-        None => bail!("Issue #{} not found", id),
-    };
+    let issue = db.require_issue(id)?;
     let labels = db.get_labels(id)?;
 
-    if db.close_issue(id)? {
-        if !quiet {
-            println!("Closed issue #{}", id);
+    let closed = db.transaction(|| -> Result<bool> {
+        let closed = db.close_issue(id)?;
+        if let (true, Some(note)) = (closed, note) {
+            db.set_resolution(id, note)?;
         }
-    } else {
-        bail!("Issue #{} not found", id);
+        Ok(closed)
+    })?;
+
+    if !closed {
+        out.info(&format!("Issue #{} is already {}", id, issue.status));
+        return Ok(());
     }
+    out.info(&format!("Closed issue #{}", id));
 
     // Update changelog if requested
-    if update_changelog {
+    if let Some(chainlink_dir) = changelog_dir {
         let project_root = chainlink_dir.parent().unwrap_or(chainlink_dir);
         let changelog_path = project_root.join("CHANGELOG.md");
 
@@ -50,7 +43,7 @@ fn close_inner(
             if let Err(e) = create_changelog(&changelog_path) {
                 eprintln!("Warning: Could not create CHANGELOG.md: {}", e);
             } else {
-                println!("Created CHANGELOG.md");
+                out.info("Created CHANGELOG.md");
             }
         }
 
@@ -60,8 +53,8 @@ fn close_inner(
 
             if let Err(e) = append_to_changelog(&changelog_path, &category, &entry) {
                 eprintln!("Warning: Could not update CHANGELOG.md: {}", e);
-            } else if !quiet {
-                println!("Added to CHANGELOG.md under {}", category);
+            } else {
+                out.info(&format!("Added to CHANGELOG.md under {}", category));
             }
         }
     }
@@ -148,35 +141,73 @@ fn append_to_changelog(path: &Path, category: &str, entry: &str) -> Result<()> {
 
 pub fn close_all(
     db: &Database,
+    out: &Reporter,
     label_filter: Option<&str>,
     priority_filter: Option<&str>,
-    update_changelog: bool,
-    chainlink_dir: &Path,
+    changelog_dir: Option<&Path>,
+    force: bool,
+    dry_run: bool,
 ) -> Result<()> {
     let issues = db.list_issues(Some("open"), label_filter, priority_filter)?;
 
     if issues.is_empty() {
-        println!("No matching open issues found.");
+        out.info("No matching open issues found.");
+        return Ok(());
+    }
+
+    if dry_run {
+        // Close for real inside a rolled-back transaction; the changelog is left alone
+        let closed = db.dry_run(|| -> Result<Vec<i64>> {
+            let mut closed = Vec::new();
+            for issue in &issues {
+                if db.close_issue(issue.id)? {
+                    closed.push(issue.id);
+                }
+            }
+            Ok(closed)
+        })?;
+        for issue in issues.iter().filter(|i| closed.contains(&i.id)) {
+            out.result(&format!("Would close #{} {}", issue.id, issue.title));
+        }
+        out.result(&format!("Would close {} issue(s).", closed.len()));
+        out.result("Dry run: nothing was changed.");
+        return Ok(());
+    }
+
+    let prompt = format!("Close {} open issue(s)?", issues.len());
+    if !confirm(&prompt, force)? {
+        out.info("Cancelled.");
         return Ok(());
     }
 
     let mut closed_count = 0;
     for issue in &issues {
-        match close(db, issue.id, update_changelog, chainlink_dir) {
+        match close(db, out, issue.id, None, changelog_dir) {
             Ok(()) => closed_count += 1,
             Err(e) => eprintln!("Warning: Failed to close #{}: {}", issue.id, e),
         }
     }
 
-    println!("Closed {} issue(s).", closed_count);
+    out.info(&format!("Closed {} issue(s).", closed_count));
     Ok(())
 }
 
-pub fn reopen(db: &Database, id: i64) -> Result<()> {
-    if db.reopen_issue(id)? {
-        println!("Reopened issue #{}", id);
+pub fn reopen(db: &Database, out: &Reporter, id: i64, recursive: bool) -> Result<()> {
+    db.require_issue(id)?;
+    if recursive {
+        let count = db.reopen_issue_recursive(id)?;
+        if count == 0 {
+            out.info(&format!("Issue #{} and its subissues are already open", id));
+        } else {
+            out.info(&format!(
+                "Reopened {} issue(s) under and including #{}",
+                count, id
+            ));
+        }
+    } else if db.reopen_issue(id)? {
+        out.info(&format!("Reopened issue #{}", id));
     } else {
-        bail!("Issue #{} not found", id);
+        out.info(&format!("Issue #{} is already open", id));
     }
     Ok(())
 }
@@ -184,6 +215,7 @@ pub fn reopen(db: &Database, id: i64) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::Verbosity;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -194,6 +226,27 @@ mod tests {
         (db, dir)
     }
 
+    #[test]
+    fn test_close_all_dry_run_changes_nothing() {
+        let (db, dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "low").unwrap();
+
+        let chainlink_dir = dir.path().join(".chainlink");
+
+        let out = Reporter::capturing(Verbosity::Normal);
+        close_all(&db, &out, None, None, Some(&chainlink_dir), false, true).unwrap();
+
+        assert_eq!(db.get_issue(a).unwrap().unwrap().status, "open");
+        assert_eq!(db.get_issue(b).unwrap().unwrap().status, "open");
+        assert!(!dir.path().join("CHANGELOG.md").exists());
+        assert!(out.output().contains("Would close 2 issue(s)."));
+
+        let quiet = Reporter::capturing(Verbosity::Quiet);
+        close_all(&db, &quiet, None, None, None, false, true).unwrap();
+        assert!(quiet.output().contains("Would close 2 issue(s)."));
+    }
+
     // ==================== Close Tests ====================
 
     #[test]
@@ -204,7 +257,7 @@ mod tests {
 
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = close(&db, issue_id, false, &chainlink_dir);
+        let result = close(&db, &Reporter::default(), issue_id, None, None);
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -212,13 +265,71 @@ mod tests {
         assert!(issue.closed_at.is_some());
     }
 
+    #[test]
+    fn test_close_and_reopen_report_through_reporter() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Chatty", None, "medium").unwrap();
+
+        let out = Reporter::capturing(Verbosity::Normal);
+        close(&db, &out, id, None, None).unwrap();
+        reopen(&db, &out, id, false).unwrap();
+        assert_eq!(
+            out.output(),
+            format!("Closed issue #{}\nReopened issue #{}\n", id, id)
+        );
+
+        let quiet = Reporter::capturing(Verbosity::Quiet);
+        close(&db, &quiet, id, None, None).unwrap();
+        reopen(&db, &quiet, id, false).unwrap();
+        assert_eq!(quiet.output(), "");
+        assert_eq!(db.get_issue(id).unwrap().unwrap().status, "open");
+    }
+
+    #[test]
+    fn test_close_with_note_records_resolution() {
+        let (db, dir) = setup_test_db();
+        let chainlink_dir = dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+
+        let with_note = db.create_issue("Crash on save", None, "high").unwrap();
+        let without = db.create_issue("Typo", None, "low").unwrap();
+
+        close(
+            &db,
+            &Reporter::default(),
+            with_note,
+            Some("fixed in abc123"),
+            None,
+        )
+        .unwrap();
+        close(&db, &Reporter::default(), without, None, None).unwrap();
+
+        assert_eq!(
+            db.get_resolution(with_note).unwrap().as_deref(),
+            Some("fixed in abc123")
+        );
+        assert_eq!(db.get_resolution(without).unwrap(), None);
+        assert_eq!(db.get_issue(without).unwrap().unwrap().status, "closed");
+    }
+
+    #[test]
+    fn test_close_with_blank_note_leaves_issue_open() {
+        let (db, dir) = setup_test_db();
+        let chainlink_dir = dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        assert!(close(&db, &Reporter::default(), id, Some("  "), None).is_err());
+        assert_eq!(db.get_issue(id).unwrap().unwrap().status, "open");
+    }
+
     #[test]
     fn test_close_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
         let chainlink_dir = _dir.path().join(".chainlink");
         std::fs::create_dir_all(&chainlink_dir).unwrap();
 
-        let result = close(&db, 99999, false, &chainlink_dir);
+        let result = close(&db, &Reporter::default(), 99999, None, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -232,9 +343,16 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
         db.close_issue(issue_id).unwrap();
 
-        // Closing again should be fine (idempotent at db level)
-        let result = close(&db, issue_id, false, &chainlink_dir);
+        // Closing again is a no-op and must not touch the changelog
+        let result = close(
+            &db,
+            &Reporter::default(),
+            issue_id,
+            None,
+            Some(&chainlink_dir),
+        );
         assert!(result.is_ok());
+        assert!(!_dir.path().join("CHANGELOG.md").exists());
     }
 
     // ==================== Reopen Tests ====================
@@ -246,7 +364,7 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
         db.close_issue(issue_id).unwrap();
 
-        let result = reopen(&db, issue_id);
+        let result = reopen(&db, &Reporter::default(), issue_id, false);
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -258,7 +376,7 @@ mod tests {
     fn test_reopen_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
 
-        let result = reopen(&db, 99999);
+        let result = reopen(&db, &Reporter::default(), 99999, false);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -270,13 +388,29 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         // Reopening an open issue - succeeds (idempotent operation)
-        let result = reopen(&db, issue_id);
+        let result = reopen(&db, &Reporter::default(), issue_id, false);
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.status, "open");
     }
 
+    #[test]
+    fn test_reopen_recursive_flag() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Epic", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        db.close_issue(child).unwrap();
+        db.close_issue(parent).unwrap();
+
+        // Without the flag only the parent is reopened
+        reopen(&db, &Reporter::default(), parent, false).unwrap();
+        assert_eq!(db.get_issue(child).unwrap().unwrap().status, "closed");
+
+        reopen(&db, &Reporter::default(), parent, true).unwrap();
+        assert_eq!(db.get_issue(child).unwrap().unwrap().status, "open");
+    }
+
     // ==================== Changelog Category Tests ====================
 
     #[test]
@@ -367,17 +501,17 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         // Close
-        close(&db, issue_id, false, &chainlink_dir).unwrap();
+        close(&db, &Reporter::default(), issue_id, None, None).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.status, "closed");
 
         // Reopen
-        reopen(&db, issue_id).unwrap();
+        reopen(&db, &Reporter::default(), issue_id, false).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.status, "open");
 
         // Close again
-        close(&db, issue_id, false, &chainlink_dir).unwrap();
+        close(&db, &Reporter::default(), issue_id, None, None).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.status, "closed");
     }
@@ -386,26 +520,26 @@ mod tests {
 
     proptest! {
         #[test]
-        fn prop_close_sets_status_to_closed(title in "[a-zA-Z0-9 ]{1,50}") {
+        fn prop_close_sets_status_to_closed(title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,49}") {
             let (db, _dir) = setup_test_db();
             let chainlink_dir = _dir.path().join(".chainlink");
             std::fs::create_dir_all(&chainlink_dir).unwrap();
 
             let issue_id = db.create_issue(&title, None, "medium").unwrap();
-            close(&db, issue_id, false, &chainlink_dir).unwrap();
+            close(&db, &Reporter::default(), issue_id, None, None).unwrap();
 
             let issue = db.get_issue(issue_id).unwrap().unwrap();
             prop_assert_eq!(issue.status, "closed");
         }
 
         #[test]
-        fn prop_reopen_sets_status_to_open(title in "[a-zA-Z0-9 ]{1,50}") {
+        fn prop_reopen_sets_status_to_open(title in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,49}") {
             let (db, _dir) = setup_test_db();
 
             let issue_id = db.create_issue(&title, None, "medium").unwrap();
             db.close_issue(issue_id).unwrap();
 
-            reopen(&db, issue_id).unwrap();
+            reopen(&db, &Reporter::default(), issue_id, false).unwrap();
 
             let issue = db.get_issue(issue_id).unwrap().unwrap();
             prop_assert_eq!(issue.status, "open");
@@ -417,7 +551,7 @@ mod tests {
             let chainlink_dir = _dir.path().join(".chainlink");
             std::fs::create_dir_all(&chainlink_dir).unwrap();
 
-            let result = close(&db, issue_id, false, &chainlink_dir);
+            let result = close(&db, &Reporter::default(), issue_id, None, None);
             prop_assert!(result.is_err());
         }
 
@@ -425,7 +559,7 @@ mod tests {
         fn prop_nonexistent_issue_reopen_fails(issue_id in 1000i64..10000) {
             let (db, _dir) = setup_test_db();
 
-            let result = reopen(&db, issue_id);
+            let result = reopen(&db, &Reporter::default(), issue_id, false);
             prop_assert!(result.is_err());
         }
 
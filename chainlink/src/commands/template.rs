@@ -0,0 +1,162 @@
+use anyhow::{bail, Result};
+
+use crate::db::Database;
+use crate::output::Reporter;
+
+pub fn save(
+    db: &Database,
+    out: &Reporter,
+    name: &str,
+    title: &str,
+    description: Option<&str>,
+    priority: &str,
+    subissues: &[String],
+) -> Result<()> {
+    if name.trim().is_empty() {
+        bail!("Template name cannot be empty");
+    }
+    db.save_template(name, title, description, priority, subissues)?;
+    out.info(&format!(
+        "Saved template '{}' with {} subissue(s)",
+        name,
+        subissues.len()
+    ));
+    Ok(())
+}
+
+/// Save an existing issue and its immediate subissues as a template.
+pub fn save_from_issue(db: &Database, out: &Reporter, id: i64, name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        bail!("Template name cannot be empty");
+    }
+    db.template_from_issue(id, name)?;
+    out.info(&format!(
+        "Saved issue #{} as template '{}' with {} subissue(s)",
+        id,
+        name,
+        db.get_subissues(id)?.len()
+    ));
+    Ok(())
+}
+
+pub fn list(db: &Database) -> Result<()> {
+    let templates = db.list_templates()?;
+
+    if templates.is_empty() {
+        println!("No templates saved.");
+        return Ok(());
+    }
+
+    for t in templates {
+        println!(
+            "{:<16} {} [{}] ({} subissue(s))",
+            t.name,
+            t.title,
+            t.priority,
+            t.subissues.len()
+        );
+        for sub in &t.subissues {
+            println!("  - {}", sub);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn delete(db: &Database, out: &Reporter, name: &str) -> Result<()> {
+    if db.delete_template(name)? {
+        out.info(&format!("Deleted template '{}'", name));
+    } else {
+        bail!("Template '{}' not found", name);
+    }
+    Ok(())
+}
+
+pub fn instantiate(db: &Database, out: &Reporter, name: &str) -> Result<()> {
+    let parent = db.instantiate_template(name)?;
+    let subissues = db.get_subissues(parent)?;
+    out.info(&format!(
+        "Created issue #{} from template '{}' with {} subissue(s)",
+        parent,
+        name,
+        subissues.len()
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_save_and_instantiate() {
+        let (db, _dir) = setup_test_db();
+        let subs = vec!["Plan".to_string(), "Retro".to_string()];
+        save(
+            &db,
+            &Reporter::default(),
+            "sprint",
+            "Sprint",
+            None,
+            "medium",
+            &subs,
+        )
+        .unwrap();
+        list(&db).unwrap();
+
+        instantiate(&db, &Reporter::default(), "sprint").unwrap();
+        let issues = db.list_issues(None, None, None).unwrap();
+        assert_eq!(issues.len(), 3);
+        let parent = issues.iter().find(|i| i.title == "Sprint").unwrap();
+        assert_eq!(db.get_subissues(parent.id).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_save_rejects_empty_name_and_bad_priority() {
+        let (db, _dir) = setup_test_db();
+        assert!(save(
+            &db,
+            &Reporter::default(),
+            "  ",
+            "Title",
+            None,
+            "medium",
+            &[]
+        )
+        .is_err());
+        assert!(save(&db, &Reporter::default(), "t", "Title", None, "urgent", &[]).is_err());
+        assert!(db.list_templates().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_save_from_issue() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Onboarding", None, "medium").unwrap();
+        db.create_subissue(parent, "Laptop", None, "medium")
+            .unwrap();
+
+        assert!(save_from_issue(&db, &Reporter::default(), parent, " ").is_err());
+        save_from_issue(&db, &Reporter::default(), parent, "onboard").unwrap();
+        let template = db.get_template("onboard").unwrap().unwrap();
+        assert_eq!(template.title, "Onboarding");
+        assert_eq!(template.subissues, vec!["Laptop"]);
+        assert!(save_from_issue(&db, &Reporter::default(), 999, "other").is_err());
+    }
+
+    #[test]
+    fn test_delete_and_instantiate_missing() {
+        let (db, _dir) = setup_test_db();
+        save(&db, &Reporter::default(), "t", "Title", None, "low", &[]).unwrap();
+        delete(&db, &Reporter::default(), "t").unwrap();
+        assert!(delete(&db, &Reporter::default(), "t").is_err());
+        assert!(instantiate(&db, &Reporter::default(), "t").is_err());
+    }
+}
@@ -5,10 +5,7 @@ use crate::db::Database;
 
 pub fn start(db: &Database, issue_id: i64) -> Result<()> {
     // Verify issue exists
-    let issue = match db.get_issue(issue_id)? {
-        Some(i) => i,
-        None => bail!("Issue #{} not found", issue_id),
-    };
+    let issue = db.require_issue(issue_id)?;
 
     // Check if there's already an active timer
     if let Some((active_id, _)) = db.get_active_timer()? {
@@ -1,67 +1,527 @@
 use anyhow::Result;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
 
-use crate::db::Database;
-use crate::models::Issue;
+use crate::db::{check_status_filter, Database};
+use crate::models::{Issue, PriorityScale, STATUSES};
+use crate::utils::{sanitize_display, truncate, MIN_TITLE_WIDTH};
 
 fn status_icon(status: &str) -> &'static str {
     match status {
         "open" => " ",
         "closed" => "x",
+        "archived" => "-",
         _ => "?",
     }
 }
 
-fn print_issue(issue: &Issue, indent: usize) {
-    let prefix = "  ".repeat(indent);
-    let icon = status_icon(&issue.status);
-    println!(
-        "{}[{}] #{} {} - {}",
-        prefix, icon, issue.id, issue.priority, issue.title
-    );
+/// How tree rows are drawn. Implement it to theme the tree; the defaults give
+/// the plain output.
+pub trait Renderer {
+    /// Status marker at the start of a row, e.g. "[x]"
+    fn status_icon(&self, status: &str) -> String {
+        format!("[{}]", status_icon(status))
+    }
+
+    /// Priority column, padded so the titles line up
+    fn priority_label(&self, scale: &PriorityScale, priority: &str, color: bool) -> String {
+        scale.display_padded(priority, scale.column_width(), color)
+    }
+
+    /// Leading whitespace for a row `depth` levels below the top
+    fn indent(&self, depth: usize) -> String {
+        "  ".repeat(depth)
+    }
+
+    /// The line explaining the status markers
+    fn legend(&self) -> String {
+        let entries: Vec<String> = STATUSES
+            .iter()
+            .map(|status| format!("{} {}", self.status_icon(status), status))
+            .collect();
+        format!("Legend: {}", entries.join(", "))
+    }
+}
+
+/// Today's plain text tree: `[ ]`/`[x]`/`[-]` markers and two-space indents.
+pub struct PlainRenderer;
+
+impl Renderer for PlainRenderer {}
+
+/// Emoji status markers and a colored dot per priority, most urgent first.
+pub struct EmojiRenderer;
+
+const PRIORITY_DOTS: [&str; 4] = ["🔴", "🟠", "🔵", "⚪"];
+
+impl Renderer for EmojiRenderer {
+    fn status_icon(&self, status: &str) -> String {
+        match status {
+            "open" => "⬜",
+            "closed" => "✅",
+            "archived" => "📦",
+            _ => "❓",
+        }
+        .to_string()
+    }
+
+    fn priority_label(&self, scale: &PriorityScale, priority: &str, color: bool) -> String {
+        let rank = scale.rank(priority) as usize;
+        let dot = if rank == 0 {
+            "⚫"
+        } else {
+            let from_top = scale.levels().len() - rank;
+            PRIORITY_DOTS[from_top.min(PRIORITY_DOTS.len() - 1)]
+        };
+        format!(
+            "{} {}",
+            dot,
+            scale.display_padded(priority, scale.column_width(), color)
+        )
+    }
+}
+
+/// Built-in renderers selectable from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum TreeStyle {
+    #[default]
+    Plain,
+    Emoji,
+}
+
+impl TreeStyle {
+    pub fn renderer(self) -> &'static dyn Renderer {
+        match self {
+            TreeStyle::Plain => &PlainRenderer,
+            TreeStyle::Emoji => &EmojiRenderer,
+        }
+    }
+}
+
+/// Which issues the tree is narrowed to. Ancestors of a match stay in the
+/// tree for context; branches with no match anywhere beneath are dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum TreeFilter {
+    #[default]
+    All,
+    /// Open issues with at least one open blocker
+    Blocked,
+    /// Open issues with no open blockers
+    Ready,
+}
+
+impl TreeFilter {
+    /// Ids of the issues that match, or None when nothing is filtered out.
+    fn matching_ids(self, db: &Database) -> Result<Option<HashSet<i64>>> {
+        Ok(match self {
+            TreeFilter::All => None,
+            TreeFilter::Blocked => Some(
+                db.list_blocked_with_meta()?
+                    .into_iter()
+                    .map(|m| m.issue.id)
+                    .collect(),
+            ),
+            TreeFilter::Ready => Some(
+                db.list_ready_issues(false)?
+                    .into_iter()
+                    .map(|i| i.id)
+                    .collect(),
+            ),
+        })
+    }
+}
+
+/// Display options for the tree command.
+#[derive(Default)]
+pub struct TreeOpts {
+    /// Append each issue's rolled-up estimate (itself plus all descendants)
+    pub show_estimates: bool,
+    /// Print only the subtree under this issue (including the issue itself)
+    pub root: Option<i64>,
+    /// Color priorities and add a priority legend
+    pub color: bool,
+    /// List blocking edges between issues under different top-level ancestors
+    pub with_deps: bool,
+    /// Keep only blocked or ready issues and their ancestors
+    pub filter: TreeFilter,
+    /// Clip titles to this width, less each row's indentation (unclipped if None)
+    pub title_width: Option<usize>,
+    /// Renderer used by `run`, `write_to` and `watch`
+    pub style: TreeStyle,
+    /// Leave off the legend footer
+    pub no_legend: bool,
+}
+
+/// What a row needs to draw itself: the renderer and the priority scale.
+struct RowStyle<'a> {
+    renderer: &'a dyn Renderer,
+    scale: &'a PriorityScale,
 }
 
-fn print_tree_recursive(
+/// Whether an issue with `status` passes the tree's status filter.
+/// Archived issues only show up when asked for explicitly or via "all".
+fn matches_status(status: &str, status_filter: Option<&str>) -> bool {
+    match status_filter {
+        Some("all") => true,
+        None => status != "archived",
+        Some(filter) => status == filter,
+    }
+}
+
+/// One tree row for an issue, on a single line whatever the title contains.
+fn format_issue(
     db: &Database,
+    style: &RowStyle<'_>,
+    issue: &Issue,
+    indent: usize,
+    opts: &TreeOpts,
+) -> Result<String> {
+    let prefix = style.renderer.indent(indent);
+    let icon = style.renderer.status_icon(&issue.status);
+    let title = sanitize_display(&issue.title);
+    let title = match opts.title_width {
+        Some(width) => truncate(
+            &title,
+            width.saturating_sub(prefix.len()).max(MIN_TITLE_WIDTH),
+        ),
+        None => title,
+    };
+    let estimate = if opts.show_estimates {
+        format!(" (est. {})", db.subtree_estimate(issue.id)?)
+    } else {
+        String::new()
+    };
+    let duplicate_of = db.get_duplicate_of(issue.id)?;
+    let duplicate = if duplicate_of.is_empty() {
+        String::new()
+    } else {
+        let strs: Vec<String> = duplicate_of.iter().map(|d| format!("#{}", d)).collect();
+        format!(" (duplicate of {})", strs.join(", "))
+    };
+    Ok(format!(
+        "{}{} #{} {} - {}{}{}",
+        prefix,
+        icon,
+        issue.id,
+        style
+            .renderer
+            .priority_label(style.scale, &issue.priority, opts.color),
+        title,
+        estimate,
+        duplicate
+    ))
+}
+
+/// Rows for everything under `parent_id`. Children are rendered first so a
+/// subissue is only kept when it matches or something beneath it does.
+fn render_recursive(
+    db: &Database,
+    style: &RowStyle<'_>,
     parent_id: i64,
     indent: usize,
     status_filter: Option<&str>,
-) -> Result<()> {
-    let subissues = db.get_subissues(parent_id)?;
-    for sub in subissues {
-        let dominated_by_filter = match status_filter {
-            Some("all") | None => false,
-            Some(filter) => sub.status != filter,
-        };
-        if dominated_by_filter {
+    opts: &TreeOpts,
+    matches: Option<&HashSet<i64>>,
+) -> Result<String> {
+    let mut out = String::new();
+    for sub in db.get_subissues(parent_id)? {
+        if !matches_status(&sub.status, status_filter) {
             continue;
         }
-        print_issue(&sub, indent);
-        print_tree_recursive(db, sub.id, indent + 1, status_filter)?;
+        let below = render_recursive(db, style, sub.id, indent + 1, status_filter, opts, matches)?;
+        if below.is_empty() && matches.is_some_and(|m| !m.contains(&sub.id)) {
+            continue;
+        }
+        out.push_str(&format_issue(db, style, &sub, indent, opts)?);
+        out.push('\n');
+        out.push_str(&below);
     }
-    Ok(())
+    Ok(out)
 }
 
-pub fn run(db: &Database, status_filter: Option<&str>) -> Result<()> {
-    // Get all top-level issues (no parent)
-    let all_issues = db.list_issues(status_filter, None, None)?;
-    let top_level: Vec<_> = all_issues
+/// Blocking edges whose ends sit under different top-level issues, as
+/// (blocker, blocker's root, blocked, blocked's root). Both ends must pass the
+/// status filter. With a `root`, the edges that leave the subtree under it
+/// are listed instead, whatever level `root` sits at.
+fn cross_dependencies(
+    db: &Database,
+    status_filter: Option<&str>,
+    root: Option<i64>,
+) -> Result<Vec<(i64, i64, i64, i64)>> {
+    let issues: HashMap<i64, Issue> = db
+        .list_issues(Some("all"), None, None)?
         .into_iter()
-        .filter(|i| i.parent_id.is_none())
+        .map(|i| (i.id, i))
         .collect();
+    // Ancestors of `id`, itself first. Bounded so a corrupt parent loop
+    // cannot hang the render
+    let ancestors = |mut id: i64| {
+        let mut chain = vec![id];
+        for _ in 0..issues.len() {
+            match issues.get(&id).and_then(|i| i.parent_id) {
+                Some(parent) if issues.contains_key(&parent) => {
+                    id = parent;
+                    chain.push(id);
+                }
+                _ => break,
+            }
+        }
+        chain
+    };
+    let root_of = |id: i64| *ancestors(id).last().unwrap_or(&id);
+    let under_root = |id: i64| root.is_none_or(|r| ancestors(id).contains(&r));
+    let shown = |id: i64| {
+        issues
+            .get(&id)
+            .is_some_and(|i| matches_status(&i.status, status_filter))
+    };
+
+    let mut edges = Vec::new();
+    for (blocked, blocker) in db.list_dependencies()? {
+        if !shown(blocked) || !shown(blocker) {
+            continue;
+        }
+        let (blocked_root, blocker_root) = (root_of(blocked), root_of(blocker));
+        let crosses = if root.is_some() {
+            // Under --root, an edge crosses when it leaves the shown subtree
+            under_root(blocked) != under_root(blocker)
+        } else {
+            blocked_root != blocker_root
+        };
+        if !crosses {
+            continue;
+        }
+        edges.push((blocker, blocker_root, blocked, blocked_root));
+    }
+    edges.sort_unstable();
+    Ok(edges)
+}
+
+fn format_endpoint(id: i64, root: i64) -> String {
+    if id == root {
+        format!("#{}", id)
+    } else {
+        format!("#{} (under #{})", id, root)
+    }
+}
+
+/// The issues at the top of the tree: `root` alone, or every top-level issue.
+fn top_level(db: &Database, status_filter: Option<&str>, root: Option<i64>) -> Result<Vec<Issue>> {
+    if let Some(filter) = status_filter {
+        check_status_filter(filter)?;
+    }
+    Ok(match root {
+        Some(root) => {
+            let issue = db.require_issue(root)?;
+            if matches_status(&issue.status, status_filter) {
+                vec![issue]
+            } else {
+                Vec::new()
+            }
+        }
+        // Get all top-level issues (no parent)
+        None => db
+            .list_issues(status_filter, None, None)?
+            .into_iter()
+            .filter(|i| i.parent_id.is_none())
+            .collect(),
+    })
+}
+
+/// An issue and its subissues, as serialized by `render_json`.
+#[derive(Serialize)]
+struct TreeNode {
+    #[serde(flatten)]
+    issue: Issue,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimate: Option<i64>,
+    children: Vec<TreeNode>,
+}
+
+/// `issue` and its subissues, or None if the tree filter prunes the whole branch.
+fn tree_node(
+    db: &Database,
+    issue: Issue,
+    status_filter: Option<&str>,
+    opts: &TreeOpts,
+    matches: Option<&HashSet<i64>>,
+) -> Result<Option<TreeNode>> {
+    let mut children = Vec::new();
+    for sub in db.get_subissues(issue.id)? {
+        if matches_status(&sub.status, status_filter) {
+            children.extend(tree_node(db, sub, status_filter, opts, matches)?);
+        }
+    }
+    if children.is_empty() && matches.is_some_and(|m| !m.contains(&issue.id)) {
+        return Ok(None);
+    }
+    let estimate = if opts.show_estimates {
+        Some(db.subtree_estimate(issue.id)?)
+    } else {
+        None
+    };
+    Ok(Some(TreeNode {
+        issue,
+        estimate,
+        children,
+    }))
+}
+
+/// The same tree as `render`, as a JSON array of nested issues.
+pub fn render_json(db: &Database, status_filter: Option<&str>, opts: &TreeOpts) -> Result<String> {
+    let matches = opts.filter.matching_ids(db)?;
+    let mut nodes = Vec::new();
+    for issue in top_level(db, status_filter, opts.root)? {
+        nodes.extend(tree_node(db, issue, status_filter, opts, matches.as_ref())?);
+    }
+    Ok(serde_json::to_string_pretty(&nodes)?)
+}
+
+/// Build the tree output, legend included, without printing it. Rows are drawn
+/// by `renderer` rather than `opts.style`, so callers can supply their own.
+pub fn render(
+    db: &Database,
+    status_filter: Option<&str>,
+    opts: &TreeOpts,
+    renderer: &dyn Renderer,
+) -> Result<String> {
+    let top_level = top_level(db, status_filter, opts.root)?;
 
     if top_level.is_empty() {
-        println!("No issues found.");
-        return Ok(());
+        return Ok("No issues found.\n".to_string());
     }
 
+    let scale = db.priority_scale()?;
+    let style = RowStyle {
+        renderer,
+        scale: &scale,
+    };
+    let matches = opts.filter.matching_ids(db)?;
+    let mut out = String::new();
     for issue in top_level {
-        print_issue(&issue, 0);
-        print_tree_recursive(db, issue.id, 1, status_filter)?;
+        let below = render_recursive(
+            db,
+            &style,
+            issue.id,
+            1,
+            status_filter,
+            opts,
+            matches.as_ref(),
+        )?;
+        if below.is_empty() && matches.as_ref().is_some_and(|m| !m.contains(&issue.id)) {
+            continue;
+        }
+        out.push_str(&format_issue(db, &style, &issue, 0, opts)?);
+        out.push('\n');
+        out.push_str(&below);
+    }
+    if out.is_empty() {
+        return Ok("No matching issues found.\n".to_string());
     }
 
-    // Legend
-    println!();
-    println!("Legend: [ ] open, [x] closed");
+    if opts.with_deps {
+        out.push_str("\nCross-dependencies:\n");
+        let edges = cross_dependencies(db, status_filter, opts.root)?;
+        if edges.is_empty() {
+            out.push_str("  (none)\n");
+        }
+        for (blocker, blocker_root, blocked, blocked_root) in edges {
+            out.push_str(&format!(
+                "  {} blocks {}\n",
+                format_endpoint(blocker, blocker_root),
+                format_endpoint(blocked, blocked_root)
+            ));
+        }
+    }
+
+    if !opts.no_legend {
+        out.push_str(&format!("\n{}\n", renderer.legend()));
+        if opts.color {
+            out.push_str(&scale.legend());
+            out.push('\n');
+        }
+    }
+
+    Ok(out)
+}
+
+/// Write the tree into any sink: stdout, a file, or a buffer for capturing.
+pub fn write_to(
+    w: &mut dyn Write,
+    db: &Database,
+    status_filter: Option<&str>,
+    opts: &TreeOpts,
+) -> Result<()> {
+    w.write_all(render(db, status_filter, opts, opts.style.renderer())?.as_bytes())?;
+    Ok(())
+}
+
+/// Write the JSON tree, newline-terminated, into any sink.
+pub fn write_json_to(
+    w: &mut dyn Write,
+    db: &Database,
+    status_filter: Option<&str>,
+    opts: &TreeOpts,
+) -> Result<()> {
+    writeln!(w, "{}", render_json(db, status_filter, opts)?)?;
+    Ok(())
+}
+
+pub fn run(db: &Database, status_filter: Option<&str>, opts: &TreeOpts) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    write_to(&mut stdout, db, status_filter, opts)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+pub fn run_json(db: &Database, status_filter: Option<&str>, opts: &TreeOpts) -> Result<()> {
+    write_json_to(&mut io::stdout().lock(), db, status_filter, opts)
+}
+
+/// Redraw the tree whenever the database file changes. Runs until interrupted.
+pub fn watch(
+    db: &Database,
+    db_path: &Path,
+    status_filter: Option<&str>,
+    opts: &TreeOpts,
+) -> Result<()> {
+    watch_loop(
+        db_path,
+        Duration::from_millis(500),
+        || {
+            // Clear the screen and move the cursor home before redrawing
+            print!("\x1b[2J\x1b[H");
+            run(db, status_filter, opts)
+        },
+        || true,
+    )
+}
+
+fn modified_time(path: &Path) -> Result<SystemTime> {
+    Ok(std::fs::metadata(path)?.modified()?)
+}
+
+/// Render once, then poll `path`'s mtime every `interval` and re-render when it changes.
+/// `keep_going` is checked after each poll so tests can stop the loop.
+fn watch_loop(
+    path: &Path,
+    interval: Duration,
+    mut render: impl FnMut() -> Result<()>,
+    mut keep_going: impl FnMut() -> bool,
+) -> Result<()> {
+    let mut last = modified_time(path)?;
+    render()?;
+
+    while keep_going() {
+        std::thread::sleep(interval);
+        let current = modified_time(path)?;
+        if current != last {
+            last = current;
+            render()?;
+        }
+    }
 
     Ok(())
 }
@@ -89,15 +549,388 @@ mod tests {
         assert_eq!(status_icon("closed"), "x");
     }
 
+    #[test]
+    fn test_status_icon_archived() {
+        assert_eq!(status_icon("archived"), "-");
+    }
+
     #[test]
     fn test_status_icon_unknown() {
-        assert_eq!(status_icon("archived"), "?");
+        assert_eq!(status_icon("bogus"), "?");
+    }
+
+    #[test]
+    fn test_format_issue_escapes_control_chars() {
+        let (db, _dir) = setup_test_db();
+        let id = db
+            .create_issue("First line\nsecond\0line", None, "medium")
+            .unwrap();
+        let issue = db.get_issue(id).unwrap().unwrap();
+
+        let line = format_issue(
+            &db,
+            &RowStyle {
+                renderer: &PlainRenderer,
+                scale: &PriorityScale::default(),
+            },
+            &issue,
+            1,
+            &TreeOpts::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            line,
+            format!("  [ ] #{} medium   - First line\\nsecond\\u{{0}}line", id)
+        );
+        assert!(!line.contains('\n') && !line.contains('\0'));
+        // Storage keeps the raw title
+        assert_eq!(issue.title, "First line\nsecond\0line");
+    }
+
+    #[test]
+    fn test_render_hierarchy() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "high").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        let grandchild = db
+            .create_subissue(child, "Grandchild", None, "low")
+            .unwrap();
+        let done = db.create_subissue(parent, "Done", None, "low").unwrap();
+        db.close_issue(done).unwrap();
+        let other = db.create_issue("Other", None, "low").unwrap();
+
+        let out = render(&db, Some("all"), &TreeOpts::default(), &PlainRenderer).unwrap();
+        // Top-level issues come newest first, children in creation order
+        assert_eq!(
+            out,
+            format!(
+                "[ ] #{other} low      - Other\n\
+                 [ ] #{parent} high     - Parent\n\
+                 \x20 [ ] #{child} medium   - Child\n\
+                 \x20   [ ] #{grandchild} low      - Grandchild\n\
+                 \x20 [x] #{done} low      - Done\n\
+                 \n\
+                 Legend: [ ] open, [x] closed, [-] archived\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_json_nests_children() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let child = db.create_subissue(epic, "Child", None, "low").unwrap();
+        let done = db.create_subissue(epic, "Done", None, "low").unwrap();
+        db.close_issue(done).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_json(&db, Some("open"), &TreeOpts::default()).unwrap())
+                .unwrap();
+        let roots = json.as_array().unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0]["id"], epic);
+        assert_eq!(roots[0]["title"], "Epic");
+        assert!(roots[0].get("estimate").is_none());
+        let children = roots[0]["children"].as_array().unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0]["id"], child);
+        assert_eq!(children[0]["children"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_render_with_deps_lists_cross_dependencies() {
+        let (db, _dir) = setup_test_db();
+        let epic_a = db.create_issue("Epic A", None, "high").unwrap();
+        let leaf_a = db
+            .create_subissue(epic_a, "Leaf A", None, "medium")
+            .unwrap();
+        let other_a = db.create_subissue(epic_a, "Other A", None, "low").unwrap();
+        let epic_b = db.create_issue("Epic B", None, "high").unwrap();
+        let task_b = db
+            .create_subissue(epic_b, "Task B", None, "medium")
+            .unwrap();
+        let leaf_b = db
+            .create_subissue(task_b, "Leaf B", None, "medium")
+            .unwrap();
+        // Cross-tree edge: leaf_a blocks leaf_b
+        db.add_dependency(leaf_b, leaf_a).unwrap();
+        // Same-tree edge is not listed
+        db.add_dependency(other_a, leaf_a).unwrap();
+
+        let opts = TreeOpts {
+            with_deps: true,
+            ..Default::default()
+        };
+        let out = render(&db, None, &opts, &PlainRenderer).unwrap();
+        let section = out.split("Cross-dependencies:\n").nth(1).unwrap();
+        let lines: Vec<&str> = section.lines().take_while(|l| !l.is_empty()).collect();
+        assert_eq!(
+            lines,
+            vec![format!(
+                "  #{} (under #{}) blocks #{} (under #{})",
+                leaf_a, epic_a, leaf_b, epic_b
+            )]
+        );
+
+        assert!(!render(&db, None, &TreeOpts::default(), &PlainRenderer)
+            .unwrap()
+            .contains("Cross-dependencies"));
+        db.remove_dependency(leaf_b, leaf_a).unwrap();
+        assert!(render(&db, None, &opts, &PlainRenderer)
+            .unwrap()
+            .contains("Cross-dependencies:\n  (none)\n"));
+    }
+
+    #[test]
+    fn test_render_with_deps_under_mid_level_root() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let task = db.create_subissue(epic, "Task", None, "medium").unwrap();
+        let leaf = db.create_subissue(task, "Leaf", None, "low").unwrap();
+        let sibling = db.create_subissue(epic, "Sibling", None, "low").unwrap();
+        let other = db.create_issue("Other", None, "low").unwrap();
+        // Leaves the subtree under #task, though it stays inside the epic
+        db.add_dependency(leaf, sibling).unwrap();
+        db.add_dependency(other, leaf).unwrap();
+        // Entirely inside the subtree
+        db.add_dependency(leaf, task).unwrap();
+
+        let opts = TreeOpts {
+            with_deps: true,
+            root: Some(task),
+            ..Default::default()
+        };
+        let out = render(&db, None, &opts, &PlainRenderer).unwrap();
+        let section = out.split("Cross-dependencies:\n").nth(1).unwrap();
+        let lines: Vec<&str> = section.lines().take_while(|l| !l.is_empty()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                format!("  #{} (under #{}) blocks #{}", leaf, epic, other),
+                format!(
+                    "  #{} (under #{}) blocks #{} (under #{})",
+                    sibling, epic, leaf, epic
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_blocked_filter_prunes_branches() {
+        let (db, _dir) = setup_test_db();
+        let epic_a = db.create_issue("Epic A", None, "high").unwrap();
+        let task_a = db
+            .create_subissue(epic_a, "Task A", None, "medium")
+            .unwrap();
+        let leaf_a = db.create_subissue(task_a, "Leaf A", None, "low").unwrap();
+        let sibling_a = db
+            .create_subissue(epic_a, "Sibling A", None, "low")
+            .unwrap();
+        let epic_b = db.create_issue("Epic B", None, "high").unwrap();
+        let leaf_b = db.create_subissue(epic_b, "Leaf B", None, "low").unwrap();
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        db.add_dependency(leaf_a, blocker).unwrap();
+
+        let opts = TreeOpts {
+            filter: TreeFilter::Blocked,
+            ..Default::default()
+        };
+        let out = render(&db, None, &opts, &PlainRenderer).unwrap();
+        // The blocked leaf keeps its ancestors; nothing else survives
+        assert_eq!(
+            out,
+            format!(
+                "[ ] #{epic_a} high     - Epic A\n\
+                 \x20 [ ] #{task_a} medium   - Task A\n\
+                 \x20   [ ] #{leaf_a} low      - Leaf A\n\
+                 \n\
+                 Legend: [ ] open, [x] closed, [-] archived\n"
+            )
+        );
+        for dropped in [sibling_a, epic_b, leaf_b, blocker] {
+            assert!(!out.contains(&format!("#{} ", dropped)));
+        }
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_json(&db, None, &opts).unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+        assert_eq!(json[0]["children"][0]["children"][0]["id"], leaf_a);
+
+        let ready = TreeOpts {
+            filter: TreeFilter::Ready,
+            ..Default::default()
+        };
+        let out = render(&db, None, &ready, &PlainRenderer).unwrap();
+        assert!(out.contains(&format!("#{} ", leaf_b)));
+        assert!(!out.contains(&format!("#{} ", leaf_a)));
+
+        db.remove_dependency(leaf_a, blocker).unwrap();
+        assert_eq!(
+            render(&db, None, &opts, &PlainRenderer).unwrap(),
+            "No matching issues found.\n"
+        );
+    }
+
+    /// Overrides only the markers and indentation, keeping the default priority column
+    struct Arrows;
+
+    impl Renderer for Arrows {
+        fn status_icon(&self, status: &str) -> String {
+            if status == "closed" {
+                "(done)"
+            } else {
+                "(todo)"
+            }
+            .to_string()
+        }
+
+        fn indent(&self, depth: usize) -> String {
+            "-> ".repeat(depth)
+        }
+    }
+
+    #[test]
+    fn test_render_with_custom_renderer() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "high").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "low").unwrap();
+        db.close_issue(child).unwrap();
+
+        let out = render(&db, None, &TreeOpts::default(), &Arrows).unwrap();
+        assert_eq!(
+            out,
+            format!(
+                "(todo) #{parent} high     - Parent\n\
+                 -> (done) #{child} low      - Child\n\
+                 \n\
+                 Legend: (todo) open, (done) closed, (todo) archived\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_emoji_style() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Ship it", None, "critical").unwrap();
+        let opts = TreeOpts {
+            style: TreeStyle::Emoji,
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        write_to(&mut buf, &db, None, &opts).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.starts_with(&format!("⬜ #{} 🔴 critical - Ship it\n", id)));
+        assert!(out.contains("Legend: ⬜ open, ✅ closed, 📦 archived\n"));
+    }
+
+    #[test]
+    fn test_render_title_width_clips_by_depth() {
+        let (db, _dir) = setup_test_db();
+        let parent = db
+            .create_issue("Parent with a long title", None, "high")
+            .unwrap();
+        let child = db
+            .create_subissue(parent, "Child with a long title", None, "low")
+            .unwrap();
+        let opts = TreeOpts {
+            title_width: Some(16),
+            ..Default::default()
+        };
+
+        let out = render(&db, None, &opts, &PlainRenderer).unwrap();
+        assert!(out.contains(&format!("#{} high     - Parent with a...\n", parent)));
+        assert!(out.contains(&format!("#{} low      - Child with ...\n", child)));
+        assert!(render(&db, None, &TreeOpts::default(), &PlainRenderer)
+            .unwrap()
+            .contains("Child with a long title\n"));
+    }
+
+    #[test]
+    fn test_render_color_adds_priority_legend() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Hot", None, "critical").unwrap();
+        let opts = TreeOpts {
+            color: true,
+            ..Default::default()
+        };
+
+        let out = render(&db, None, &opts, &PlainRenderer).unwrap();
+        assert!(out.starts_with(&format!("[ ] #{} \x1b[31mcritical\x1b[0m - Hot\n", id)));
+        assert!(out.ends_with(&format!("{}\n", PriorityScale::default().legend())));
+        assert!(!render(&db, None, &TreeOpts::default(), &PlainRenderer)
+            .unwrap()
+            .contains("Priorities:"));
+    }
+
+    #[test]
+    fn test_rejects_unknown_status() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Parent", None, "medium").unwrap();
+        let opts = TreeOpts::default();
+
+        assert!(render(&db, Some("all"), &opts, &PlainRenderer).is_ok());
+        let err = render(&db, Some("opne"), &opts, &PlainRenderer).unwrap_err();
+        assert!(err.to_string().contains("Invalid status 'opne'"));
+
+        // Checked even when the tree starts from a single root
+        let rooted = TreeOpts {
+            root: Some(id),
+            ..Default::default()
+        };
+        assert!(render_json(&db, Some("opne"), &rooted).is_err());
+    }
+
+    #[test]
+    fn test_no_legend() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Hot", None, "critical").unwrap();
+        let opts = TreeOpts {
+            color: true,
+            no_legend: true,
+            ..Default::default()
+        };
+
+        let out = render(&db, None, &opts, &PlainRenderer).unwrap();
+        assert_eq!(out, format!("[ ] #{} \x1b[31mcritical\x1b[0m - Hot\n", id));
+    }
+
+    #[test]
+    fn test_write_to_buffer() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "high").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "low").unwrap();
+
+        let mut buf: Vec<u8> = Vec::new();
+        write_to(&mut buf, &db, None, &TreeOpts::default()).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            format!(
+                "[ ] #{parent} high     - Parent\n\
+                 \x20 [ ] #{child} low      - Child\n\
+                 \n\
+                 Legend: [ ] open, [x] closed, [-] archived\n"
+            )
+        );
+
+        let mut buf: Vec<u8> = Vec::new();
+        write_json_to(&mut buf, &db, None, &TreeOpts::default()).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(json[0]["children"][0]["id"], child);
+        assert!(buf.ends_with(b"\n"));
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let (db, _dir) = setup_test_db();
+        let out = render(&db, None, &TreeOpts::default(), &PlainRenderer).unwrap();
+        assert_eq!(out, "No issues found.\n");
     }
 
     #[test]
     fn test_run_empty() {
         let (db, _dir) = setup_test_db();
-        run(&db, None).unwrap();
+        run(&db, None, &TreeOpts::default()).unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert!(issues.is_empty());
     }
@@ -106,7 +939,7 @@ mod tests {
     fn test_run_single_issue() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
-        run(&db, None).unwrap();
+        run(&db, None, &TreeOpts::default()).unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert_eq!(issues.len(), 1);
         assert_eq!(issues[0].id, id);
@@ -120,7 +953,7 @@ mod tests {
             .create_subissue(parent, "Child 1", None, "medium")
             .unwrap();
         let c2 = db.create_subissue(parent, "Child 2", None, "low").unwrap();
-        run(&db, None).unwrap();
+        run(&db, None, &TreeOpts::default()).unwrap();
         let subs = db.get_subissues(parent).unwrap();
         assert_eq!(subs.len(), 2);
         assert!(subs.iter().any(|s| s.id == c1));
@@ -135,7 +968,7 @@ mod tests {
             .create_subissue(grandparent, "Parent", None, "medium")
             .unwrap();
         let child = db.create_subissue(parent, "Child", None, "low").unwrap();
-        run(&db, None).unwrap();
+        run(&db, None, &TreeOpts::default()).unwrap();
         let child_issue = db.get_issue(child).unwrap().unwrap();
         assert_eq!(child_issue.parent_id, Some(parent));
         let parent_issue = db.get_issue(parent).unwrap().unwrap();
@@ -148,7 +981,7 @@ mod tests {
         let closed_id = db.create_issue("Closed issue", None, "medium").unwrap();
         let open_id = db.create_issue("Open issue", None, "medium").unwrap();
         db.close_issue(closed_id).unwrap();
-        run(&db, Some("open")).unwrap();
+        run(&db, Some("open"), &TreeOpts::default()).unwrap();
         let open_issues = db.list_issues(Some("open"), None, None).unwrap();
         assert_eq!(open_issues.len(), 1);
         assert_eq!(open_issues[0].id, open_id);
@@ -159,7 +992,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Issue", None, "medium").unwrap();
         db.close_issue(id).unwrap();
-        run(&db, Some("closed")).unwrap();
+        run(&db, Some("closed"), &TreeOpts::default()).unwrap();
         let closed = db.list_issues(Some("closed"), None, None).unwrap();
         assert_eq!(closed.len(), 1);
         assert_eq!(closed[0].id, id);
@@ -171,11 +1004,118 @@ mod tests {
         db.create_issue("Open issue", None, "medium").unwrap();
         let id = db.create_issue("Closed issue", None, "medium").unwrap();
         db.close_issue(id).unwrap();
-        run(&db, Some("all")).unwrap();
+        run(&db, Some("all"), &TreeOpts::default()).unwrap();
         let all = db.list_issues(Some("all"), None, None).unwrap();
         assert_eq!(all.len(), 2);
     }
 
+    #[test]
+    fn test_run_show_estimates() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "high").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "low").unwrap();
+        db.create_subissue(parent, "Unestimated", None, "low")
+            .unwrap();
+        db.set_estimate(parent, Some(2)).unwrap();
+        db.set_estimate(child, Some(3)).unwrap();
+
+        let opts = TreeOpts {
+            show_estimates: true,
+            ..Default::default()
+        };
+        run(&db, None, &opts).unwrap();
+        assert_eq!(db.subtree_estimate(parent).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_run_with_root() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let child = db.create_subissue(epic, "Child", None, "medium").unwrap();
+        let leaf = db.create_subissue(child, "Leaf", None, "low").unwrap();
+        db.create_issue("Other", None, "low").unwrap();
+
+        let opts = TreeOpts {
+            root: Some(child),
+            no_legend: true,
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        write_to(&mut buf, &db, Some("all"), &opts).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2, "{}", out);
+        assert!(lines[0].contains(&format!("#{}", child)) && lines[0].contains("Child"));
+        assert!(lines[1].contains(&format!("#{}", leaf)) && lines[1].contains("Leaf"));
+        assert!(!out.contains("Epic"));
+        assert!(!out.contains("Other"));
+    }
+
+    #[test]
+    fn test_run_with_missing_root() {
+        let (db, _dir) = setup_test_db();
+        let opts = TreeOpts {
+            root: Some(99999),
+            ..Default::default()
+        };
+        let result = run(&db, Some("all"), &opts);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_matches_status() {
+        assert!(matches_status("archived", Some("all")));
+        assert!(!matches_status("archived", None));
+        assert!(matches_status("open", None));
+        assert!(matches_status("closed", Some("closed")));
+        assert!(!matches_status("open", Some("closed")));
+    }
+
+    #[test]
+    fn test_watch_loop_rerenders_on_mtime_change() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("watched.db");
+        std::fs::write(&path, "x").unwrap();
+
+        let mut renders = 0;
+        let mut polls = 0;
+        watch_loop(
+            &path,
+            Duration::from_millis(1),
+            || {
+                renders += 1;
+                Ok(())
+            },
+            || {
+                polls += 1;
+                if polls == 2 {
+                    let file = std::fs::File::options().write(true).open(&path).unwrap();
+                    file.set_modified(SystemTime::now() + Duration::from_secs(60))
+                        .unwrap();
+                }
+                polls <= 4
+            },
+        )
+        .unwrap();
+
+        // Initial render plus exactly one redraw for the single bump
+        assert_eq!(renders, 2);
+    }
+
+    #[test]
+    fn test_watch_loop_missing_file() {
+        let dir = tempdir().unwrap();
+        let result = watch_loop(
+            &dir.path().join("missing.db"),
+            Duration::from_millis(1),
+            || Ok(()),
+            || false,
+        );
+        assert!(result.is_err());
+    }
+
     proptest! {
         #[test]
         fn prop_run_never_panics(count in 0usize..5) {
@@ -183,7 +1123,7 @@ mod tests {
             for i in 0..count {
                 db.create_issue(&format!("Issue {}", i), None, "medium").unwrap();
             }
-            let result = run(&db, None);
+            let result = run(&db, None, &TreeOpts::default());
             prop_assert!(result.is_ok());
         }
 
@@ -194,7 +1134,7 @@ mod tests {
             for i in 0..depth {
                 parent_id = db.create_subissue(parent_id, &format!("Child {}", i), None, "medium").unwrap();
             }
-            let result = run(&db, None);
+            let result = run(&db, None, &TreeOpts::default());
             prop_assert!(result.is_ok());
         }
     }
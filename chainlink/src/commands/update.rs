@@ -1,10 +1,11 @@
 use anyhow::{bail, Result};
 
-use crate::commands::create::validate_priority;
 use crate::db::Database;
+use crate::output::Reporter;
 
 pub fn run(
     db: &Database,
+    out: &Reporter,
     id: i64,
     title: Option<&str>,
     description: Option<&str>,
@@ -15,16 +16,11 @@ pub fn run(
     }
 
     if let Some(p) = priority {
-        if !validate_priority(p) {
-            bail!(
-                "Invalid priority '{}'. Must be one of: low, medium, high, critical",
-                p
-            );
-        }
+        db.check_priority(p)?;
     }
 
     if db.update_issue(id, title, description, priority)? {
-        println!("Updated issue #{}", id);
+        out.info(&format!("Updated issue #{}", id));
     } else {
         bail!("Issue #{} not found", id);
     }
@@ -32,9 +28,30 @@ pub fn run(
     Ok(())
 }
 
+pub fn reprioritize(
+    db: &Database,
+    out: &Reporter,
+    status: &str,
+    from: &str,
+    to: &str,
+) -> Result<()> {
+    let count = db.set_priority_where(Some(status), from, to)?;
+    if count > 0 {
+        out.info(&format!(
+            "Reprioritized {} issue(s) from {} to {}",
+            count, from, to
+        ));
+    } else {
+        out.info(&format!("No {} issues at priority {}", status, from));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::Verbosity;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -47,12 +64,38 @@ mod tests {
 
     // ==================== Unit Tests ====================
 
+    #[test]
+    fn test_reprioritize_reports_through_reporter() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Slipped", None, "low").unwrap();
+
+        let out = Reporter::capturing(Verbosity::Normal);
+        reprioritize(&db, &out, "open", "low", "medium").unwrap();
+        assert_eq!(
+            out.output(),
+            "Reprioritized 1 issue(s) from low to medium\n"
+        );
+        assert_eq!(db.get_issue(id).unwrap().unwrap().priority, "medium");
+
+        let quiet = Reporter::capturing(Verbosity::Quiet);
+        reprioritize(&db, &quiet, "open", "medium", "high").unwrap();
+        assert_eq!(quiet.output(), "");
+        assert_eq!(db.get_issue(id).unwrap().unwrap().priority, "high");
+    }
+
     #[test]
     fn test_update_title() {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Original title", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, Some("New title"), None, None);
+        let result = run(
+            &db,
+            &Reporter::default(),
+            issue_id,
+            Some("New title"),
+            None,
+            None,
+        );
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -64,7 +107,14 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, None, Some("New description"), None);
+        let result = run(
+            &db,
+            &Reporter::default(),
+            issue_id,
+            None,
+            Some("New description"),
+            None,
+        );
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -76,7 +126,14 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, None, None, Some("critical"));
+        let result = run(
+            &db,
+            &Reporter::default(),
+            issue_id,
+            None,
+            None,
+            Some("critical"),
+        );
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -92,6 +149,7 @@ mod tests {
 
         let result = run(
             &db,
+            &Reporter::default(),
             issue_id,
             Some("New title"),
             Some("New description"),
@@ -110,7 +168,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, None, None, None);
+        let result = run(&db, &Reporter::default(), issue_id, None, None, None);
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -122,7 +180,14 @@ mod tests {
     fn test_update_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
 
-        let result = run(&db, 99999, Some("New title"), None, None);
+        let result = run(
+            &db,
+            &Reporter::default(),
+            99999,
+            Some("New title"),
+            None,
+            None,
+        );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -132,7 +197,14 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, None, None, Some("urgent"));
+        let result = run(
+            &db,
+            &Reporter::default(),
+            issue_id,
+            None,
+            None,
+            Some("urgent"),
+        );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invalid priority"));
     }
@@ -145,7 +217,15 @@ mod tests {
             .unwrap();
 
         // Only update title
-        run(&db, issue_id, Some("New title"), None, None).unwrap();
+        run(
+            &db,
+            &Reporter::default(),
+            issue_id,
+            Some("New title"),
+            None,
+            None,
+        )
+        .unwrap();
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.title, "New title");
@@ -158,7 +238,14 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Original", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, Some("新しいタイトル 🎉"), None, None);
+        let result = run(
+            &db,
+            &Reporter::default(),
+            issue_id,
+            Some("新しいタイトル 🎉"),
+            None,
+            None,
+        );
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -172,7 +259,7 @@ mod tests {
             .create_issue("Test", Some("Has description"), "medium")
             .unwrap();
 
-        let result = run(&db, issue_id, None, Some(""), None);
+        let result = run(&db, &Reporter::default(), issue_id, None, Some(""), None);
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -185,7 +272,14 @@ mod tests {
         let issue_id = db.create_issue("Original", None, "medium").unwrap();
 
         let malicious = "'; DROP TABLE issues; --";
-        let result = run(&db, issue_id, Some(malicious), None, None);
+        let result = run(
+            &db,
+            &Reporter::default(),
+            issue_id,
+            Some(malicious),
+            None,
+            None,
+        );
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -202,7 +296,14 @@ mod tests {
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
         db.close_issue(issue_id).unwrap();
 
-        let result = run(&db, issue_id, Some("Updated closed issue"), None, None);
+        let result = run(
+            &db,
+            &Reporter::default(),
+            issue_id,
+            Some("Updated closed issue"),
+            None,
+            None,
+        );
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -215,13 +316,13 @@ mod tests {
     proptest! {
         #[test]
         fn prop_update_title_roundtrip(
-            original in "[a-zA-Z0-9 ]{1,30}",
-            new_title in "[a-zA-Z0-9 ]{1,30}"
+            original in "[a-zA-Z0-9][a-zA-Z0-9 ]{0,29}",
+            new_title in "[a-zA-Z0-9]([a-zA-Z0-9 ]{0,28}[a-zA-Z0-9])?"
         ) {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue(&original, None, "medium").unwrap();
 
-            run(&db, issue_id, Some(&new_title), None, None).unwrap();
+            run(&db, &Reporter::default(), issue_id, Some(&new_title), None, None).unwrap();
 
             let issue = db.get_issue(issue_id).unwrap().unwrap();
             prop_assert_eq!(issue.title, new_title);
@@ -232,7 +333,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            let result = run(&db, issue_id, None, None, Some(&priority));
+            let result = run(&db, &Reporter::default(), issue_id, None, None, Some(&priority));
             prop_assert!(result.is_ok());
 
             let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -249,7 +350,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            let result = run(&db, issue_id, None, None, Some(&priority));
+            let result = run(&db, &Reporter::default(), issue_id, None, None, Some(&priority));
             prop_assert!(result.is_err());
         }
 
@@ -257,7 +358,7 @@ mod tests {
         fn prop_nonexistent_issue_fails(issue_id in 1000i64..10000) {
             let (db, _dir) = setup_test_db();
 
-            let result = run(&db, issue_id, Some("New title"), None, None);
+            let result = run(&db, &Reporter::default(), issue_id, Some("New title"), None, None);
             prop_assert!(result.is_err());
         }
 
@@ -266,7 +367,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            run(&db, issue_id, None, Some(&desc), None).unwrap();
+            run(&db, &Reporter::default(), issue_id, None, Some(&desc), None).unwrap();
 
             let issue = db.get_issue(issue_id).unwrap().unwrap();
             prop_assert_eq!(issue.description, Some(desc));
@@ -0,0 +1,73 @@
+use anyhow::{bail, Result};
+
+use crate::db::Database;
+use crate::output::Reporter;
+
+pub fn run(db: &Database, out: &Reporter, id: i64, url: Option<&str>) -> Result<()> {
+    if !db.set_url(id, url)? {
+        bail!("Issue #{} not found", id);
+    }
+
+    match url {
+        Some(url) => out.info(&format!("Linked issue #{} to {}", id, url.trim())),
+        None => out.info(&format!("Cleared URL of issue #{}", id)),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_set_and_clear_url() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        run(
+            &db,
+            &Reporter::default(),
+            id,
+            Some("https://example.com/pr/1"),
+        )
+        .unwrap();
+        assert_eq!(
+            db.get_url(id).unwrap().as_deref(),
+            Some("https://example.com/pr/1")
+        );
+
+        run(&db, &Reporter::default(), id, None).unwrap();
+        assert_eq!(db.get_url(id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_non_http_url_rejected() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        let err = run(&db, &Reporter::default(), id, Some("file:///etc/passwd")).unwrap_err();
+        assert!(err.to_string().contains("http:// or https://"));
+        assert_eq!(db.get_url(id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_url_nonexistent_issue() {
+        let (db, _dir) = setup_test_db();
+        let result = run(
+            &db,
+            &Reporter::default(),
+            99999,
+            Some("https://example.com"),
+        );
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+}
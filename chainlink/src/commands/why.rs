@@ -0,0 +1,90 @@
+use anyhow::Result;
+
+use crate::db::Database;
+
+/// Explain whether an issue can be started: "ready", "blocked by open #2, #5", or its status.
+pub fn explain(db: &Database, id: i64) -> Result<String> {
+    let issue = db.require_issue(id)?;
+    if issue.status != "open" {
+        return Ok(issue.status);
+    }
+
+    let mut blocker_ids = db.get_blockers(id)?;
+    blocker_ids.sort_unstable();
+    let blockers = db.get_issues(&blocker_ids)?;
+    let open_blockers: Vec<String> = blocker_ids
+        .iter()
+        .filter(|b| blockers.get(b).is_some_and(|i| i.status == "open"))
+        .map(|b| format!("#{}", b))
+        .collect();
+
+    if open_blockers.is_empty() {
+        Ok("ready".to_string())
+    } else {
+        Ok(format!("blocked by open {}", open_blockers.join(", ")))
+    }
+}
+
+pub fn run(db: &Database, id: i64) -> Result<()> {
+    println!("#{}: {}", id, explain(db, id)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_why_ready() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Free", None, "medium").unwrap();
+        assert_eq!(explain(&db, id).unwrap(), "ready");
+        run(&db, id).unwrap();
+    }
+
+    #[test]
+    fn test_why_blocked_lists_only_open_blockers() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Target", None, "medium").unwrap();
+        let b1 = db.create_issue("Blocker 1", None, "medium").unwrap();
+        let b2 = db.create_issue("Blocker 2", None, "medium").unwrap();
+        let done = db.create_issue("Done blocker", None, "medium").unwrap();
+        for b in [b1, b2, done] {
+            db.add_dependency(id, b).unwrap();
+        }
+        db.close_issue(done).unwrap();
+
+        assert_eq!(
+            explain(&db, id).unwrap(),
+            format!("blocked by open #{}, #{}", b1, b2)
+        );
+
+        db.close_issue(b1).unwrap();
+        db.close_issue(b2).unwrap();
+        assert_eq!(explain(&db, id).unwrap(), "ready");
+    }
+
+    #[test]
+    fn test_why_closed() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Finished", None, "medium").unwrap();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        db.add_dependency(id, blocker).unwrap();
+        db.close_issue(id).unwrap();
+        assert_eq!(explain(&db, id).unwrap(), "closed");
+    }
+
+    #[test]
+    fn test_why_unknown_issue() {
+        let (db, _dir) = setup_test_db();
+        assert!(run(&db, 99).is_err());
+    }
+}
@@ -1,44 +1,311 @@
-use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rusqlite::{params, Connection};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::models::{
+    check_slug, Comment, Event, Issue, IssueRef, IssueTemplate, IssueWithBlockers, PriorityScale,
+    ReadyScore, Session, DEPENDENCY_KINDS, STATUSES,
+};
+
+const SCHEMA_VERSION: i32 = 21;
+
+/// Errors returned by `Database` methods.
+#[derive(Debug)]
+pub enum DbError {
+    /// No issue exists with this id
+    NotFound(i64),
+    /// A subissue was requested under an issue that does not exist
+    ParentNotFound(i64),
+    /// No saved template has this name
+    TemplateNotFound(String),
+    /// No issue has this slug
+    SlugNotFound(String),
+    /// No issue has this exact id, and it is the start of several others
+    AmbiguousId { prefix: i64, candidates: Vec<i64> },
+    /// The priority is not one of the configured levels
+    InvalidPriority {
+        priority: String,
+        valid: Vec<String>,
+    },
+    /// Adding a dependency would close this cycle. Ids are listed in blocking
+    /// order, starting and ending with the issue that would be blocked.
+    Cycle(Vec<i64>),
+    /// The request is inconsistent on its own, e.g. an issue blocking itself
+    Invalid(String),
+    /// The database file's location is unusable (a directory, read-only, or
+    /// its parent directory could not be created)
+    Storage { path: PathBuf, reason: String },
+    /// The underlying SQLite call failed
+    Sqlite(rusqlite::Error),
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DbError::NotFound(id) => write!(f, "Issue #{} not found", id),
+            DbError::ParentNotFound(id) => write!(f, "Parent issue #{} not found", id),
+            DbError::TemplateNotFound(name) => write!(f, "Template '{}' not found", name),
+            DbError::SlugNotFound(slug) => write!(f, "No issue has slug '{}'", slug),
+            DbError::AmbiguousId { prefix, candidates } => {
+                let shown: Vec<String> = candidates
+                    .iter()
+                    .take(AMBIGUOUS_SHOWN)
+                    .map(|id| format!("#{}", id))
+                    .collect();
+                let more = candidates.len().saturating_sub(AMBIGUOUS_SHOWN);
+                write!(
+                    f,
+                    "Issue id {} is ambiguous; it could be {}",
+                    prefix,
+                    shown.join(", ")
+                )?;
+                if more > 0 {
+                    write!(f, " and {} more", more)?;
+                }
+                Ok(())
+            }
+            DbError::InvalidPriority { priority, valid } => write!(
+                f,
+                "Invalid priority '{}'. Must be one of: {}",
+                priority,
+                valid.join(", ")
+            ),
+            DbError::Cycle(path) => {
+                let chain: Vec<String> = path.iter().map(|id| format!("#{}", id)).collect();
+                write!(
+                    f,
+                    "Adding this dependency would create a circular dependency chain: {}",
+                    chain.join(" -> ")
+                )
+            }
+            DbError::Invalid(msg) => write!(f, "{}", msg),
+            DbError::Storage { path, reason } => {
+                write!(f, "Cannot open database at {}: {}", path.display(), reason)
+            }
+            DbError::Sqlite(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DbError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DbError::Sqlite(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for DbError {
+    fn from(e: rusqlite::Error) -> Self {
+        DbError::Sqlite(e)
+    }
+}
 
-use crate::models::{Comment, Issue, Session};
+pub type Result<T> = std::result::Result<T, DbError>;
+
+/// `refresh_blocked` condition selecting the issues that `?1` blocks.
+const BLOCKED_BY_ID: &str =
+    "id IN (SELECT blocked_id FROM dependencies WHERE blocker_id = ?1 AND kind = 'blocks')";
+
+/// Assignee filter value that matches issues nobody owns.
+pub const UNASSIGNED: &str = "none";
+
+/// Filters for `list_issues_filtered`. A `None` field does not filter.
+#[derive(Default, Clone, Copy)]
+pub struct IssueFilter<'a> {
+    /// Comma-separated statuses to match (e.g. "open,closed"), "all" for any;
+    /// `None` hides archived issues
+    pub status: Option<&'a str>,
+    pub label: Option<&'a str>,
+    pub priority: Option<&'a str>,
+    /// Assignee name, or `UNASSIGNED` for issues without one
+    pub assignee: Option<&'a str>,
+}
+
+/// Options for `search_issues`. The default is a substring match over every issue.
+#[derive(Default, Clone, Copy)]
+pub struct SearchOptions {
+    /// Only search descendants of this issue (not the issue itself)
+    pub under: Option<i64>,
+    /// Match the query only at the start of a word in the title or description
+    pub prefix: bool,
+    /// Return at most this many results, keeping the best-ranked
+    pub limit: Option<usize>,
+}
 
-const SCHEMA_VERSION: i32 = 8;
+/// Facts about the open database, for `chainlink info`.
+pub struct DbInfo {
+    pub sqlite_version: &'static str,
+    pub schema_version: i32,
+    pub journal_mode: String,
+    /// Every issue, whatever its status
+    pub issue_count: i64,
+}
 
 pub struct Database {
     conn: Connection,
+    max_depth: usize,
+    max_description_bytes: usize,
+    default_priority: String,
+}
+
+/// Default cap on subissue nesting; see `Database::set_max_depth`.
+pub const DEFAULT_MAX_DEPTH: usize = 50;
+
+/// Default cap on description size; see `Database::set_max_description_bytes`.
+pub const DEFAULT_MAX_DESCRIPTION_BYTES: usize = 64 * 1024;
+
+/// Priority used when none is given; see `Database::set_default_priority`.
+pub const DEFAULT_PRIORITY: &str = "medium";
+
+/// Maximum ids bound in one `IN (...)` query by `get_issues`, well under SQLite's parameter limit.
+const ID_CHUNK_SIZE: usize = 500;
+
+/// Number of rows `IssueIter` fetches per query.
+const ISSUE_PAGE_SIZE: usize = 256;
+
+/// Candidates listed in an `AmbiguousId` error before the rest are counted.
+const AMBIGUOUS_SHOWN: usize = 10;
+
+/// Lazily pages through issues matching a filter, newest first.
+/// Uses keyset pagination on id so each page is a fresh, short-lived statement.
+struct IssueIter<'a> {
+    db: &'a Database,
+    filter: IssueFilter<'a>,
+    before: Option<i64>,
+    page: std::vec::IntoIter<Issue>,
+    done: bool,
+}
+
+impl Iterator for IssueIter<'_> {
+    type Item = Result<Issue>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(issue) = self.page.next() {
+            return Some(Ok(issue));
+        }
+        if self.done {
+            return None;
+        }
+
+        match self.db.issue_page(&self.filter, self.before) {
+            Ok(page) => {
+                self.done = page.len() < ISSUE_PAGE_SIZE;
+                self.before = page.last().map(|i| i.id);
+                self.page = page.into_iter();
+                self.page.next().map(Ok)
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
 }
 
 impl Database {
+    /// Open (or create) the database at `path`, creating missing parent directories.
     pub fn open(path: &Path) -> Result<Self> {
-        let conn = Connection::open(path).context("Failed to open database")?;
-        let db = Database { conn };
+        prepare_location(path)?;
+        let conn = Connection::open(path)?;
+        let db = Database {
+            conn,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_description_bytes: DEFAULT_MAX_DESCRIPTION_BYTES,
+            default_priority: DEFAULT_PRIORITY.to_string(),
+        };
         db.init_schema()?;
         Ok(db)
     }
 
+    /// Limit how deeply subissues may nest. A top-level issue has depth 0.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Limit how large a description may be, in bytes after line-ending normalization.
+    pub fn set_max_description_bytes(&mut self, max_bytes: usize) {
+        self.max_description_bytes = max_bytes;
+    }
+
+    /// Priority for new issues created without one. Must be a configured level.
+    pub fn set_default_priority(&mut self, priority: &str) -> Result<()> {
+        self.check_priority(priority)?;
+        self.default_priority = priority.to_string();
+        Ok(())
+    }
+
+    pub fn default_priority(&self) -> &str {
+        &self.default_priority
+    }
+
+    /// Normalize `\r\n` and lone `\r` line endings to `\n` and enforce the size cap.
+    fn clean_description(&self, description: &str) -> Result<String> {
+        let normalized = description.replace("\r\n", "\n").replace('\r', "\n");
+        if normalized.len() > self.max_description_bytes {
+            return Err(DbError::Invalid(format!(
+                "Description is {} bytes; the limit is {}",
+                normalized.len(),
+                self.max_description_bytes
+            )));
+        }
+        Ok(normalized)
+    }
+
     /// Execute a closure within a database transaction.
     /// If the closure returns Ok, the transaction is committed.
     /// If the closure returns Err, the transaction is rolled back.
-    pub fn transaction<T, F>(&self, f: F) -> Result<T>
+    /// Inside another transaction (such as a dry run) it nests as a savepoint.
+    pub fn transaction<T, E, F>(&self, f: F) -> std::result::Result<T, E>
     where
-        F: FnOnce() -> Result<T>,
+        F: FnOnce() -> std::result::Result<T, E>,
+        E: From<DbError>,
     {
-        self.conn.execute("BEGIN TRANSACTION", [])?;
+        let (begin, commit, rollback) = self.transaction_statements();
+        self.conn.execute_batch(begin).map_err(DbError::from)?;
         match f() {
             Ok(result) => {
-                self.conn.execute("COMMIT", [])?;
+                self.conn.execute_batch(commit).map_err(DbError::from)?;
                 Ok(result)
             }
             Err(e) => {
-                let _ = self.conn.execute("ROLLBACK", []);
+                let _ = self.conn.execute_batch(rollback);
                 Err(e)
             }
         }
     }
 
+    /// Execute a closure within a transaction that is always rolled back,
+    /// so a command can report what it would change without keeping it.
+    pub fn dry_run<T, E, F>(&self, f: F) -> std::result::Result<T, E>
+    where
+        F: FnOnce() -> std::result::Result<T, E>,
+        E: From<DbError>,
+    {
+        let (begin, _, rollback) = self.transaction_statements();
+        self.conn.execute_batch(begin).map_err(DbError::from)?;
+        let result = f();
+        self.conn.execute_batch(rollback).map_err(DbError::from)?;
+        result
+    }
+
+    /// Begin, commit and rollback statements, using a savepoint when
+    /// already inside a transaction so calls can nest.
+    fn transaction_statements(&self) -> (&'static str, &'static str, &'static str) {
+        if self.conn.is_autocommit() {
+            ("BEGIN TRANSACTION", "COMMIT", "ROLLBACK")
+        } else {
+            (
+                "SAVEPOINT nested",
+                "RELEASE nested",
+                "ROLLBACK TO nested; RELEASE nested",
+            )
+        }
+    }
+
     fn init_schema(&self) -> Result<()> {
         // Check if we need to initialize
         let version: i32 = self
@@ -75,11 +342,13 @@ impl Database {
                     FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
                 );
 
-                -- Dependencies (blocker blocks blocked)
+                -- Dependencies (blocker blocks blocked, or blocked duplicates blocker)
                 CREATE TABLE IF NOT EXISTS dependencies (
                     blocker_id INTEGER NOT NULL,
                     blocked_id INTEGER NOT NULL,
-                    PRIMARY KEY (blocker_id, blocked_id),
+                    kind TEXT NOT NULL DEFAULT 'blocks',
+                    created_at TEXT,
+                    PRIMARY KEY (blocker_id, blocked_id, kind),
                     FOREIGN KEY (blocker_id) REFERENCES issues(id) ON DELETE CASCADE,
                     FOREIGN KEY (blocked_id) REFERENCES issues(id) ON DELETE CASCADE
                 );
@@ -142,11 +411,46 @@ impl Database {
                     FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
                 );
 
+                -- Saved issue templates
+                CREATE TABLE IF NOT EXISTS templates (
+                    name TEXT PRIMARY KEY,
+                    title TEXT NOT NULL,
+                    description TEXT,
+                    priority TEXT NOT NULL DEFAULT 'medium'
+                );
+
+                -- Subissue titles created with each template, in order
+                CREATE TABLE IF NOT EXISTS template_subissues (
+                    template_name TEXT NOT NULL,
+                    position INTEGER NOT NULL,
+                    title TEXT NOT NULL,
+                    PRIMARY KEY (template_name, position),
+                    FOREIGN KEY (template_name) REFERENCES templates(name) ON DELETE CASCADE
+                );
+
+                -- Configured priority levels; empty means the built-in defaults
+                CREATE TABLE IF NOT EXISTS priorities (
+                    name TEXT PRIMARY KEY,
+                    rank INTEGER NOT NULL UNIQUE
+                );
+
+                -- Lifecycle events per issue (status, priority and parent changes)
+                CREATE TABLE IF NOT EXISTS issue_events (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    issue_id INTEGER NOT NULL,
+                    kind TEXT NOT NULL,
+                    old_value TEXT,
+                    new_value TEXT,
+                    created_at TEXT NOT NULL,
+                    FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+                );
+
                 -- Indexes
                 CREATE INDEX IF NOT EXISTS idx_issues_status ON issues(status);
                 CREATE INDEX IF NOT EXISTS idx_issues_priority ON issues(priority);
                 CREATE INDEX IF NOT EXISTS idx_labels_issue ON labels(issue_id);
                 CREATE INDEX IF NOT EXISTS idx_comments_issue ON comments(issue_id);
+                CREATE INDEX IF NOT EXISTS idx_issue_events_issue ON issue_events(issue_id);
                 CREATE INDEX IF NOT EXISTS idx_deps_blocker ON dependencies(blocker_id);
                 CREATE INDEX IF NOT EXISTS idx_deps_blocked ON dependencies(blocked_id);
                 CREATE INDEX IF NOT EXISTS idx_issues_parent ON issues(parent_id);
@@ -191,6 +495,132 @@ impl Database {
                     .execute("ALTER TABLE sessions ADD COLUMN last_action TEXT", []);
             }
 
+            // Migration v9: Add estimate column to issues table
+            if version < 9 {
+                let _ = self
+                    .conn
+                    .execute("ALTER TABLE issues ADD COLUMN estimate INTEGER", []);
+            }
+
+            // Migration v10: Add assignee column to issues table
+            if version < 10 {
+                let _ = self
+                    .conn
+                    .execute("ALTER TABLE issues ADD COLUMN assignee TEXT", []);
+            }
+
+            // Migration v12: Add kind column to dependencies table
+            if version < 12 {
+                let _ = self.conn.execute(
+                    "ALTER TABLE dependencies ADD COLUMN kind TEXT NOT NULL DEFAULT 'blocks'",
+                    [],
+                );
+            }
+
+            // Migration v13: Timestamp dependency rows. Existing edges get the newer of
+            // their two issues' creation times, the earliest they could have been added.
+            if version < 13 {
+                let _ = self
+                    .conn
+                    .execute("ALTER TABLE dependencies ADD COLUMN created_at TEXT", []);
+                self.conn.execute(
+                    r#"
+                    UPDATE dependencies SET created_at = (
+                        SELECT MAX(created_at) FROM issues WHERE id IN (blocker_id, blocked_id)
+                    )
+                    WHERE created_at IS NULL
+                    "#,
+                    [],
+                )?;
+            }
+
+            // Migration v15: Start each existing issue's history with its creation and,
+            // if closed, its closing. Earlier changes were never recorded.
+            if version < 15 {
+                self.conn.execute_batch(
+                    r#"
+                    INSERT INTO issue_events (issue_id, kind, created_at)
+                    SELECT id, 'created', created_at FROM issues;
+                    INSERT INTO issue_events (issue_id, kind, old_value, new_value, created_at)
+                    SELECT id, 'status', 'open', 'closed', closed_at
+                    FROM issues WHERE closed_at IS NOT NULL;
+                    "#,
+                )?;
+            }
+
+            // Migration v16: Add due_date column (YYYY-MM-DD) to issues table
+            if version < 16 {
+                let _ = self
+                    .conn
+                    .execute("ALTER TABLE issues ADD COLUMN due_date TEXT", []);
+            }
+
+            // Migration v17: Add resolution column (note recorded on close) to issues table
+            if version < 17 {
+                let _ = self
+                    .conn
+                    .execute("ALTER TABLE issues ADD COLUMN resolution TEXT", []);
+            }
+
+            // Migration v18: Add unique slug column (short name usable in place of an id)
+            if version < 18 {
+                let _ = self
+                    .conn
+                    .execute("ALTER TABLE issues ADD COLUMN slug TEXT", []);
+                self.conn.execute(
+                    "CREATE UNIQUE INDEX IF NOT EXISTS idx_issues_slug ON issues(slug)",
+                    [],
+                )?;
+            }
+
+            // Migration v19: Cache whether each issue has an open blocker, so the
+            // ready and blocked lists don't join dependencies for every issue
+            if version < 19 {
+                let _ = self.conn.execute(
+                    "ALTER TABLE issues ADD COLUMN is_blocked INTEGER NOT NULL DEFAULT 0",
+                    [],
+                );
+                self.conn.execute(
+                    "CREATE INDEX IF NOT EXISTS idx_issues_blocked ON issues(status, is_blocked)",
+                    [],
+                )?;
+                self.recompute_blocked()?;
+            }
+
+            // Migration v20: Add url column linking an issue to an external ticket or PR
+            if version < 20 {
+                let _ = self
+                    .conn
+                    .execute("ALTER TABLE issues ADD COLUMN url TEXT", []);
+            }
+
+            // Migration v21: Key dependencies by kind as well, so one pair of issues can
+            // have edges of several kinds (e.g. "blocks" and "after") side by side
+            if version < 21 {
+                self.transaction(|| -> Result<()> {
+                    self.conn.execute_batch(
+                        r#"
+                        CREATE TABLE dependencies_new (
+                            blocker_id INTEGER NOT NULL,
+                            blocked_id INTEGER NOT NULL,
+                            kind TEXT NOT NULL DEFAULT 'blocks',
+                            created_at TEXT,
+                            PRIMARY KEY (blocker_id, blocked_id, kind),
+                            FOREIGN KEY (blocker_id) REFERENCES issues(id) ON DELETE CASCADE,
+                            FOREIGN KEY (blocked_id) REFERENCES issues(id) ON DELETE CASCADE
+                        );
+                        INSERT INTO dependencies_new (blocker_id, blocked_id, kind, created_at)
+                        SELECT blocker_id, blocked_id, kind, created_at FROM dependencies;
+                        DROP TABLE dependencies;
+                        ALTER TABLE dependencies_new RENAME TO dependencies;
+                        CREATE INDEX IF NOT EXISTS idx_deps_blocker ON dependencies(blocker_id);
+                        CREATE INDEX IF NOT EXISTS idx_deps_blocked ON dependencies(blocked_id);
+                        "#,
+                    )?;
+                    Ok(())
+                })?;
+            }
+
             self.conn
                 .execute(&format!("PRAGMA user_version = {}", SCHEMA_VERSION), [])?;
         }
@@ -218,9 +648,70 @@ impl Database {
         description: Option<&str>,
         priority: &str,
     ) -> Result<i64> {
+        if self.get_issue(parent_id)?.is_none() {
+            return Err(DbError::ParentNotFound(parent_id));
+        }
+        self.check_depth(parent_id, 0)?;
         self.create_issue_with_parent(title, description, priority, Some(parent_id))
     }
 
+    /// Fail if putting a subtree `height` levels tall under `parent_id` would nest
+    /// deeper than the cap.
+    fn check_depth(&self, parent_id: i64, height: usize) -> Result<()> {
+        if self.depth_of(parent_id)? + 1 + height > self.max_depth {
+            return Err(DbError::Invalid(format!(
+                "Cannot nest deeper than {} levels",
+                self.max_depth
+            )));
+        }
+        Ok(())
+    }
+
+    /// Number of ancestors above an issue, found by walking up the parent chain.
+    /// Stops counting once past the depth cap so a corrupt parent loop cannot spin forever.
+    fn depth_of(&self, id: i64) -> Result<usize> {
+        let mut depth = 0;
+        let mut current = id;
+        while depth <= self.max_depth {
+            let parent = match self.conn.query_row(
+                "SELECT parent_id FROM issues WHERE id = ?1",
+                [current],
+                |row| row.get::<_, Option<i64>>(0),
+            ) {
+                Ok(parent) => parent,
+                Err(rusqlite::Error::QueryReturnedNoRows) => None,
+                Err(e) => return Err(e.into()),
+            };
+            match parent {
+                Some(p) => {
+                    depth += 1;
+                    current = p;
+                }
+                None => break,
+            }
+        }
+        Ok(depth)
+    }
+
+    /// Number of levels of subissues below an issue; 0 for a leaf.
+    /// Bounded like `depth_of`, so a parent loop cannot recurse forever.
+    fn height_of(&self, id: i64) -> Result<usize> {
+        let height: i64 = self.conn.query_row(
+            r#"
+            WITH RECURSIVE subtree(id, depth) AS (
+                SELECT id, 1 FROM issues WHERE parent_id = ?1
+                UNION ALL
+                SELECT i.id, s.depth + 1 FROM issues i JOIN subtree s ON i.parent_id = s.id
+                WHERE s.depth <= ?2
+            )
+            SELECT COALESCE(MAX(depth), 0) FROM subtree
+            "#,
+            params![id, self.max_depth as i64],
+            |row| row.get(0),
+        )?;
+        Ok(height as usize)
+    }
+
     fn create_issue_with_parent(
         &self,
         title: &str,
@@ -228,12 +719,20 @@ impl Database {
         priority: &str,
         parent_id: Option<i64>,
     ) -> Result<i64> {
+        let title = clean_title(title)?;
+        let description = description.map(|d| self.clean_description(d)).transpose()?;
+        self.check_priority(priority)?;
         let now = Utc::now().to_rfc3339();
         self.conn.execute(
             "INSERT INTO issues (title, description, priority, parent_id, status, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, 'open', ?5, ?5)",
             params![title, description, priority, parent_id, now],
         )?;
-        Ok(self.conn.last_insert_rowid())
+        let id = self.conn.last_insert_rowid();
+        self.conn.execute(
+            "INSERT INTO issue_events (issue_id, kind, created_at) VALUES (?1, 'created', ?2)",
+            params![id, now],
+        )?;
+        Ok(id)
     }
 
     pub fn get_subissues(&self, parent_id: i64) -> Result<Vec<Issue>> {
@@ -258,11 +757,72 @@ impl Database {
         Ok(issue)
     }
 
+    /// Fetch many issues at once. Ids that don't exist are simply absent from the map.
+    pub fn get_issues(&self, ids: &[i64]) -> Result<HashMap<i64, Issue>> {
+        let mut issues = HashMap::with_capacity(ids.len());
+        for chunk in ids.chunks(ID_CHUNK_SIZE) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!(
+                "SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at FROM issues WHERE id IN ({})",
+                placeholders
+            );
+            let mut stmt = self.conn.prepare(&sql)?;
+            let rows = stmt.query_map(rusqlite::params_from_iter(chunk), issue_from_row)?;
+            for issue in rows {
+                let issue = issue?;
+                issues.insert(issue.id, issue);
+            }
+        }
+        Ok(issues)
+    }
+
     /// Get an issue by ID, returning an error if not found.
     /// Use this instead of get_issue when you need the issue to exist.
     pub fn require_issue(&self, id: i64) -> Result<Issue> {
-        self.get_issue(id)?
-            .ok_or_else(|| anyhow::anyhow!("Issue #{} not found", id))
+        self.get_issue(id)?.ok_or(DbError::NotFound(id))
+    }
+
+    /// Turn an id or slug given on the command line into an issue id. Ids are
+    /// passed through unchecked; slugs must belong to an issue.
+    pub fn resolve_issue(&self, issue: &IssueRef) -> Result<i64> {
+        match issue {
+            IssueRef::Id(id) => Ok(*id),
+            IssueRef::Slug(slug) => self
+                .slug_owner(slug)
+                .ok_or_else(|| DbError::SlugNotFound(slug.clone())),
+        }
+    }
+
+    /// Like `resolve_issue` for an id, except that an id no issue has stands
+    /// for the one issue whose id starts with those digits, and is ambiguous if
+    /// several do; with no such issue it is passed through for the caller to
+    /// report. Only meant for read-only lookups.
+    pub fn resolve_id_prefix(&self, id: i64) -> Result<i64> {
+        if id <= 0 || self.get_issue(id)?.is_some() {
+            return Ok(id);
+        }
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM issues WHERE CAST(id AS TEXT) LIKE ?1 || '%' ORDER BY id")?;
+        let candidates = stmt
+            .query_map([id], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+        match candidates.as_slice() {
+            [] => Ok(id),
+            [only] => Ok(*only),
+            _ => Err(DbError::AmbiguousId {
+                prefix: id,
+                candidates,
+            }),
+        }
+    }
+
+    fn slug_owner(&self, slug: &str) -> Option<i64> {
+        self.conn
+            .query_row("SELECT id FROM issues WHERE slug = ?1", [slug], |row| {
+                row.get(0)
+            })
+            .ok()
     }
 
     pub fn list_issues(
@@ -271,39 +831,53 @@ impl Database {
         label_filter: Option<&str>,
         priority_filter: Option<&str>,
     ) -> Result<Vec<Issue>> {
-        let mut sql = String::from(
-            "SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at FROM issues i",
-        );
-        let mut conditions = Vec::new();
-        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
-
-        if label_filter.is_some() {
-            sql.push_str(" JOIN labels l ON i.id = l.issue_id");
-        }
-
-        if let Some(status) = status_filter {
-            if status != "all" {
-                conditions.push("i.status = ?".to_string());
-                params_vec.push(Box::new(status.to_string()));
-            }
-        }
+        self.list_issues_filtered(&IssueFilter {
+            status: status_filter,
+            label: label_filter,
+            priority: priority_filter,
+            ..Default::default()
+        })
+    }
 
-        if let Some(label) = label_filter {
-            conditions.push("l.label = ?".to_string());
-            params_vec.push(Box::new(label.to_string()));
-        }
+    pub fn list_issues_filtered(&self, filter: &IssueFilter<'_>) -> Result<Vec<Issue>> {
+        self.iter_issues(*filter).collect()
+    }
 
-        if let Some(priority) = priority_filter {
-            conditions.push("i.priority = ?".to_string());
-            params_vec.push(Box::new(priority.to_string()));
+    /// Stream issues matching `filter` newest first without loading them all at once.
+    /// Rows are fetched in pages of `ISSUE_PAGE_SIZE` as the iterator advances.
+    pub fn iter_issues<'a>(
+        &'a self,
+        filter: IssueFilter<'a>,
+    ) -> impl Iterator<Item = Result<Issue>> + 'a {
+        IssueIter {
+            db: self,
+            filter,
+            before: None,
+            page: Vec::new().into_iter(),
+            done: false,
         }
+    }
 
-        if !conditions.is_empty() {
-            sql.push_str(" WHERE ");
-            sql.push_str(&conditions.join(" AND "));
-        }
+    /// Count issues matching `filter` without loading them.
+    pub fn count_issues(&self, filter: &IssueFilter<'_>) -> Result<i64> {
+        let (from, params_vec) = filter_sql(filter, None)?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+        let count = self.conn.query_row(
+            &format!("SELECT COUNT(DISTINCT i.id) {}", from),
+            params_refs.as_slice(),
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
 
-        sql.push_str(" ORDER BY i.id DESC");
+    /// Fetch one page of filtered issues with ids below `before`, newest first.
+    fn issue_page(&self, filter: &IssueFilter<'_>, before: Option<i64>) -> Result<Vec<Issue>> {
+        let (from, params_vec) = filter_sql(filter, before)?;
+        let sql = format!(
+            "SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at {} ORDER BY i.id DESC LIMIT {}",
+            from, ISSUE_PAGE_SIZE
+        );
 
         let mut stmt = self.conn.prepare(&sql)?;
         let params_refs: Vec<&dyn rusqlite::ToSql> =
@@ -323,6 +897,11 @@ impl Database {
         description: Option<&str>,
         priority: Option<&str>,
     ) -> Result<bool> {
+        let title = title.map(clean_title).transpose()?;
+        let description = description.map(|d| self.clean_description(d)).transpose()?;
+        if let Some(p) = priority {
+            self.check_priority(p)?;
+        }
         let now = Utc::now().to_rfc3339();
         let mut updates = vec!["updated_at = ?1".to_string()];
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(now)];
@@ -334,12 +913,13 @@ impl Database {
 
         if let Some(d) = description {
             updates.push(format!("description = ?{}", params_vec.len() + 1));
-            params_vec.push(Box::new(d.to_string()));
+            params_vec.push(Box::new(d));
         }
 
         if let Some(p) = priority {
             updates.push(format!("priority = ?{}", params_vec.len() + 1));
             params_vec.push(Box::new(p.to_string()));
+            self.record_change(id, "priority", "priority", p)?;
         }
 
         params_vec.push(Box::new(id));
@@ -355,31 +935,234 @@ impl Database {
         Ok(rows > 0)
     }
 
+    /// Move every issue at priority `from` to `to`, in one transaction.
+    /// `status_filter` works as in `list_issues`. Returns how many issues changed.
+    pub fn set_priority_where(
+        &self,
+        status_filter: Option<&str>,
+        from: &str,
+        to: &str,
+    ) -> Result<usize> {
+        self.check_priority(from)?;
+        self.check_priority(to)?;
+        if from == to {
+            return Ok(0);
+        }
+        self.transaction(|| {
+            let issues = self.list_issues(status_filter, None, Some(from))?;
+            for issue in &issues {
+                self.update_issue(issue.id, None, None, Some(to))?;
+            }
+            Ok(issues.len())
+        })
+    }
+
+    /// Close an open issue. Returns false if the issue is missing or was not open.
     pub fn close_issue(&self, id: i64) -> Result<bool> {
         let now = Utc::now().to_rfc3339();
+        self.record_status_changes("closed", "id = ?1 AND status = 'open'", id, &now)?;
         let rows = self.conn.execute(
-            "UPDATE issues SET status = 'closed', closed_at = ?1, updated_at = ?1 WHERE id = ?2",
+            "UPDATE issues SET status = 'closed', closed_at = ?1, updated_at = ?1 WHERE id = ?2 AND status = 'open'",
             params![now, id],
         )?;
+        self.refresh_blocked(BLOCKED_BY_ID, &[&id])?;
         Ok(rows > 0)
     }
 
+    /// Reopen an issue. Returns false if the issue is missing or already open.
     pub fn reopen_issue(&self, id: i64) -> Result<bool> {
         let now = Utc::now().to_rfc3339();
+        self.record_status_changes("open", "id = ?1 AND status != 'open'", id, &now)?;
         let rows = self.conn.execute(
-            "UPDATE issues SET status = 'open', closed_at = NULL, updated_at = ?1 WHERE id = ?2",
+            "UPDATE issues SET status = 'open', closed_at = NULL, resolution = NULL, updated_at = ?1 WHERE id = ?2 AND status != 'open'",
             params![now, id],
         )?;
+        self.refresh_blocked(BLOCKED_BY_ID, &[&id])?;
         Ok(rows > 0)
     }
 
+    /// Reopen an issue and every closed issue beneath it. Returns how many were reopened.
+    pub fn reopen_issue_recursive(&self, id: i64) -> Result<usize> {
+        self.transaction(|| {
+            let mut reopened = usize::from(self.reopen_issue(id)?);
+            let now = Utc::now().to_rfc3339();
+            self.record_status_changes(
+                "open",
+                r#"status = 'closed' AND id IN (
+                    WITH RECURSIVE subtree(id) AS (
+                        SELECT id FROM issues WHERE parent_id = ?1
+                        UNION
+                        SELECT i.id FROM issues i JOIN subtree s ON i.parent_id = s.id
+                    )
+                    SELECT id FROM subtree
+                )"#,
+                id,
+                &now,
+            )?;
+            reopened += self.conn.execute(
+                r#"
+                WITH RECURSIVE subtree(id) AS (
+                    SELECT id FROM issues WHERE parent_id = ?1
+                    UNION
+                    SELECT i.id FROM issues i JOIN subtree s ON i.parent_id = s.id
+                )
+                UPDATE issues SET status = 'open', closed_at = NULL, resolution = NULL, updated_at = ?2
+                WHERE id IN (SELECT id FROM subtree) AND status = 'closed'
+                "#,
+                params![id, now],
+            )?;
+            self.recompute_blocked()?;
+            Ok(reopened)
+        })
+    }
+
     pub fn delete_issue(&self, id: i64) -> Result<bool> {
         let rows = self
             .conn
             .execute("DELETE FROM issues WHERE id = ?1", [id])?;
+        // Deleting cascades to subissues and their edges; any of them may have been a blocker
+        self.recompute_blocked()?;
         Ok(rows > 0)
     }
 
+    /// Recompute the cached `is_blocked` flag of the issues matching `condition`
+    /// (a fixed WHERE clause over `issues` using `params`). Returns how many
+    /// flags were wrong.
+    fn refresh_blocked(&self, condition: &str, params: &[&dyn rusqlite::ToSql]) -> Result<usize> {
+        let rows = self.conn.execute(
+            &format!(
+                r#"
+                UPDATE issues SET is_blocked = NOT is_blocked
+                WHERE ({}) AND is_blocked != EXISTS (
+                    SELECT 1 FROM dependencies d
+                    JOIN issues blocker ON blocker.id = d.blocker_id
+                    WHERE d.blocked_id = issues.id AND d.kind = 'blocks' AND blocker.status = 'open'
+                )
+                "#,
+                condition
+            ),
+            params,
+        )?;
+        Ok(rows)
+    }
+
+    /// Bring every issue's cached `is_blocked` flag back in line with its
+    /// dependencies. Returns how many flags were wrong.
+    pub fn recompute_blocked(&self) -> Result<usize> {
+        self.refresh_blocked("1", &[])
+    }
+
+    // History
+    /// Record a status change to `new_status` for every issue matching `condition`.
+    /// Call before the UPDATE, with the same condition, so the old status is captured.
+    /// `?1` in the condition is bound to `id`.
+    fn record_status_changes(
+        &self,
+        new_status: &str,
+        condition: &str,
+        id: i64,
+        now: &str,
+    ) -> Result<()> {
+        self.conn.execute(
+            &format!(
+                r#"
+                INSERT INTO issue_events (issue_id, kind, old_value, new_value, created_at)
+                SELECT id, 'status', status, ?2, ?3 FROM issues WHERE {}
+                "#,
+                condition
+            ),
+            params![id, new_status, now],
+        )?;
+        Ok(())
+    }
+
+    /// Record a change of `column` to `value`, if it actually changes.
+    fn record_change(&self, id: i64, kind: &str, column: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            &format!(
+                r#"
+                INSERT INTO issue_events (issue_id, kind, old_value, new_value, created_at)
+                SELECT id, ?2, {col}, ?3, ?4 FROM issues WHERE id = ?1 AND {col} IS NOT ?3
+                "#,
+                col = column
+            ),
+            params![id, kind, value, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// How long an issue took from creation to its final close, from its history.
+    /// Issues closed more than once count from creation to the last close.
+    /// Returns None for issues that are missing or not closed.
+    pub fn time_to_close(&self, id: i64) -> Result<Option<chrono::Duration>> {
+        Ok(self.cycle_times(Some(id))?.into_iter().next())
+    }
+
+    /// Mean creation-to-final-close time over all closed (and archived) issues.
+    pub fn average_time_to_close(&self) -> Result<Option<chrono::Duration>> {
+        let times = self.cycle_times(None)?;
+        if times.is_empty() {
+            return Ok(None);
+        }
+        let total: chrono::Duration = times.iter().sum();
+        Ok(Some(total / times.len() as i32))
+    }
+
+    /// Creation-to-final-close times of closed issues, or just `id` if given.
+    /// Unarchiving records archived -> closed, which is not a close, so only
+    /// open -> closed events count.
+    fn cycle_times(&self, id: Option<i64>) -> Result<Vec<chrono::Duration>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT
+                COALESCE(
+                    (SELECT MIN(created_at) FROM issue_events
+                     WHERE issue_id = i.id AND kind = 'created'),
+                    i.created_at),
+                COALESCE(
+                    (SELECT MAX(created_at) FROM issue_events
+                     WHERE issue_id = i.id AND kind = 'status'
+                       AND old_value = 'open' AND new_value = 'closed'),
+                    i.closed_at)
+            FROM issues i
+            WHERE i.closed_at IS NOT NULL AND (?1 IS NULL OR i.id = ?1)
+            ORDER BY i.id
+            "#,
+        )?;
+        let rows = stmt
+            .query_map([id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        rows.iter()
+            .map(|(created, closed)| Ok(parse_timestamp(closed)? - parse_timestamp(created)?))
+            .collect()
+    }
+
+    /// An issue's lifecycle events, oldest first.
+    pub fn get_history(&self, issue_id: i64) -> Result<Vec<Event>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, issue_id, kind, old_value, new_value, created_at
+            FROM issue_events WHERE issue_id = ?1 ORDER BY created_at, id
+            "#,
+        )?;
+        let events = stmt
+            .query_map([issue_id], |row| {
+                Ok(Event {
+                    id: row.get(0)?,
+                    issue_id: row.get(1)?,
+                    kind: row.get(2)?,
+                    old_value: row.get(3)?,
+                    new_value: row.get(4)?,
+                    created_at: parse_datetime(row.get::<_, String>(5)?),
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(events)
+    }
+
     // Labels
     pub fn add_label(&self, issue_id: i64, label: &str) -> Result<bool> {
         let result = self.conn.execute(
@@ -409,17 +1192,22 @@ impl Database {
 
     // Comments
     pub fn add_comment(&self, issue_id: i64, content: &str) -> Result<i64> {
-        let now = Utc::now().to_rfc3339();
+        self.add_comment_at(issue_id, content, &Utc::now().to_rfc3339())
+    }
+
+    /// Add a comment with a given RFC 3339 timestamp, e.g. when restoring an export.
+    pub fn add_comment_at(&self, issue_id: i64, content: &str, created_at: &str) -> Result<i64> {
+        let created_at = parse_timestamp(created_at)?.to_rfc3339();
         self.conn.execute(
             "INSERT INTO comments (issue_id, content, created_at) VALUES (?1, ?2, ?3)",
-            params![issue_id, content, now],
+            params![issue_id, content, created_at],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
 
     pub fn get_comments(&self, issue_id: i64) -> Result<Vec<Comment>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, issue_id, content, created_at FROM comments WHERE issue_id = ?1 ORDER BY created_at",
+            "SELECT id, issue_id, content, created_at FROM comments WHERE issue_id = ?1 ORDER BY created_at, id",
         )?;
         let comments = stmt
             .query_map([issue_id], |row| {
@@ -436,118 +1224,712 @@ impl Database {
 
     // Dependencies
     pub fn add_dependency(&self, blocked_id: i64, blocker_id: i64) -> Result<bool> {
-        // Prevent self-blocking
+        self.add_dependency_of_kind(blocked_id, blocker_id, "blocks")
+    }
+
+    /// Add a dependency edge of the given kind (see `DEPENDENCY_KINDS`).
+    /// For "duplicates", `blocked_id` is the duplicate and `blocker_id` the original;
+    /// for "after", `blocked_id` should be done after `blocker_id`.
+    pub fn add_dependency_of_kind(
+        &self,
+        blocked_id: i64,
+        blocker_id: i64,
+        kind: &str,
+    ) -> Result<bool> {
+        if !DEPENDENCY_KINDS.contains(&kind) {
+            return Err(DbError::Invalid(format!(
+                "Invalid dependency kind '{}'. Must be one of: {}",
+                kind,
+                DEPENDENCY_KINDS.join(", ")
+            )));
+        }
+
+        // Prevent self-links
         if blocked_id == blocker_id {
-            anyhow::bail!("An issue cannot block itself");
+            let msg = match kind {
+                "blocks" => "An issue cannot block itself",
+                "after" => "An issue cannot come after itself",
+                _ => "An issue cannot duplicate itself",
+            };
+            return Err(DbError::Invalid(msg.to_string()));
         }
 
-        // Check for circular dependencies before inserting
-        if self.would_create_cycle(blocked_id, blocker_id)? {
-            anyhow::bail!("Adding this dependency would create a circular dependency chain");
+        // Check for circular dependencies before inserting; only "blocks" edges can deadlock
+        if kind == "blocks" {
+            if let Some(mut path) = self.blocking_path(blocked_id, blocker_id, false)? {
+                // Close the loop: blocker_id would now block blocked_id
+                path.push(blocked_id);
+                return Err(DbError::Cycle(path));
+            }
         }
 
+        let now = Utc::now().to_rfc3339();
         let result = self.conn.execute(
-            "INSERT OR IGNORE INTO dependencies (blocker_id, blocked_id) VALUES (?1, ?2)",
-            params![blocker_id, blocked_id],
+            "INSERT OR IGNORE INTO dependencies (blocker_id, blocked_id, kind, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![blocker_id, blocked_id, kind, now],
         )?;
+        self.refresh_blocked("id = ?1", &[&blocked_id])?;
         Ok(result > 0)
     }
 
-    /// Check if adding blocker_id -> blocked_id would create a cycle.
-    /// A cycle exists if blocked_id can already reach blocker_id through existing dependencies.
-    fn would_create_cycle(&self, blocked_id: i64, blocker_id: i64) -> Result<bool> {
-        // If blocked_id can reach blocker_id, then adding blocker_id -> blocked_id creates a cycle
+    fn is_open(&self, id: i64) -> Result<bool> {
+        Ok(self.get_issue(id)?.is_some_and(|i| i.status == "open"))
+    }
+
+    /// Find a chain of existing "blocks" edges from `from` to `to`.
+    /// Adding to -> from would create a cycle exactly when such a chain exists.
+    /// With `open_only`, every blocker on the chain must still be open.
+    fn blocking_path(&self, from: i64, to: i64, open_only: bool) -> Result<Option<Vec<i64>>> {
+        if open_only && !self.is_open(from)? {
+            return Ok(None);
+        }
+        // Depth-first search, remembering how each issue was reached
+        let mut came_from: std::collections::HashMap<i64, i64> = std::collections::HashMap::new();
         let mut visited = std::collections::HashSet::new();
-        let mut stack = vec![blocked_id];
+        let mut stack = vec![from];
 
         while let Some(current) = stack.pop() {
-            if current == blocker_id {
-                return Ok(true); // Found a path from blocked_id to blocker_id
+            if current == to {
+                let mut path = vec![current];
+                let mut node = current;
+                while let Some(&prev) = came_from.get(&node) {
+                    path.push(prev);
+                    node = prev;
+                }
+                path.reverse();
+                return Ok(Some(path));
             }
 
             if visited.insert(current) {
                 // Get all issues that 'current' blocks (issues where current is the blocker)
-                let blocking = self.get_blocking(current)?;
-                for next in blocking {
-                    if !visited.contains(&next) {
+                for next in self.get_blocking(current)? {
+                    if open_only && next != to && !self.is_open(next)? {
+                        continue;
+                    }
+                    if !visited.contains(&next) && next != from {
+                        came_from.entry(next).or_insert(current);
                         stack.push(next);
                     }
                 }
             }
         }
 
-        Ok(false)
+        Ok(None)
+    }
+
+    /// Chain of "blocks" edges by which `blocker_id` still holds up `issue_id`,
+    /// listed from the issue back to the blocker. Closed blockers hold nothing up,
+    /// so chains through them do not count.
+    pub fn transitive_blocker_path(
+        &self,
+        issue_id: i64,
+        blocker_id: i64,
+    ) -> Result<Option<Vec<i64>>> {
+        Ok(self
+            .blocking_path(blocker_id, issue_id, true)?
+            .map(|mut path| {
+                path.reverse();
+                path
+            }))
+    }
+
+    /// Ids of the open issues directly blocking `issue_id`, sorted.
+    pub fn open_blockers(&self, issue_id: i64) -> Result<Vec<i64>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT d.blocker_id FROM dependencies d
+            JOIN issues blocker ON blocker.id = d.blocker_id
+            WHERE d.blocked_id = ?1 AND d.kind = 'blocks' AND blocker.status = 'open'
+            ORDER BY d.blocker_id
+            "#,
+        )?;
+        let blockers = stmt
+            .query_map([issue_id], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+        Ok(blockers)
+    }
+
+    /// Whether closing `candidate` would make `target` ready: `target` is open and
+    /// `candidate` is its only open blocker. A candidate that blocks `target` only
+    /// transitively does not count, since the issues in between stay open.
+    pub fn would_unblock(&self, candidate: i64, target: i64) -> Result<bool> {
+        let target = self.require_issue(target)?;
+        Ok(target.status == "open" && self.open_blockers(target.id)? == [candidate])
     }
 
     pub fn remove_dependency(&self, blocked_id: i64, blocker_id: i64) -> Result<bool> {
         let rows = self.conn.execute(
-            "DELETE FROM dependencies WHERE blocker_id = ?1 AND blocked_id = ?2",
+            "DELETE FROM dependencies WHERE blocker_id = ?1 AND blocked_id = ?2 AND kind = 'blocks'",
             params![blocker_id, blocked_id],
         )?;
+        self.refresh_blocked("id = ?1", &[&blocked_id])?;
         Ok(rows > 0)
     }
 
+    /// Reverse the "blocks" edge between `a` and `b`, whichever way it points.
+    /// The new direction goes through the usual cycle check; on failure the
+    /// original edge is kept. Returns the new (blocked, blocker) pair.
+    pub fn swap_dependency(&self, a: i64, b: i64) -> Result<(i64, i64)> {
+        self.transaction(|| {
+            let (blocked, blocker) = if self.remove_dependency(a, b)? {
+                (b, a)
+            } else if self.remove_dependency(b, a)? {
+                (a, b)
+            } else {
+                return Err(DbError::Invalid(format!(
+                    "No dependency between #{} and #{}",
+                    a, b
+                )));
+            };
+            self.add_dependency(blocked, blocker)?;
+            Ok((blocked, blocker))
+        })
+    }
+
+    /// Remove every "blocks" edge holding up `issue_id`. Returns how many were removed.
+    pub fn clear_dependencies(&self, issue_id: i64) -> Result<usize> {
+        let rows = self.conn.execute(
+            "DELETE FROM dependencies WHERE blocked_id = ?1 AND kind = 'blocks'",
+            [issue_id],
+        )?;
+        self.refresh_blocked("id = ?1", &[&issue_id])?;
+        Ok(rows)
+    }
+
     pub fn get_blockers(&self, issue_id: i64) -> Result<Vec<i64>> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT blocker_id FROM dependencies WHERE blocked_id = ?1")?;
+        let mut stmt = self.conn.prepare(
+            "SELECT blocker_id FROM dependencies WHERE blocked_id = ?1 AND kind = 'blocks'",
+        )?;
         let blockers = stmt
             .query_map([issue_id], |row| row.get(0))?
             .collect::<std::result::Result<Vec<i64>, _>>()?;
         Ok(blockers)
     }
 
+    /// Which of `ids` are open issues with an open blocker, from the cached flag.
+    pub fn blocked_among(&self, ids: &[i64]) -> Result<std::collections::HashSet<i64>> {
+        let mut blocked = std::collections::HashSet::new();
+        for chunk in ids.chunks(ID_CHUNK_SIZE) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!(
+                "SELECT id FROM issues WHERE status = 'open' AND is_blocked = 1 AND id IN ({})",
+                placeholders
+            );
+            let mut stmt = self.conn.prepare(&sql)?;
+            for id in stmt.query_map(rusqlite::params_from_iter(chunk), |row| row.get(0))? {
+                blocked.insert(id?);
+            }
+        }
+        Ok(blocked)
+    }
+
+    /// Blockers of many issues at once, sorted by id. Every requested id is in
+    /// the map, with an empty list if nothing blocks it.
+    pub fn get_blockers_for(&self, ids: &[i64]) -> Result<HashMap<i64, Vec<i64>>> {
+        let mut blockers: HashMap<i64, Vec<i64>> = ids.iter().map(|&id| (id, Vec::new())).collect();
+        for chunk in ids.chunks(ID_CHUNK_SIZE) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!(
+                "SELECT blocked_id, blocker_id FROM dependencies WHERE kind = 'blocks' AND blocked_id IN ({}) ORDER BY blocked_id, blocker_id",
+                placeholders
+            );
+            let mut stmt = self.conn.prepare(&sql)?;
+            let rows = stmt.query_map(rusqlite::params_from_iter(chunk), |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+            })?;
+            for row in rows {
+                let (blocked, blocker) = row?;
+                blockers.entry(blocked).or_default().push(blocker);
+            }
+        }
+        Ok(blockers)
+    }
+
     pub fn get_blocking(&self, issue_id: i64) -> Result<Vec<i64>> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT blocked_id FROM dependencies WHERE blocker_id = ?1")?;
+        let mut stmt = self.conn.prepare(
+            "SELECT blocked_id FROM dependencies WHERE blocker_id = ?1 AND kind = 'blocks'",
+        )?;
         let blocking = stmt
             .query_map([issue_id], |row| row.get(0))?
             .collect::<std::result::Result<Vec<i64>, _>>()?;
         Ok(blocking)
     }
 
-    pub fn list_blocked_issues(&self) -> Result<Vec<Issue>> {
+    /// Every "blocks" edge as (blocked_id, blocker_id), ordered by blocked then blocker.
+    pub fn list_dependencies(&self) -> Result<Vec<(i64, i64)>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
-            FROM issues i
-            JOIN dependencies d ON i.id = d.blocked_id
-            JOIN issues blocker ON d.blocker_id = blocker.id
-            WHERE i.status = 'open' AND blocker.status = 'open'
-            ORDER BY i.id
+            SELECT blocked_id, blocker_id FROM dependencies
+            WHERE kind = 'blocks'
+            ORDER BY blocked_id, blocker_id
             "#,
         )?;
+        let edges = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<Vec<(i64, i64)>, _>>()?;
+        Ok(edges)
+    }
 
-        let issues = stmt
-            .query_map([], issue_from_row)?
+    /// Connected components of the blocking graph with edge direction ignored.
+    /// Issues without any blocking edge are left out. Each component is sorted
+    /// by id, and components are ordered by their lowest id.
+    pub fn dependency_components(&self) -> Result<Vec<Vec<i64>>> {
+        let mut neighbours: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+        for (blocked, blocker) in self.list_dependencies()? {
+            neighbours.entry(blocked).or_default().push(blocker);
+            neighbours.entry(blocker).or_default().push(blocked);
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        let mut components = Vec::new();
+        for &start in neighbours.keys() {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut component = vec![start];
+            let mut stack = vec![start];
+            while let Some(id) = stack.pop() {
+                for &next in &neighbours[&id] {
+                    if seen.insert(next) {
+                        component.push(next);
+                        stack.push(next);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        Ok(components)
+    }
+
+    /// Open issues grouped into scheduling tiers: tier 0 has no open blockers,
+    /// and every other issue sits one tier after its latest open blocker. Each
+    /// tier is sorted by id. Fails with `Cycle` if the open issues block each
+    /// other in a loop.
+    pub fn dependency_levels(&self) -> Result<Vec<Vec<i64>>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM issues WHERE status = 'open' ORDER BY id")?;
+        let open = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+
+        let mut blockers: BTreeMap<i64, Vec<i64>> =
+            open.iter().map(|&id| (id, Vec::new())).collect();
+        let mut dependents: HashMap<i64, Vec<i64>> = HashMap::new();
+        for (blocked, blocker) in self.list_dependencies()? {
+            if blockers.contains_key(&blocked) && blockers.contains_key(&blocker) {
+                blockers.get_mut(&blocked).unwrap().push(blocker);
+                dependents.entry(blocker).or_default().push(blocked);
+            }
+        }
+
+        // Peel off issues whose open blockers are all in earlier tiers
+        let mut waiting: HashMap<i64, usize> =
+            blockers.iter().map(|(&id, b)| (id, b.len())).collect();
+        let mut tier: Vec<i64> = open.iter().copied().filter(|id| waiting[id] == 0).collect();
+        let mut levels = Vec::new();
+        let mut placed = 0;
+        while !tier.is_empty() {
+            placed += tier.len();
+            let mut next = Vec::new();
+            for id in &tier {
+                for &dependent in dependents.get(id).into_iter().flatten() {
+                    let count = waiting.get_mut(&dependent).unwrap();
+                    *count -= 1;
+                    if *count == 0 {
+                        next.push(dependent);
+                    }
+                }
+            }
+            next.sort_unstable();
+            levels.push(std::mem::replace(&mut tier, next));
+        }
+
+        if placed < open.len() {
+            // Every unplaced issue waits on another unplaced one; follow blockers until one repeats
+            let mut path = vec![*blockers.keys().find(|id| waiting[*id] > 0).unwrap()];
+            loop {
+                let current = *path.last().unwrap();
+                let blocker = blockers[&current]
+                    .iter()
+                    .copied()
+                    .find(|b| waiting[b] > 0)
+                    .unwrap();
+                if let Some(start) = path.iter().position(|&id| id == blocker) {
+                    let mut cycle = path.split_off(start);
+                    cycle.push(blocker);
+                    cycle.reverse();
+                    return Err(DbError::Cycle(cycle));
+                }
+                path.push(blocker);
+            }
+        }
+        Ok(levels)
+    }
+
+    /// The `n` open issues with the most open issues waiting on them, directly
+    /// or through a chain of blockers. Ties go to the lower id; issues blocking
+    /// nothing are left out.
+    pub fn top_blockers(&self, n: usize) -> Result<Vec<(Issue, usize)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT d.blocker_id, d.blocked_id FROM dependencies d
+            JOIN issues a ON a.id = d.blocker_id
+            JOIN issues b ON b.id = d.blocked_id
+            WHERE d.kind = 'blocks' AND a.status = 'open' AND b.status = 'open'
+            "#,
+        )?;
+        let mut dependents: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+        for edge in stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))? {
+            let (blocker, blocked): (i64, i64) = edge?;
+            dependents.entry(blocker).or_default().push(blocked);
+        }
+
+        let mut counts: Vec<(i64, usize)> = Vec::new();
+        for &start in dependents.keys() {
+            let mut seen = std::collections::HashSet::new();
+            let mut stack = vec![start];
+            while let Some(id) = stack.pop() {
+                for &next in dependents.get(&id).into_iter().flatten() {
+                    if next != start && seen.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+            counts.push((start, seen.len()));
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts.truncate(n);
+
+        let ids: Vec<i64> = counts.iter().map(|&(id, _)| id).collect();
+        let mut issues = self.get_issues(&ids)?;
+        Ok(counts
+            .into_iter()
+            .filter_map(|(id, count)| issues.remove(&id).map(|issue| (issue, count)))
+            .collect())
+    }
+
+    /// Issues that this issue is marked as a duplicate of.
+    pub fn get_duplicate_of(&self, issue_id: i64) -> Result<Vec<i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT blocker_id FROM dependencies WHERE blocked_id = ?1 AND kind = 'duplicates' ORDER BY blocker_id",
+        )?;
+        let originals = stmt
+            .query_map([issue_id], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+        Ok(originals)
+    }
+
+    /// Issues marked as duplicates of this issue.
+    pub fn get_duplicated_by(&self, issue_id: i64) -> Result<Vec<i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT blocked_id FROM dependencies WHERE blocker_id = ?1 AND kind = 'duplicates' ORDER BY blocked_id",
+        )?;
+        let duplicates = stmt
+            .query_map([issue_id], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+        Ok(duplicates)
+    }
+
+    /// Every dependency edge as (blocked_id, blocker_id, kind), ordered by blocked, blocker, then kind.
+    pub fn list_dependencies_of_all_kinds(&self) -> Result<Vec<(i64, i64, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT blocked_id, blocker_id, kind FROM dependencies ORDER BY blocked_id, blocker_id, kind",
+        )?;
+        let edges = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
             .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(edges)
+    }
 
-        Ok(issues)
+    /// Count open issues directly blocked by this issue.
+    pub fn count_dependents(&self, issue_id: i64) -> Result<i64> {
+        let count: i64 = self.conn.query_row(
+            r#"
+            SELECT COUNT(*) FROM dependencies d
+            JOIN issues i ON i.id = d.blocked_id
+            WHERE d.blocker_id = ?1 AND d.kind = 'blocks' AND i.status = 'open'
+            "#,
+            [issue_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
     }
 
-    pub fn list_ready_issues(&self) -> Result<Vec<Issue>> {
+    /// Blocker and dependent counts for every issue with at least one blocking
+    /// edge, keyed by id as `(blockers, dependents)`. One aggregate query.
+    pub fn dependency_counts(&self) -> Result<HashMap<i64, (i64, i64)>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
-            FROM issues i
-            WHERE i.status = 'open'
-            AND NOT EXISTS (
-                SELECT 1 FROM dependencies d
-                JOIN issues blocker ON d.blocker_id = blocker.id
-                WHERE d.blocked_id = i.id AND blocker.status = 'open'
+            SELECT id, SUM(blockers), SUM(dependents) FROM (
+                SELECT blocked_id AS id, 1 AS blockers, 0 AS dependents
+                FROM dependencies WHERE kind = 'blocks'
+                UNION ALL
+                SELECT blocker_id, 0, 1 FROM dependencies WHERE kind = 'blocks'
             )
-            ORDER BY i.id
+            GROUP BY id
             "#,
         )?;
+        let counts = stmt
+            .query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?
+            .collect::<std::result::Result<HashMap<_, _>, _>>()?;
+        Ok(counts)
+    }
 
-        let issues = stmt
-            .query_map([], issue_from_row)?
+    /// Group open issues whose titles match after trimming, lowercasing and collapsing
+    /// whitespace. Only groups with more than one member are returned, ordered by lowest id.
+    pub fn find_duplicates(&self) -> Result<Vec<Vec<i64>>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, title FROM issues WHERE status = 'open' ORDER BY id")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut groups: Vec<(String, Vec<i64>)> = Vec::new();
+        let mut index: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for (id, title) in rows {
+            let key = title_key(&title);
+            match index.get(&key) {
+                Some(&i) => groups[i].1.push(id),
+                None => {
+                    index.insert(key.clone(), groups.len());
+                    groups.push((key, vec![id]));
+                }
+            }
+        }
+
+        Ok(groups
+            .into_iter()
+            .map(|(_, ids)| ids)
+            .filter(|ids| ids.len() > 1)
+            .collect())
+    }
+
+    /// Find the oldest open issue under `parent` (top-level when None) whose
+    /// title matches `title` after collapsing whitespace and ignoring case.
+    pub fn find_issue_by_title(&self, title: &str, parent: Option<i64>) -> Result<Option<i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title FROM issues WHERE status = 'open' AND parent_id IS ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map([parent], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let key = title_key(title);
+        for row in rows {
+            let (id, existing) = row?;
+            if title_key(&existing) == key {
+                return Ok(Some(id));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Count open issues this one should come after ("after" edges).
+    pub fn count_open_predecessors(&self, issue_id: i64) -> Result<i64> {
+        let count: i64 = self.conn.query_row(
+            r#"
+            SELECT COUNT(*) FROM dependencies d
+            JOIN issues i ON i.id = d.blocker_id
+            WHERE d.blocked_id = ?1 AND d.kind = 'after' AND i.status = 'open'
+            "#,
+            [issue_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Find dependency rows where the blocked or blocking issue no longer exists.
+    /// Returns (blocked_id, blocker_id) pairs, matching the argument order of add_dependency.
+    pub fn find_dangling_dependencies(&self) -> Result<Vec<(i64, i64)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT d.blocked_id, d.blocker_id
+            FROM dependencies d
+            WHERE NOT EXISTS (SELECT 1 FROM issues WHERE id = d.blocked_id)
+               OR NOT EXISTS (SELECT 1 FROM issues WHERE id = d.blocker_id)
+            ORDER BY d.blocked_id, d.blocker_id
+            "#,
+        )?;
+        let edges = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<Vec<(i64, i64)>, _>>()?;
+        Ok(edges)
+    }
+
+    /// Ids in `1..=max` with no issue, in ascending order.
+    pub fn missing_ids(&self, max: i64) -> Result<Vec<i64>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM issues WHERE id BETWEEN 1 AND ?1 ORDER BY id")?;
+        let present = stmt
+            .query_map([max], |row| row.get::<_, i64>(0))?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
+        let mut missing = Vec::new();
+        let mut next = 1;
+        for id in present.into_iter().chain(std::iter::once(max + 1)) {
+            missing.extend(next..id);
+            next = id + 1;
+        }
+        Ok(missing)
+    }
+
+    pub fn info(&self) -> Result<DbInfo> {
+        let schema_version = self
+            .conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        let journal_mode = self
+            .conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))?;
+        let issue_count = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM issues", [], |row| row.get(0))?;
+        Ok(DbInfo {
+            sqlite_version: rusqlite::version(),
+            schema_version,
+            journal_mode,
+            issue_count,
+        })
+    }
+
+    /// The highest issue id ever handed out, including deleted ones (ids are never reused).
+    pub fn last_issue_id(&self) -> Result<i64> {
+        let seq = self.conn.query_row(
+            "SELECT COALESCE(MAX(seq), 0) FROM sqlite_sequence WHERE name = 'issues'",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(seq)
+    }
+
+    /// Delete all dangling dependency rows. Returns the number of rows removed.
+    pub fn delete_dangling_dependencies(&self) -> Result<usize> {
+        let rows = self.conn.execute(
+            r#"
+            DELETE FROM dependencies
+            WHERE NOT EXISTS (SELECT 1 FROM issues WHERE id = dependencies.blocked_id)
+               OR NOT EXISTS (SELECT 1 FROM issues WHERE id = dependencies.blocker_id)
+            "#,
+            [],
+        )?;
+        Ok(rows)
+    }
+
+    /// Open issues with at least one open blocker, each with all of its blocker ids.
+    /// Ordered by priority (highest first), then id.
+    pub fn list_blocked_with_meta(&self) -> Result<Vec<IssueWithBlockers>> {
+        let mut issues = self.query_with_blockers("i.is_blocked = 1", &[])?;
+
+        self.priority_scale()?
+            .sort_most_urgent_first(&mut issues, |m| &m.issue.priority);
+        Ok(issues)
+    }
+
+    /// List open issues with no open blockers, each with its (closed) blocker ids.
+    /// With `exclude_closed_parents`, subissues whose parent is no longer open are left out.
+    /// Ordered by priority (highest first), then id.
+    pub fn list_ready_with_meta(
+        &self,
+        exclude_closed_parents: bool,
+    ) -> Result<Vec<IssueWithBlockers>> {
+        self.ready_with_meta(exclude_closed_parents, None)
+    }
+
+    /// `list_ready_with_meta` narrowed to one assignee, or `UNASSIGNED` for
+    /// ready issues nobody owns.
+    pub fn list_ready_for_assignee(
+        &self,
+        exclude_closed_parents: bool,
+        assignee: &str,
+    ) -> Result<Vec<IssueWithBlockers>> {
+        self.ready_with_meta(exclude_closed_parents, Some(assignee))
+    }
+
+    fn ready_with_meta(
+        &self,
+        exclude_closed_parents: bool,
+        assignee: Option<&str>,
+    ) -> Result<Vec<IssueWithBlockers>> {
+        let mut issues = self.query_with_blockers(
+            r#"
+            i.is_blocked = 0
+            AND (
+                ?1 = 0
+                OR i.parent_id IS NULL
+                OR EXISTS (SELECT 1 FROM issues p WHERE p.id = i.parent_id AND p.status = 'open')
+            )
+            AND (
+                ?2 IS NULL
+                OR (?2 = ?3 AND COALESCE(i.assignee, '') = '')
+                OR (?2 != ?3 AND i.assignee = ?2)
+            )
+            "#,
+            &[&exclude_closed_parents, &assignee, &UNASSIGNED],
+        )?;
+
+        self.priority_scale()?
+            .sort_most_urgent_first(&mut issues, |m| &m.issue.priority);
         Ok(issues)
     }
 
+    /// List open issues with no open blockers, highest priority first.
+    /// With `exclude_closed_parents`, subissues whose parent is no longer open are left out.
+    pub fn list_ready_issues(&self, exclude_closed_parents: bool) -> Result<Vec<Issue>> {
+        Ok(self
+            .list_ready_with_meta(exclude_closed_parents)?
+            .into_iter()
+            .map(|m| m.issue)
+            .collect())
+    }
+
+    /// Open issues matching `condition` (a fixed SQL fragment over `i` using `params`),
+    /// joined with their "blocks" blocker ids in a single query. Ordered by id.
+    fn query_with_blockers(
+        &self,
+        condition: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<IssueWithBlockers>> {
+        let sql = format!(
+            r#"
+            SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at,
+                   GROUP_CONCAT(b.blocker_id)
+            FROM issues i
+            LEFT JOIN dependencies b ON b.blocked_id = i.id AND b.kind = 'blocks'
+            WHERE i.status = 'open' AND {}
+            GROUP BY i.id
+            ORDER BY i.id
+            "#,
+            condition
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map(params, |row| {
+                let issue = issue_from_row(row)?;
+                let blockers: Option<String> = row.get(9)?;
+                Ok((issue, blockers))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(issue, blockers)| {
+                let mut blockers: Vec<i64> = blockers
+                    .as_deref()
+                    .unwrap_or("")
+                    .split(',')
+                    .filter_map(|b| b.parse().ok())
+                    .collect();
+                blockers.sort_unstable();
+                IssueWithBlockers { issue, blockers }
+            })
+            .collect())
+    }
+
     // Sessions
     pub fn start_session(&self) -> Result<i64> {
         let now = Utc::now().to_rfc3339();
@@ -690,34 +2072,77 @@ impl Database {
         Ok(total)
     }
 
-    /// Search issues by query string across titles, descriptions, and comments
-    pub fn search_issues(&self, query: &str) -> Result<Vec<Issue>> {
-        // Escape SQL LIKE wildcards to prevent unintended pattern matching
-        let escaped = query.replace('%', "\\%").replace('_', "\\_");
-        let pattern = format!("%{}%", escaped);
+    /// Search issues by query string across titles, descriptions, and comments.
+    /// In prefix mode comments are skipped and the query must start a word.
+    ///
+    /// Results are ranked title matches first, then description matches, then
+    /// comment-only matches, newest first within each group. `opts.limit` is
+    /// applied after ranking.
+    pub fn search_issues(&self, query: &str, opts: &SearchOptions) -> Result<Vec<Issue>> {
+        let pattern = like_pattern(query);
+        // The substring LIKE narrows candidates; prefix mode then checks word boundaries
         let mut stmt = self.conn.prepare(
             r#"
+            WITH RECURSIVE subtree(id) AS (
+                SELECT id FROM issues WHERE parent_id = ?2
+                UNION
+                SELECT i.id FROM issues i JOIN subtree s ON i.parent_id = s.id
+            )
             SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
             FROM issues i
             LEFT JOIN comments c ON i.id = c.issue_id
-            WHERE i.title LIKE ?1 ESCAPE '\' COLLATE NOCASE
-               OR i.description LIKE ?1 ESCAPE '\' COLLATE NOCASE
-               OR c.content LIKE ?1 ESCAPE '\' COLLATE NOCASE
+            WHERE (?2 IS NULL OR i.id IN (SELECT id FROM subtree))
+              AND (i.title LIKE ?1 ESCAPE '\' COLLATE NOCASE
+                OR i.description LIKE ?1 ESCAPE '\' COLLATE NOCASE
+                OR (?3 = 0 AND c.content LIKE ?1 ESCAPE '\' COLLATE NOCASE))
             ORDER BY i.id DESC
             "#,
         )?;
 
         let issues = stmt
-            .query_map([&pattern], issue_from_row)?
-            .collect::<std::result::Result<Vec<_>, _>>()?;
+            .query_map(params![pattern, opts.under, opts.prefix], issue_from_row)?
+            .collect::<std::result::Result<Vec<Issue>, _>>()?;
 
-        Ok(issues)
+        let matches = |text: &str| {
+            if opts.prefix {
+                starts_word(text, query)
+            } else {
+                text.to_ascii_lowercase()
+                    .contains(&query.to_ascii_lowercase())
+            }
+        };
+
+        // 0 = title, 1 = description, 2 = comments only (never in prefix mode)
+        let mut ranked: Vec<(u8, Issue)> = issues
+            .into_iter()
+            .map(|i| {
+                let rank = if matches(&i.title) {
+                    0
+                } else if i.description.as_deref().is_some_and(matches) {
+                    1
+                } else {
+                    2
+                };
+                (rank, i)
+            })
+            .filter(|(rank, _)| !opts.prefix || *rank < 2)
+            .collect();
+        // Stable, so ids stay newest first within a rank
+        ranked.sort_by_key(|(rank, _)| *rank);
+
+        Ok(ranked
+            .into_iter()
+            .map(|(_, i)| i)
+            .take(opts.limit.unwrap_or(usize::MAX))
+            .collect())
     }
 
     // Relations (bidirectional)
     pub fn add_relation(&self, issue_id_1: i64, issue_id_2: i64) -> Result<bool> {
         if issue_id_1 == issue_id_2 {
-            anyhow::bail!("Cannot relate an issue to itself");
+            return Err(DbError::Invalid(
+                "Cannot relate an issue to itself".to_string(),
+            ));
         }
         // Store with smaller ID first for consistency
         let (a, b) = if issue_id_1 < issue_id_2 {
@@ -747,7 +2172,23 @@ impl Database {
     }
 
     pub fn update_parent(&self, id: i64, parent_id: Option<i64>) -> Result<bool> {
+        if parent_id == Some(id) {
+            return Err(DbError::Invalid(
+                "An issue cannot be its own parent".to_string(),
+            ));
+        }
+        if let Some(parent_id) = parent_id {
+            self.check_depth(parent_id, self.height_of(id)?)?;
+        }
         let now = chrono::Utc::now().to_rfc3339();
+        self.conn.execute(
+            r#"
+            INSERT INTO issue_events (issue_id, kind, old_value, new_value, created_at)
+            SELECT id, 'parent', parent_id, ?2, ?3 FROM issues
+            WHERE id = ?1 AND parent_id IS NOT ?2
+            "#,
+            params![id, parent_id, now],
+        )?;
         let rows = self.conn.execute(
             "UPDATE issues SET parent_id = ?1, updated_at = ?2 WHERE id = ?3",
             params![parent_id, now, id],
@@ -755,45 +2196,486 @@ impl Database {
         Ok(rows > 0)
     }
 
-    pub fn get_related_issues(&self, issue_id: i64) -> Result<Vec<Issue>> {
-        let mut stmt = self.conn.prepare(
-            r#"
-            SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
-            FROM issues i
-            WHERE i.id IN (
-                SELECT issue_id_2 FROM relations WHERE issue_id_1 = ?1
-                UNION
-                SELECT issue_id_1 FROM relations WHERE issue_id_2 = ?1
-            )
-            ORDER BY i.id
-            "#,
+    // Assignees
+    /// Set or clear the assignee. A blank name is stored as no assignee.
+    pub fn set_assignee(&self, id: i64, assignee: Option<&str>) -> Result<bool> {
+        let assignee = assignee.map(str::trim).filter(|a| !a.is_empty());
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE issues SET assignee = ?1, updated_at = ?2 WHERE id = ?3",
+            params![assignee, now, id],
         )?;
+        Ok(rows > 0)
+    }
 
-        let issues = stmt
-            .query_map([issue_id], issue_from_row)?
-            .collect::<std::result::Result<Vec<_>, _>>()?;
-
-        Ok(issues)
+    pub fn get_assignee(&self, id: i64) -> Result<Option<String>> {
+        let assignee: Option<Option<String>> = self
+            .conn
+            .query_row("SELECT assignee FROM issues WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .ok();
+        Ok(assignee.flatten().filter(|a| !a.is_empty()))
     }
 
-    // Milestones
-    pub fn create_milestone(&self, name: &str, description: Option<&str>) -> Result<i64> {
+    /// Give an issue a unique slug, replacing any it had.
+    pub fn set_slug(&self, id: i64, slug: &str) -> Result<bool> {
+        let slug = slug.trim();
+        check_slug(slug).map_err(DbError::Invalid)?;
+        if let Some(owner) = self.slug_owner(slug).filter(|&owner| owner != id) {
+            return Err(DbError::Invalid(format!(
+                "Slug '{}' is already used by issue #{}",
+                slug, owner
+            )));
+        }
         let now = Utc::now().to_rfc3339();
-        self.conn.execute(
-            "INSERT INTO milestones (name, description, status, created_at) VALUES (?1, ?2, 'open', ?3)",
-            params![name, description, now],
+        let rows = self.conn.execute(
+            "UPDATE issues SET slug = ?1, updated_at = ?2 WHERE id = ?3",
+            params![slug, now, id],
         )?;
-        Ok(self.conn.last_insert_rowid())
+        Ok(rows > 0)
     }
 
-    pub fn get_milestone(&self, id: i64) -> Result<Option<crate::models::Milestone>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, name, description, status, created_at, closed_at FROM milestones WHERE id = ?1",
+    pub fn clear_slug(&self, id: i64) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE issues SET slug = NULL, updated_at = ?1 WHERE id = ?2",
+            params![now, id],
         )?;
+        Ok(rows > 0)
+    }
 
-        let milestone = stmt
-            .query_row([id], |row| {
-                Ok(crate::models::Milestone {
+    pub fn get_slug(&self, id: i64) -> Result<Option<String>> {
+        let slug: Option<Option<String>> = self
+            .conn
+            .query_row("SELECT slug FROM issues WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .ok();
+        Ok(slug.flatten())
+    }
+
+    /// Set or clear the external link of an issue. Only http and https URLs
+    /// are accepted.
+    pub fn set_url(&self, id: i64, url: Option<&str>) -> Result<bool> {
+        let url = url.map(str::trim);
+        if let Some(url) = url {
+            check_url(url)?;
+        }
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE issues SET url = ?1, updated_at = ?2 WHERE id = ?3",
+            params![url, now, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    pub fn get_url(&self, id: i64) -> Result<Option<String>> {
+        let url: Option<Option<String>> = self
+            .conn
+            .query_row("SELECT url FROM issues WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .ok();
+        Ok(url.flatten())
+    }
+
+    // Estimates
+    pub fn set_estimate(&self, id: i64, estimate: Option<i64>) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE issues SET estimate = ?1, updated_at = ?2 WHERE id = ?3",
+            params![estimate, now, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    pub fn get_estimate(&self, id: i64) -> Result<Option<i64>> {
+        let estimate: Option<Option<i64>> = self
+            .conn
+            .query_row("SELECT estimate FROM issues WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .ok();
+        Ok(estimate.flatten())
+    }
+
+    /// Sum the estimates of an issue and all its descendants. Missing estimates count as 0.
+    pub fn subtree_estimate(&self, id: i64) -> Result<i64> {
+        let total: i64 = self.conn.query_row(
+            r#"
+            WITH RECURSIVE subtree(id) AS (
+                SELECT ?1
+                UNION
+                SELECT i.id FROM issues i JOIN subtree s ON i.parent_id = s.id
+            )
+            SELECT COALESCE(SUM(i.estimate), 0) FROM issues i
+            WHERE i.id IN (SELECT id FROM subtree)
+            "#,
+            [id],
+            |row| row.get(0),
+        )?;
+        Ok(total)
+    }
+
+    // Due dates
+    pub fn set_due_date(&self, id: i64, due: Option<NaiveDate>) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let due = due.map(|d| d.format("%Y-%m-%d").to_string());
+        let rows = self.conn.execute(
+            "UPDATE issues SET due_date = ?1, updated_at = ?2 WHERE id = ?3",
+            params![due, now, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Record how a closed issue was resolved. Reopening clears it.
+    pub fn set_resolution(&self, id: i64, note: &str) -> Result<bool> {
+        let note = note.trim();
+        if note.is_empty() {
+            return Err(DbError::Invalid(
+                "Resolution note cannot be empty".to_string(),
+            ));
+        }
+        let note = self.clean_description(note)?;
+        let rows = self.conn.execute(
+            "UPDATE issues SET resolution = ?1 WHERE id = ?2",
+            params![note, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    pub fn get_resolution(&self, id: i64) -> Result<Option<String>> {
+        let note: Option<Option<String>> = self
+            .conn
+            .query_row("SELECT resolution FROM issues WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .ok();
+        Ok(note.flatten())
+    }
+
+    pub fn get_due_date(&self, id: i64) -> Result<Option<NaiveDate>> {
+        let due: Option<Option<String>> = self
+            .conn
+            .query_row("SELECT due_date FROM issues WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .ok();
+        Ok(due
+            .flatten()
+            .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()))
+    }
+
+    /// Score a ready issue for recommendation as of `now`. See `ReadyScore` for the formula.
+    pub fn score_ready(&self, issue: &Issue, now: DateTime<Utc>) -> Result<ReadyScore> {
+        let scale = self.priority_scale()?;
+        self.score_with_scale(&scale, issue, now)
+    }
+
+    fn score_with_scale(
+        &self,
+        scale: &PriorityScale,
+        issue: &Issue,
+        now: DateTime<Utc>,
+    ) -> Result<ReadyScore> {
+        let due = match self.get_due_date(issue.id)? {
+            Some(date) => {
+                let days_left = (date - now.date_naive()).num_days();
+                if days_left <= 0 {
+                    20.0
+                } else {
+                    20.0 / (1.0 + days_left as f64)
+                }
+            }
+            None => 0.0,
+        };
+
+        let subissues = self.get_subissues(issue.id)?;
+        let closed = subissues.iter().filter(|s| s.status == "closed").count();
+        let progress = if closed > 0 && closed < subissues.len() {
+            5.0
+        } else {
+            0.0
+        };
+
+        let effort = match self.get_estimate(issue.id)? {
+            Some(estimate) if estimate >= 0 => 1.0 / (1.0 + estimate as f64),
+            _ => 0.0,
+        };
+
+        Ok(ReadyScore {
+            priority: 10.0 * scale.rank(&issue.priority) as f64,
+            due,
+            unblocks: 5.0 * self.count_dependents(issue.id)? as f64,
+            progress,
+            effort,
+            order: -5.0 * self.count_open_predecessors(issue.id)? as f64,
+        })
+    }
+
+    /// Ready issues with their `score_ready` totals, best first. Equal scores
+    /// keep id order.
+    pub fn rank_ready(&self, now: DateTime<Utc>) -> Result<Vec<(Issue, f64)>> {
+        let scale = self.priority_scale()?;
+        let mut ranked = Vec::new();
+        for issue in self.list_ready_issues(false)? {
+            let score = self.score_with_scale(&scale, &issue, now)?.total();
+            ranked.push((issue, score));
+        }
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(ranked)
+    }
+
+    pub fn get_related_issues(&self, issue_id: i64) -> Result<Vec<Issue>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
+            FROM issues i
+            WHERE i.id IN (
+                SELECT issue_id_2 FROM relations WHERE issue_id_1 = ?1
+                UNION
+                SELECT issue_id_1 FROM relations WHERE issue_id_2 = ?1
+            )
+            ORDER BY i.id
+            "#,
+        )?;
+
+        let issues = stmt
+            .query_map([issue_id], issue_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(issues)
+    }
+
+    // Priorities
+    /// The configured priority levels, least to most urgent. Databases that
+    /// never configured any get `VALID_PRIORITIES`.
+    pub fn priority_scale(&self) -> Result<PriorityScale> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name FROM priorities ORDER BY rank")?;
+        let levels = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<String>, _>>()?;
+
+        if levels.is_empty() {
+            Ok(PriorityScale::default())
+        } else {
+            Ok(PriorityScale::new(levels))
+        }
+    }
+
+    /// Fail with `InvalidPriority` unless `priority` is a configured level.
+    pub fn check_priority(&self, priority: &str) -> Result<()> {
+        let scale = self.priority_scale()?;
+        if scale.contains(priority) {
+            Ok(())
+        } else {
+            Err(DbError::InvalidPriority {
+                priority: priority.to_string(),
+                valid: scale.levels().to_vec(),
+            })
+        }
+    }
+
+    /// Replace the configured priority levels, given least to most urgent.
+    /// Levels must be non-blank and distinct, and every priority still used by
+    /// an issue or template must remain in the set.
+    pub fn set_priorities(&self, levels: &[String]) -> Result<()> {
+        if levels.is_empty() {
+            return Err(DbError::Invalid(
+                "At least one priority level is required".to_string(),
+            ));
+        }
+        for (i, level) in levels.iter().enumerate() {
+            if level.trim().is_empty() {
+                return Err(DbError::Invalid(
+                    "Priority levels cannot be blank".to_string(),
+                ));
+            }
+            if levels[..i].contains(level) {
+                return Err(DbError::Invalid(format!(
+                    "Priority '{}' is listed more than once",
+                    level
+                )));
+            }
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT priority FROM issues UNION SELECT priority FROM templates ORDER BY 1",
+        )?;
+        let in_use = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if let Some(missing) = in_use.iter().find(|p| !levels.contains(p)) {
+            return Err(DbError::Invalid(format!(
+                "Priority '{}' is still in use; change those issues first",
+                missing
+            )));
+        }
+
+        self.transaction(|| {
+            self.conn.execute("DELETE FROM priorities", [])?;
+            for (rank, level) in levels.iter().enumerate() {
+                self.conn.execute(
+                    "INSERT INTO priorities (name, rank) VALUES (?1, ?2)",
+                    params![level, rank as i64],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    // Templates
+    /// Save a template, replacing any existing template with the same name.
+    pub fn save_template(
+        &self,
+        name: &str,
+        title: &str,
+        description: Option<&str>,
+        priority: &str,
+        subissue_titles: &[String],
+    ) -> Result<()> {
+        self.check_priority(priority)?;
+        self.transaction(|| {
+            self.conn
+                .execute("DELETE FROM templates WHERE name = ?1", [name])?;
+            self.conn.execute(
+                "INSERT INTO templates (name, title, description, priority) VALUES (?1, ?2, ?3, ?4)",
+                params![name, title, description, priority],
+            )?;
+            for (position, sub_title) in subissue_titles.iter().enumerate() {
+                self.conn.execute(
+                    "INSERT INTO template_subissues (template_name, position, title) VALUES (?1, ?2, ?3)",
+                    params![name, position as i64, sub_title],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Save an issue as a template: its title, description and priority, plus the
+    /// titles of its immediate subissues. Replaces any template with the same name.
+    pub fn template_from_issue(&self, id: i64, name: &str) -> Result<()> {
+        let issue = self.require_issue(id)?;
+        let subissue_titles: Vec<String> = self
+            .get_subissues(id)?
+            .into_iter()
+            .map(|sub| sub.title)
+            .collect();
+        self.save_template(
+            name,
+            &issue.title,
+            issue.description.as_deref(),
+            &issue.priority,
+            &subissue_titles,
+        )
+    }
+
+    pub fn get_template(&self, name: &str) -> Result<Option<IssueTemplate>> {
+        let template = self
+            .conn
+            .query_row(
+                "SELECT name, title, description, priority FROM templates WHERE name = ?1",
+                [name],
+                |row| {
+                    Ok(IssueTemplate {
+                        name: row.get(0)?,
+                        title: row.get(1)?,
+                        description: row.get(2)?,
+                        priority: row.get(3)?,
+                        subissues: Vec::new(),
+                    })
+                },
+            )
+            .ok();
+
+        match template {
+            Some(mut t) => {
+                t.subissues = self.get_template_subissues(&t.name)?;
+                Ok(Some(t))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn get_template_subissues(&self, name: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT title FROM template_subissues WHERE template_name = ?1 ORDER BY position",
+        )?;
+        let titles = stmt
+            .query_map([name], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<String>, _>>()?;
+        Ok(titles)
+    }
+
+    pub fn list_templates(&self) -> Result<Vec<IssueTemplate>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, title, description, priority FROM templates ORDER BY name")?;
+        let mut templates = stmt
+            .query_map([], |row| {
+                Ok(IssueTemplate {
+                    name: row.get(0)?,
+                    title: row.get(1)?,
+                    description: row.get(2)?,
+                    priority: row.get(3)?,
+                    subissues: Vec::new(),
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        for t in &mut templates {
+            t.subissues = self.get_template_subissues(&t.name)?;
+        }
+        Ok(templates)
+    }
+
+    pub fn delete_template(&self, name: &str) -> Result<bool> {
+        let rows = self
+            .conn
+            .execute("DELETE FROM templates WHERE name = ?1", [name])?;
+        Ok(rows > 0)
+    }
+
+    /// Create the template's parent issue and its subissues. Returns the new parent id.
+    pub fn instantiate_template(&self, name: &str) -> Result<i64> {
+        let template = self
+            .get_template(name)?
+            .ok_or_else(|| DbError::TemplateNotFound(name.to_string()))?;
+
+        self.transaction(|| {
+            let parent = self.create_issue(
+                &template.title,
+                template.description.as_deref(),
+                &template.priority,
+            )?;
+            for sub_title in &template.subissues {
+                self.create_subissue(parent, sub_title, None, &template.priority)?;
+            }
+            Ok(parent)
+        })
+    }
+
+    // Milestones
+    pub fn create_milestone(&self, name: &str, description: Option<&str>) -> Result<i64> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO milestones (name, description, status, created_at) VALUES (?1, ?2, 'open', ?3)",
+            params![name, description, now],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    pub fn get_milestone(&self, id: i64) -> Result<Option<crate::models::Milestone>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, description, status, created_at, closed_at FROM milestones WHERE id = ?1",
+        )?;
+
+        let milestone = stmt
+            .query_row([id], |row| {
+                Ok(crate::models::Milestone {
                     id: row.get(0)?,
                     name: row.get(1)?,
                     description: row.get(2)?,
@@ -919,6 +2801,7 @@ impl Database {
     // Archiving
     pub fn archive_issue(&self, id: i64) -> Result<bool> {
         let now = Utc::now().to_rfc3339();
+        self.record_status_changes("archived", "id = ?1 AND status = 'closed'", id, &now)?;
         let rows = self.conn.execute(
             "UPDATE issues SET status = 'archived', updated_at = ?1 WHERE id = ?2 AND status = 'closed'",
             params![now, id],
@@ -928,6 +2811,7 @@ impl Database {
 
     pub fn unarchive_issue(&self, id: i64) -> Result<bool> {
         let now = Utc::now().to_rfc3339();
+        self.record_status_changes("closed", "id = ?1 AND status = 'archived'", id, &now)?;
         let rows = self.conn.execute(
             "UPDATE issues SET status = 'closed', updated_at = ?1 WHERE id = ?2 AND status = 'archived'",
             params![now, id],
@@ -947,11 +2831,96 @@ impl Database {
         Ok(issues)
     }
 
+    /// Open issues not updated in the `older_than_days` days before `now` (RFC 3339), oldest first.
+    pub fn list_stale(&self, older_than_days: i64, now: &str) -> Result<Vec<Issue>> {
+        let cutoff = (parse_timestamp(now)? - chrono::Duration::days(older_than_days)).to_rfc3339();
+
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at
+            FROM issues
+            WHERE status = 'open' AND updated_at < ?1
+            ORDER BY updated_at, id
+            "#,
+        )?;
+        let issues = stmt
+            .query_map([cutoff], issue_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(issues)
+    }
+
+    /// Open top-level issues with no blocking edges in either direction, oldest first.
+    pub fn list_isolated(&self) -> Result<Vec<Issue>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at
+            FROM issues i
+            WHERE status = 'open' AND parent_id IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM dependencies d
+                  WHERE d.kind = 'blocks' AND i.id IN (d.blocked_id, d.blocker_id)
+              )
+            ORDER BY id
+            "#,
+        )?;
+        let issues = stmt
+            .query_map([], issue_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(issues)
+    }
+
+    /// Issues with no subissues, i.e. the concrete tasks at the bottom of the hierarchy.
+    /// `status_filter` works as in `list_issues`. Ordered by id.
+    pub fn list_leaves(&self, status_filter: Option<&str>) -> Result<Vec<Issue>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT parent_id FROM issues WHERE parent_id IS NOT NULL")?;
+        let parents = stmt
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<std::result::Result<std::collections::HashSet<_>, _>>()?;
+
+        let mut leaves: Vec<Issue> = self
+            .list_issues(status_filter, None, None)?
+            .into_iter()
+            .filter(|i| !parents.contains(&i.id))
+            .collect();
+        leaves.sort_by_key(|i| i.id);
+        Ok(leaves)
+    }
+
+    /// Open issues still blocked by an open issue through a "blocks" edge added before `since`.
+    pub fn list_long_blocked(&self, since: &str) -> Result<Vec<Issue>> {
+        let cutoff = parse_timestamp(since)?.to_rfc3339();
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
+            FROM issues i
+            JOIN dependencies d ON i.id = d.blocked_id
+            JOIN issues blocker ON d.blocker_id = blocker.id
+            WHERE i.status = 'open' AND blocker.status = 'open'
+              AND d.kind = 'blocks' AND d.created_at < ?1
+            ORDER BY i.id
+            "#,
+        )?;
+        let issues = stmt
+            .query_map([cutoff], issue_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(issues)
+    }
+
     pub fn archive_older_than(&self, days: i64) -> Result<i32> {
         let cutoff = Utc::now() - chrono::Duration::days(days);
         let cutoff_str = cutoff.to_rfc3339();
         let now = Utc::now().to_rfc3339();
 
+        self.conn.execute(
+            r#"
+            INSERT INTO issue_events (issue_id, kind, old_value, new_value, created_at)
+            SELECT id, 'status', status, 'archived', ?1 FROM issues
+            WHERE status = 'closed' AND closed_at < ?2
+            "#,
+            params![now, cutoff_str],
+        )?;
         let rows = self.conn.execute(
             "UPDATE issues SET status = 'archived', updated_at = ?1 WHERE status = 'closed' AND closed_at < ?2",
             params![now, cutoff_str],
@@ -961,12 +2930,113 @@ impl Database {
     }
 }
 
+/// Make sure `path` can hold a writable database file before SQLite sees it,
+/// whose own errors for these cases are cryptic.
+fn prepare_location(path: &Path) -> Result<()> {
+    let storage_err = |reason: String| DbError::Storage {
+        path: path.to_path_buf(),
+        reason,
+    };
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| storage_err(format!("could not create {}: {}", parent.display(), e)))?;
+    }
+
+    match std::fs::metadata(path) {
+        Ok(meta) if meta.is_dir() => Err(storage_err("path is a directory".to_string())),
+        Ok(meta) if meta.permissions().readonly() => {
+            Err(storage_err("file is read-only".to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Parse a caller-supplied RFC 3339 timestamp, normalised to UTC so it compares as stored text.
+fn parse_timestamp(s: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| DbError::Invalid(format!("Invalid timestamp '{}': {}", s, e)))
+}
+
+/// Split a comma-separated status filter, rejecting anything but known
+/// statuses and "all".
+pub fn check_status_filter(filter: &str) -> Result<Vec<&str>> {
+    let statuses: Vec<&str> = filter
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    let valid = |s: &&str| *s == "all" || STATUSES.contains(s);
+    if let Some(bad) = statuses.iter().find(|s| !valid(s)) {
+        return Err(DbError::Invalid(format!(
+            "Invalid status '{}'. Must be one of: {}, all",
+            bad,
+            STATUSES.join(", ")
+        )));
+    }
+    if statuses.is_empty() {
+        return Err(DbError::Invalid(format!(
+            "No status given. Must be one of: {}, all",
+            STATUSES.join(", ")
+        )));
+    }
+    Ok(statuses)
+}
+
+/// Accept an http or https URL with something after the scheme and no
+/// whitespace or control characters.
+fn check_url(url: &str) -> Result<()> {
+    let lower = url.to_ascii_lowercase();
+    let rest = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"));
+    match rest {
+        Some(rest)
+            if !rest.is_empty() && !url.contains(|c: char| c.is_whitespace() || c.is_control()) =>
+        {
+            Ok(())
+        }
+        _ => Err(DbError::Invalid(format!(
+            "Invalid URL '{}': must start with http:// or https://",
+            url
+        ))),
+    }
+}
+
+/// Trim an issue title, rejecting one that is empty or only whitespace.
+fn clean_title(title: &str) -> Result<&str> {
+    let trimmed = title.trim();
+    if trimmed.is_empty() {
+        return Err(DbError::Invalid("Issue title cannot be empty".to_string()));
+    }
+    Ok(trimmed)
+}
+
 fn parse_datetime(s: String) -> DateTime<Utc> {
     DateTime::parse_from_rfc3339(&s)
         .map(|dt| dt.with_timezone(&Utc))
         .unwrap_or_else(|_| Utc::now())
 }
 
+/// Whether `query` occurs in `text` at the start of a word, ignoring ASCII case.
+fn starts_word(text: &str, query: &str) -> bool {
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    haystack.match_indices(&needle).any(|(start, _)| {
+        text[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric())
+    })
+}
+
+/// Build a substring LIKE pattern, escaping SQL LIKE wildcards so they match literally.
+fn like_pattern(query: &str) -> String {
+    let escaped = query.replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
 /// Maps a database row to an Issue struct.
 /// Expects columns in order: id, title, description, status, priority, parent_id, created_at, updated_at, closed_at
 fn issue_from_row(row: &rusqlite::Row) -> rusqlite::Result<Issue> {
@@ -983,6 +3053,78 @@ fn issue_from_row(row: &rusqlite::Row) -> rusqlite::Result<Issue> {
     })
 }
 
+/// The `FROM ... WHERE ...` part of a query over issues `i` matching `filter`,
+/// with ids below `before` if given, and the parameters it binds.
+fn filter_sql(
+    filter: &IssueFilter<'_>,
+    before: Option<i64>,
+) -> Result<(String, Vec<Box<dyn rusqlite::ToSql>>)> {
+    let mut sql = String::from("FROM issues i");
+    let mut conditions = Vec::new();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if filter.label.is_some() {
+        sql.push_str(" JOIN labels l ON i.id = l.issue_id");
+    }
+
+    // Archived issues stay hidden unless asked for explicitly or via "all"
+    match filter.status {
+        Some(status) => {
+            let statuses = check_status_filter(status)?;
+            if !statuses.contains(&"all") {
+                let placeholders = vec!["?"; statuses.len()].join(", ");
+                conditions.push(format!("i.status IN ({})", placeholders));
+                for s in statuses {
+                    params_vec.push(Box::new(s.to_string()));
+                }
+            }
+        }
+        None => conditions.push("i.status != 'archived'".to_string()),
+    }
+
+    if let Some(label) = filter.label {
+        conditions.push("l.label = ?".to_string());
+        params_vec.push(Box::new(label.to_string()));
+    }
+
+    if let Some(priority) = filter.priority {
+        conditions.push("i.priority = ?".to_string());
+        params_vec.push(Box::new(priority.to_string()));
+    }
+
+    match filter.assignee {
+        Some(UNASSIGNED) => {
+            conditions.push("(i.assignee IS NULL OR i.assignee = '')".to_string());
+        }
+        Some(assignee) => {
+            conditions.push("i.assignee = ?".to_string());
+            params_vec.push(Box::new(assignee.to_string()));
+        }
+        None => {}
+    }
+
+    if let Some(before) = before {
+        conditions.push("i.id < ?".to_string());
+        params_vec.push(Box::new(before));
+    }
+
+    if !conditions.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&conditions.join(" AND "));
+    }
+
+    Ok((sql, params_vec))
+}
+
+/// Normalize a title for duplicate detection.
+fn title_key(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1134,6 +3276,32 @@ mod tests {
         assert_eq!(issue.priority, "low");
     }
 
+    #[test]
+    fn test_set_priority_where() {
+        let (db, _dir) = setup_test_db();
+
+        let open_low = db.create_issue("Open low", None, "low").unwrap();
+        let other_low = db.create_issue("Another low", None, "low").unwrap();
+        let open_high = db.create_issue("Open high", None, "high").unwrap();
+        let closed_low = db.create_issue("Closed low", None, "low").unwrap();
+        db.close_issue(closed_low).unwrap();
+
+        let changed = db
+            .set_priority_where(Some("open"), "low", "medium")
+            .unwrap();
+        assert_eq!(changed, 2);
+
+        let priority = |id| db.get_issue(id).unwrap().unwrap().priority;
+        assert_eq!(priority(open_low), "medium");
+        assert_eq!(priority(other_low), "medium");
+        assert_eq!(priority(open_high), "high");
+        assert_eq!(priority(closed_low), "low");
+
+        assert!(db.set_priority_where(Some("open"), "bogus", "low").is_err());
+        assert!(db.set_priority_where(Some("open"), "low", "bogus").is_err());
+        assert_eq!(db.set_priority_where(None, "high", "high").unwrap(), 0);
+    }
+
     #[test]
     fn test_close_and_reopen_issue() {
         let (db, _dir) = setup_test_db();
@@ -1155,6 +3323,22 @@ mod tests {
         assert!(issue.closed_at.is_none());
     }
 
+    #[test]
+    fn test_close_and_reopen_report_no_op() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        assert!(!db.reopen_issue(id).unwrap());
+
+        assert!(db.close_issue(id).unwrap());
+        let closed_at = db.get_issue(id).unwrap().unwrap().closed_at;
+        assert!(!db.close_issue(id).unwrap());
+        assert_eq!(db.get_issue(id).unwrap().unwrap().closed_at, closed_at);
+
+        assert!(!db.close_issue(99999).unwrap());
+        assert!(!db.reopen_issue(99999).unwrap());
+    }
+
     #[test]
     fn test_close_nonexistent_issue_returns_false() {
         let (db, _dir) = setup_test_db();
@@ -1198,6 +3382,161 @@ mod tests {
         assert!(!deleted);
     }
 
+    // ==================== History Tests ====================
+
+    #[test]
+    fn test_history_close_then_reopen() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+        db.close_issue(id).unwrap();
+        db.reopen_issue(id).unwrap();
+
+        let status: Vec<(Option<String>, Option<String>)> = db
+            .get_history(id)
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.kind == "status")
+            .map(|e| (e.old_value, e.new_value))
+            .collect();
+        assert_eq!(
+            status,
+            vec![
+                (Some("open".to_string()), Some("closed".to_string())),
+                (Some("closed".to_string()), Some("open".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_skips_no_op_changes() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+        db.update_issue(id, Some("Renamed"), None, Some("medium"))
+            .unwrap();
+        db.reopen_issue(id).unwrap();
+        db.update_parent(id, None).unwrap();
+
+        let kinds: Vec<String> = db
+            .get_history(id)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.kind)
+            .collect();
+        assert_eq!(kinds, vec!["created"]);
+    }
+
+    #[test]
+    fn test_history_recursive_reopen() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        db.close_issue(child).unwrap();
+        db.close_issue(parent).unwrap();
+        db.reopen_issue_recursive(parent).unwrap();
+
+        let last = db.get_history(child).unwrap().pop().unwrap();
+        assert_eq!(last.new_value.as_deref(), Some("open"));
+    }
+
+    #[test]
+    fn test_history_backfilled_on_upgrade() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let id = {
+            let db = Database::open(&db_path).unwrap();
+            let id = db.create_issue("Issue", None, "medium").unwrap();
+            db.close_issue(id).unwrap();
+            db.conn
+                .execute_batch("DROP TABLE issue_events; PRAGMA user_version = 14;")
+                .unwrap();
+            id
+        };
+
+        let db = Database::open(&db_path).unwrap();
+        let kinds: Vec<String> = db
+            .get_history(id)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.kind)
+            .collect();
+        assert_eq!(kinds, vec!["created", "status"]);
+    }
+
+    #[test]
+    fn test_dependency_kinds_coexist() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+
+        assert!(db.add_dependency_of_kind(a, b, "duplicates").unwrap());
+        assert!(db.add_dependency(a, b).unwrap());
+        assert!(db.add_dependency_of_kind(a, b, "after").unwrap());
+        assert!(!db.add_dependency(a, b).unwrap());
+
+        assert_eq!(db.get_blockers(a).unwrap(), vec![b]);
+        assert_eq!(db.get_duplicate_of(a).unwrap(), vec![b]);
+        assert_eq!(db.count_open_predecessors(a).unwrap(), 1);
+        assert!(db
+            .list_blocked_with_meta()
+            .unwrap()
+            .iter()
+            .any(|m| m.issue.id == a));
+
+        // Removing the blocking edge leaves the other kinds alone
+        assert!(db.remove_dependency(a, b).unwrap());
+        assert!(db.get_blockers(a).unwrap().is_empty());
+        assert_eq!(db.get_duplicate_of(a).unwrap(), vec![b]);
+        assert_eq!(db.count_open_predecessors(a).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_dependency_key_rebuilt_on_upgrade() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let (a, b) = {
+            let db = Database::open(&db_path).unwrap();
+            let a = db.create_issue("A", None, "medium").unwrap();
+            let b = db.create_issue("B", None, "medium").unwrap();
+            db.conn
+                .execute_batch(
+                    r#"
+                    DROP TABLE dependencies;
+                    CREATE TABLE dependencies (
+                        blocker_id INTEGER NOT NULL,
+                        blocked_id INTEGER NOT NULL,
+                        kind TEXT NOT NULL DEFAULT 'blocks',
+                        created_at TEXT,
+                        PRIMARY KEY (blocker_id, blocked_id)
+                    );
+                    PRAGMA user_version = 20;
+                    "#,
+                )
+                .unwrap();
+            db.add_dependency_of_kind(a, b, "after").unwrap();
+            (a, b)
+        };
+
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(db.count_open_predecessors(a).unwrap(), 1);
+        assert!(db.add_dependency(a, b).unwrap());
+        assert_eq!(db.get_blockers(a).unwrap(), vec![b]);
+    }
+
+    #[test]
+    fn test_history_deleted_with_issue() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+        db.close_issue(id).unwrap();
+        db.delete_issue(id).unwrap();
+
+        assert!(db.get_history(id).unwrap().is_empty());
+        let count: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM issue_events", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
     // ==================== Labels Tests ====================
 
     #[test]
@@ -1335,7 +3674,7 @@ mod tests {
     }
 
     #[test]
-    fn test_list_blocked_issues() {
+    fn test_list_blocked_with_meta() {
         let (db, _dir) = setup_test_db();
 
         let blocker = db.create_issue("Blocker", None, "high").unwrap();
@@ -1344,322 +3683,2096 @@ mod tests {
 
         db.add_dependency(blocked, blocker).unwrap();
 
-        let blocked_issues = db.list_blocked_issues().unwrap();
+        let blocked_issues = db.list_blocked_with_meta().unwrap();
         assert_eq!(blocked_issues.len(), 1);
-        assert_eq!(blocked_issues[0].id, blocked);
+        assert_eq!(blocked_issues[0].issue.id, blocked);
 
         // Unblocked issue should not appear
-        assert!(!blocked_issues.iter().any(|i| i.id == unblocked));
+        assert!(!blocked_issues.iter().any(|m| m.issue.id == unblocked));
     }
 
     #[test]
-    fn test_list_ready_issues() {
+    fn test_list_stale() {
         let (db, _dir) = setup_test_db();
+        let fresh = db.create_issue("Fresh", None, "medium").unwrap();
+        let old = db.create_issue("Old", None, "medium").unwrap();
+        let older = db.create_issue("Older", None, "medium").unwrap();
+        let old_closed = db.create_issue("Old but closed", None, "medium").unwrap();
+        db.close_issue(old_closed).unwrap();
 
-        let blocker = db.create_issue("Blocker", None, "high").unwrap();
-        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
-        let ready = db.create_issue("Ready", None, "low").unwrap();
+        let now = Utc::now();
+        let backdate = |id: i64, days: i64| {
+            db.conn
+                .execute(
+                    "UPDATE issues SET updated_at = ?1 WHERE id = ?2",
+                    params![(now - chrono::Duration::days(days)).to_rfc3339(), id],
+                )
+                .unwrap();
+        };
+        backdate(old, 40);
+        backdate(older, 90);
+        backdate(old_closed, 90);
+
+        let stale: Vec<i64> = db
+            .list_stale(30, &now.to_rfc3339())
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(stale, vec![older, old]);
+        assert!(!stale.contains(&fresh));
+
+        assert!(matches!(
+            db.list_stale(30, "yesterday"),
+            Err(DbError::Invalid(_))
+        ));
+    }
 
-        db.add_dependency(blocked, blocker).unwrap();
+    #[test]
+    fn test_list_long_blocked() {
+        let (db, _dir) = setup_test_db();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        let old = db.create_issue("Blocked long ago", None, "medium").unwrap();
+        let new = db.create_issue("Blocked just now", None, "medium").unwrap();
+        db.add_dependency(old, blocker).unwrap();
+        db.add_dependency(new, blocker).unwrap();
 
-        let ready_issues = db.list_ready_issues().unwrap();
+        let now = Utc::now();
+        db.conn
+            .execute(
+                "UPDATE dependencies SET created_at = ?1 WHERE blocked_id = ?2",
+                params![(now - chrono::Duration::days(60)).to_rfc3339(), old],
+            )
+            .unwrap();
 
-        // Blocker and ready should be in ready list (not blocked by anything)
-        let ready_ids: Vec<i64> = ready_issues.iter().map(|i| i.id).collect();
-        assert!(ready_ids.contains(&blocker));
-        assert!(ready_ids.contains(&ready));
-        assert!(!ready_ids.contains(&blocked));
+        let cutoff = (now - chrono::Duration::days(30)).to_rfc3339();
+        let ids: Vec<i64> = db
+            .list_long_blocked(&cutoff)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ids, vec![old]);
+
+        // Once the blocker closes, nothing is blocked any more
+        db.close_issue(blocker).unwrap();
+        assert!(db.list_long_blocked(&cutoff).unwrap().is_empty());
     }
 
     #[test]
-    fn test_blocked_becomes_ready_when_blocker_closed() {
+    fn test_reopen_issue_recursive() {
         let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let child_a = db.create_subissue(epic, "Child A", None, "medium").unwrap();
+        let child_b = db.create_subissue(epic, "Child B", None, "medium").unwrap();
+        let grandchild = db
+            .create_subissue(child_a, "Grandchild", None, "low")
+            .unwrap();
+        let outside = db.create_issue("Outside", None, "low").unwrap();
+        for id in [epic, child_a, child_b, grandchild, outside] {
+            db.close_issue(id).unwrap();
+        }
 
-        let blocker = db.create_issue("Blocker", None, "high").unwrap();
-        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        assert_eq!(db.reopen_issue_recursive(epic).unwrap(), 4);
+        for id in [epic, child_a, child_b, grandchild] {
+            assert_eq!(db.get_issue(id).unwrap().unwrap().status, "open");
+            assert!(db.get_issue(id).unwrap().unwrap().closed_at.is_none());
+        }
+        assert_eq!(db.get_issue(outside).unwrap().unwrap().status, "closed");
 
-        db.add_dependency(blocked, blocker).unwrap();
+        // Nothing left to reopen
+        assert_eq!(db.reopen_issue_recursive(epic).unwrap(), 0);
+    }
 
-        // Initially blocked
-        let blocked_issues = db.list_blocked_issues().unwrap();
-        assert_eq!(blocked_issues.len(), 1);
+    #[test]
+    fn test_create_subissue_missing_parent() {
+        let (db, _dir) = setup_test_db();
+        match db.create_subissue(42, "Orphan", None, "medium") {
+            Err(DbError::ParentNotFound(id)) => assert_eq!(id, 42),
+            other => panic!("expected ParentNotFound, got {:?}", other),
+        }
+        let err = db
+            .create_subissue(42, "Orphan", None, "medium")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Parent issue #42 not found");
+        assert!(db.list_issues(Some("all"), None, None).unwrap().is_empty());
+
+        // A deleted parent is just as missing
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        db.delete_issue(parent).unwrap();
+        assert!(matches!(
+            db.create_subissue(parent, "Orphan", None, "medium"),
+            Err(DbError::ParentNotFound(_))
+        ));
+    }
 
-        // Close blocker
-        db.close_issue(blocker).unwrap();
+    #[test]
+    fn test_update_parent_rejects_self() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+        assert!(matches!(
+            db.update_parent(id, Some(id)),
+            Err(DbError::Invalid(_))
+        ));
+        assert_eq!(db.get_issue(id).unwrap().unwrap().parent_id, None);
+    }
 
-        // Now should be ready
-        let blocked_issues = db.list_blocked_issues().unwrap();
-        assert!(blocked_issues.is_empty());
+    #[test]
+    fn test_create_subissue_depth_cap() {
+        let (db, _dir) = setup_test_db();
+        let mut parent = db.create_issue("Root", None, "medium").unwrap();
+        for level in 1..=DEFAULT_MAX_DEPTH {
+            parent = db
+                .create_subissue(parent, &format!("Level {}", level), None, "medium")
+                .unwrap();
+        }
 
-        let ready_issues = db.list_ready_issues().unwrap();
-        assert!(ready_issues.iter().any(|i| i.id == blocked));
+        let result = db.create_subissue(parent, "Too deep", None, "medium");
+        assert!(matches!(result, Err(DbError::Invalid(_))));
     }
 
-    // ==================== Sessions Tests ====================
+    #[test]
+    fn test_transaction_nested_rolls_back_inner_only() {
+        let (db, _dir) = setup_test_db();
+
+        db.transaction(|| -> Result<()> {
+            db.create_issue("Outer", None, "medium")?;
+            let inner: Result<()> = db.transaction(|| {
+                db.create_issue("Inner", None, "medium")?;
+                Err(DbError::Invalid("abort".to_string()))
+            });
+            assert!(inner.is_err());
+            Ok(())
+        })
+        .unwrap();
+
+        let titles: Vec<String> = db
+            .list_issues(Some("all"), None, None)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.title)
+            .collect();
+        assert_eq!(titles, vec!["Outer"]);
+    }
 
     #[test]
-    fn test_start_and_get_session() {
+    fn test_dry_run_discards_changes() {
         let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Keep me", None, "medium").unwrap();
 
-        let id = db.start_session().unwrap();
-        assert!(id > 0);
+        let deleted = db
+            .dry_run(|| -> Result<bool> {
+                // Nested transactions still work inside a dry run
+                db.transaction(|| db.create_issue("Temp", None, "low"))?;
+                db.delete_issue(id)
+            })
+            .unwrap();
+        assert!(deleted);
+        assert!(db.get_issue(id).unwrap().is_some());
+        assert_eq!(db.list_issues(Some("all"), None, None).unwrap().len(), 1);
+
+        // Errors are passed through and still roll back
+        let failed: Result<()> = db.dry_run(|| {
+            db.close_issue(id)?;
+            Err(DbError::NotFound(7))
+        });
+        assert!(failed.is_err());
+        assert_eq!(db.get_issue(id).unwrap().unwrap().status, "open");
+    }
 
-        let session = db.get_current_session().unwrap().unwrap();
-        assert_eq!(session.id, id);
-        assert!(session.ended_at.is_none());
-        assert!(session.active_issue_id.is_none());
+    #[test]
+    fn test_dry_run_nested_in_transaction() {
+        let (db, _dir) = setup_test_db();
+
+        db.transaction(|| -> Result<()> {
+            db.create_issue("Outer", None, "medium")?;
+            db.dry_run(|| db.create_issue("Inner", None, "medium"))?;
+            Ok(())
+        })
+        .unwrap();
+
+        let titles: Vec<String> = db
+            .list_issues(Some("all"), None, None)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.title)
+            .collect();
+        assert_eq!(titles, vec!["Outer"]);
     }
 
     #[test]
-    fn test_end_session() {
+    fn test_set_max_depth() {
+        let (mut db, _dir) = setup_test_db();
+        db.set_max_depth(2);
+        let root = db.create_issue("Root", None, "medium").unwrap();
+        let child = db.create_subissue(root, "Child", None, "medium").unwrap();
+        let grandchild = db
+            .create_subissue(child, "Grandchild", None, "medium")
+            .unwrap();
+        assert!(db
+            .create_subissue(grandchild, "Great", None, "medium")
+            .is_err());
+        // Siblings at an allowed depth are still fine
+        assert!(db.create_subissue(child, "Sibling", None, "medium").is_ok());
+
+        // Moving a subtree counts its own height too
+        let other = db.create_issue("Other", None, "medium").unwrap();
+        db.create_subissue(other, "Other child", None, "medium")
+            .unwrap();
+        assert!(db.update_parent(other, Some(child)).is_err());
+        assert_eq!(db.get_issue(other).unwrap().unwrap().parent_id, None);
+        assert!(db.update_parent(other, Some(root)).is_ok());
+        assert!(db.update_parent(child, None).is_ok());
+
+        // Templates nest their subissues one level down
+        db.set_max_depth(0);
+        db.save_template("sprint", "Sprint", None, "medium", &["Plan".to_string()])
+            .unwrap();
+        assert!(db.instantiate_template("sprint").is_err());
+    }
+
+    #[test]
+    fn test_list_dependencies_matches_inserted() {
         let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        let d = db.create_issue("D", None, "medium").unwrap();
+
+        let inserted = vec![(c, a), (b, a), (c, b), (d, c)];
+        for &(blocked, blocker) in &inserted {
+            db.add_dependency(blocked, blocker).unwrap();
+        }
+        // Non-blocking links are not dependencies
+        db.add_dependency_of_kind(d, a, "duplicates").unwrap();
 
-        let id = db.start_session().unwrap();
-        db.end_session(id, Some("Handoff notes")).unwrap();
+        let listed: std::collections::HashSet<(i64, i64)> =
+            db.list_dependencies().unwrap().into_iter().collect();
+        let expected: std::collections::HashSet<(i64, i64)> = inserted.into_iter().collect();
+        assert_eq!(listed, expected);
 
-        let current = db.get_current_session().unwrap();
-        assert!(current.is_none());
+        db.remove_dependency(c, a).unwrap();
+        assert_eq!(db.list_dependencies().unwrap().len(), 3);
+    }
 
-        let last = db.get_last_session().unwrap().unwrap();
-        assert_eq!(last.id, id);
-        assert!(last.ended_at.is_some());
-        assert_eq!(last.handoff_notes, Some("Handoff notes".to_string()));
+    #[test]
+    fn test_dependency_components() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        let d = db.create_issue("D", None, "medium").unwrap();
+        let e = db.create_issue("E", None, "medium").unwrap();
+        db.create_issue("Loner", None, "medium").unwrap();
+        assert!(db.dependency_components().unwrap().is_empty());
+
+        // Two separate chains: a <- b <- d and c <- e
+        db.add_dependency(b, a).unwrap();
+        db.add_dependency(d, b).unwrap();
+        db.add_dependency(e, c).unwrap();
+
+        assert_eq!(
+            db.dependency_components().unwrap(),
+            vec![vec![a, b, d], vec![c, e]]
+        );
+    }
+
+    fn cached_blocked(db: &Database, id: i64) -> bool {
+        db.conn
+            .query_row("SELECT is_blocked FROM issues WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .unwrap()
     }
 
     #[test]
-    fn test_set_session_issue() {
+    fn test_blocked_flag_follows_block_unblock_close() {
         let (db, _dir) = setup_test_db();
+        let task = db.create_issue("Task", None, "medium").unwrap();
+        let first = db.create_issue("First blocker", None, "medium").unwrap();
+        let second = db.create_issue("Second blocker", None, "medium").unwrap();
+        assert!(!cached_blocked(&db, task));
+
+        db.add_dependency(task, first).unwrap();
+        db.add_dependency(task, second).unwrap();
+        assert!(cached_blocked(&db, task));
+
+        db.close_issue(first).unwrap();
+        assert!(cached_blocked(&db, task));
+        db.close_issue(second).unwrap();
+        assert!(!cached_blocked(&db, task));
+
+        db.reopen_issue(second).unwrap();
+        assert!(cached_blocked(&db, task));
+        db.remove_dependency(task, second).unwrap();
+        assert!(!cached_blocked(&db, task));
+
+        db.reopen_issue(first).unwrap();
+        assert!(cached_blocked(&db, task));
+        db.clear_dependencies(task).unwrap();
+        assert!(!cached_blocked(&db, task));
+
+        // Swapping moves the flag to the other end of the edge
+        db.add_dependency(task, first).unwrap();
+        db.swap_dependency(task, first).unwrap();
+        assert!(!cached_blocked(&db, task));
+        assert!(cached_blocked(&db, first));
+
+        // Deleting a blocker releases what it blocked
+        db.delete_issue(task).unwrap();
+        assert!(!cached_blocked(&db, first));
+
+        // Soft links never block
+        db.add_dependency_of_kind(second, first, "after").unwrap();
+        assert!(!cached_blocked(&db, second));
+        assert_eq!(db.recompute_blocked().unwrap(), 0);
+    }
 
-        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
-        let session_id = db.start_session().unwrap();
+    #[test]
+    fn test_blocked_flag_after_recursive_reopen() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        let waiting = db.create_issue("Waiting", None, "medium").unwrap();
+        db.add_dependency(waiting, child).unwrap();
+        db.close_issue(child).unwrap();
+        db.close_issue(parent).unwrap();
+        assert!(!cached_blocked(&db, waiting));
+
+        db.reopen_issue_recursive(parent).unwrap();
+        assert!(cached_blocked(&db, waiting));
+        assert_eq!(db.recompute_blocked().unwrap(), 0);
+    }
 
-        db.set_session_issue(session_id, issue_id).unwrap();
+    #[test]
+    fn test_dependency_levels() {
+        let (db, _dir) = setup_test_db();
+        let design = db.create_issue("Design", None, "medium").unwrap();
+        let docs = db.create_issue("Docs", None, "medium").unwrap();
+        let backend = db.create_issue("Backend", None, "medium").unwrap();
+        let frontend = db.create_issue("Frontend", None, "medium").unwrap();
+        let release = db.create_issue("Release", None, "medium").unwrap();
+        let done = db.create_issue("Done", None, "medium").unwrap();
+
+        db.add_dependency(backend, design).unwrap();
+        db.add_dependency(frontend, design).unwrap();
+        db.add_dependency(frontend, backend).unwrap();
+        db.add_dependency(release, frontend).unwrap();
+        db.add_dependency(release, docs).unwrap();
+        // A closed blocker no longer holds anything back, and closed issues are left out
+        db.add_dependency(docs, done).unwrap();
+        db.close_issue(done).unwrap();
+
+        assert_eq!(
+            db.dependency_levels().unwrap(),
+            vec![
+                vec![design, docs],
+                vec![backend],
+                vec![frontend],
+                vec![release]
+            ]
+        );
+    }
 
-        let session = db.get_current_session().unwrap().unwrap();
-        assert_eq!(session.active_issue_id, Some(issue_id));
+    #[test]
+    fn test_dependency_levels_reports_cycle() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        db.add_dependency(b, a).unwrap();
+        db.add_dependency(c, b).unwrap();
+        // add_dependency refuses cycles, so plant one directly as an old database might have
+        db.conn
+            .execute(
+                "INSERT INTO dependencies (blocker_id, blocked_id) VALUES (?1, ?2)",
+                [c, a],
+            )
+            .unwrap();
+
+        match db.dependency_levels() {
+            Err(DbError::Cycle(path)) => assert_eq!(path, vec![a, b, c, a]),
+            other => panic!("expected a cycle, got {:?}", other),
+        }
     }
 
-    // ==================== Time Tracking Tests ====================
+    #[test]
+    fn test_top_blockers_ranks_hub_first() {
+        let (db, _dir) = setup_test_db();
+        let hub = db.create_issue("Hub", None, "medium").unwrap();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        let leaf = db.create_issue("Leaf", None, "medium").unwrap();
+        let side = db.create_issue("Side", None, "medium").unwrap();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+
+        db.add_dependency(a, hub).unwrap();
+        db.add_dependency(b, hub).unwrap();
+        db.add_dependency(c, hub).unwrap();
+        // Reached twice through a and b, but counted once
+        db.add_dependency(leaf, a).unwrap();
+        db.add_dependency(leaf, b).unwrap();
+        db.add_dependency(closed, side).unwrap();
+        db.close_issue(closed).unwrap();
+
+        let top: Vec<(i64, usize)> = db
+            .top_blockers(10)
+            .unwrap()
+            .into_iter()
+            .map(|(issue, count)| (issue.id, count))
+            .collect();
+        assert_eq!(top, vec![(hub, 4), (a, 1), (b, 1)]);
+
+        assert_eq!(db.top_blockers(1).unwrap()[0].0.id, hub);
+    }
 
     #[test]
-    fn test_start_and_stop_timer() {
+    fn test_duplicate_edge_does_not_block() {
         let (db, _dir) = setup_test_db();
+        let original = db.create_issue("Original", None, "medium").unwrap();
+        let dup = db.create_issue("Duplicate", None, "medium").unwrap();
+
+        assert!(db
+            .add_dependency_of_kind(dup, original, "duplicates")
+            .unwrap());
+        assert!(db.get_blockers(dup).unwrap().is_empty());
+        assert!(db.get_blocking(original).unwrap().is_empty());
+        assert_eq!(db.count_dependents(original).unwrap(), 0);
+        assert!(db.list_blocked_with_meta().unwrap().is_empty());
+        let ready: Vec<i64> = db
+            .list_ready_issues(false)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert!(ready.contains(&dup));
+
+        assert_eq!(db.get_duplicate_of(dup).unwrap(), vec![original]);
+        assert_eq!(db.get_duplicated_by(original).unwrap(), vec![dup]);
+
+        // unblock only removes "blocks" edges
+        assert!(!db.remove_dependency(dup, original).unwrap());
+        assert_eq!(db.get_duplicate_of(dup).unwrap(), vec![original]);
+    }
 
-        let id = db.create_issue("Test issue", None, "medium").unwrap();
+    #[test]
+    fn test_relates_link_does_not_block() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
 
-        let timer_id = db.start_timer(id).unwrap();
-        assert!(timer_id > 0);
+        db.add_relation(a, b).unwrap();
+        assert!(db.get_blockers(a).unwrap().is_empty());
+        assert!(db.get_blockers(b).unwrap().is_empty());
+        assert_eq!(db.list_ready_issues(false).unwrap().len(), 2);
+    }
 
-        let active = db.get_active_timer().unwrap();
-        assert!(active.is_some());
-        assert_eq!(active.unwrap().0, id);
+    #[test]
+    fn test_duplicate_edges_skip_cycle_check_but_reject_bad_kind() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+
+        db.add_dependency(a, b).unwrap();
+        // A duplicate link in the other direction is not a blocking cycle
+        assert!(db.add_dependency_of_kind(b, a, "duplicates").unwrap());
+        assert!(matches!(
+            db.add_dependency_of_kind(a, a, "duplicates"),
+            Err(DbError::Invalid(_))
+        ));
+        assert!(matches!(
+            db.add_dependency_of_kind(a, b, "follows"),
+            Err(DbError::Invalid(_))
+        ));
+    }
 
-        std::thread::sleep(std::time::Duration::from_millis(100));
+    #[test]
+    fn test_get_issues_skips_missing_ids() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "high").unwrap();
+
+        let found = db.get_issues(&[a, 999, b, a, -1]).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[&a].title, "A");
+        assert_eq!(found[&b].priority, "high");
+        assert!(!found.contains_key(&999));
+        assert!(db.get_issues(&[]).unwrap().is_empty());
+    }
 
-        db.stop_timer(id).unwrap();
+    #[test]
+    fn test_get_issues_across_chunks() {
+        let (db, _dir) = setup_test_db();
+        let ids: Vec<i64> = (0..ID_CHUNK_SIZE + 5)
+            .map(|n| {
+                db.create_issue(&format!("Issue {}", n), None, "low")
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(db.get_issues(&ids).unwrap().len(), ids.len());
+    }
 
-        let active = db.get_active_timer().unwrap();
-        assert!(active.is_none());
+    #[test]
+    fn test_batch_lookups_past_sqlite_parameter_limit() {
+        let (db, _dir) = setup_test_db();
+        // SQLite's default limit is 999 bound parameters per statement
+        let ids: Vec<i64> = db
+            .transaction(|| -> Result<Vec<i64>> {
+                let root = db.create_issue("Root", None, "low")?;
+                let mut ids = vec![root];
+                for n in 1..1500 {
+                    let id = db.create_issue(&format!("Issue {}", n), None, "low")?;
+                    db.add_dependency(id, root)?;
+                    ids.push(id);
+                }
+                Ok(ids)
+            })
+            .unwrap();
+
+        let issues = db.get_issues(&ids).unwrap();
+        assert_eq!(issues.len(), 1500);
+        assert!(ids.iter().all(|id| issues.contains_key(id)));
+
+        let blockers = db.get_blockers_for(&ids).unwrap();
+        assert_eq!(blockers.len(), 1500);
+        assert!(blockers[&ids[0]].is_empty());
+        assert!(ids[1..].iter().all(|id| blockers[id] == vec![ids[0]]));
     }
 
     #[test]
-    fn test_get_total_time() {
+    fn test_with_meta_matches_get_blockers() {
         let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        let d = db.create_issue("D", None, "medium").unwrap();
+        let e = db.create_issue("E", None, "medium").unwrap();
+        db.add_dependency(c, b).unwrap();
+        db.add_dependency(c, a).unwrap();
+        db.add_dependency(d, c).unwrap();
+        db.add_dependency(e, a).unwrap();
+        db.add_dependency_of_kind(b, a, "duplicates").unwrap();
+        db.close_issue(a).unwrap();
+
+        let blocked = db.list_blocked_with_meta().unwrap();
+        let ready = db.list_ready_with_meta(false).unwrap();
+        let blocked_ids: Vec<i64> = blocked.iter().map(|m| m.issue.id).collect();
+        let ready_ids: Vec<i64> = ready.iter().map(|m| m.issue.id).collect();
+        assert_eq!(blocked_ids, vec![c, d]);
+        assert_eq!(ready_ids, vec![b, e]);
+
+        for meta in blocked.iter().chain(ready.iter()) {
+            let mut expected = db.get_blockers(meta.issue.id).unwrap();
+            expected.sort_unstable();
+            assert_eq!(meta.blockers, expected, "issue #{}", meta.issue.id);
+        }
+        // Ready issues keep their closed blockers, and duplicate links are not blockers
+        assert_eq!(ready[1].blockers, vec![a]);
+        assert!(ready[0].blockers.is_empty());
+    }
 
-        let id = db.create_issue("Test issue", None, "medium").unwrap();
+    #[test]
+    fn test_list_blocked_with_meta_priority_order() {
+        let (db, _dir) = setup_test_db();
 
-        // No time tracked yet
-        let total = db.get_total_time(id).unwrap();
-        assert_eq!(total, 0);
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        let low = db.create_issue("Low", None, "low").unwrap();
+        let high_a = db.create_issue("High A", None, "high").unwrap();
+        let critical = db.create_issue("Critical", None, "critical").unwrap();
+        let medium = db.create_issue("Medium", None, "medium").unwrap();
+        let high_b = db.create_issue("High B", None, "high").unwrap();
+        for id in [low, high_a, critical, medium, high_b] {
+            db.add_dependency(id, blocker).unwrap();
+        }
+
+        let ids: Vec<i64> = db
+            .list_blocked_with_meta()
+            .unwrap()
+            .iter()
+            .map(|m| m.issue.id)
+            .collect();
+        assert_eq!(ids, vec![critical, high_a, high_b, medium, low]);
+    }
+
+    #[test]
+    fn test_list_ready_issues_priority_order() {
+        let (db, _dir) = setup_test_db();
+        let low = db.create_issue("Low", None, "low").unwrap();
+        let high_a = db.create_issue("High A", None, "high").unwrap();
+        let critical = db.create_issue("Critical", None, "critical").unwrap();
+        let high_b = db.create_issue("High B", None, "high").unwrap();
+
+        let ids: Vec<i64> = db
+            .list_ready_issues(false)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ids, vec![critical, high_a, high_b, low]);
+    }
+
+    #[test]
+    fn test_list_ready_issues() {
+        let (db, _dir) = setup_test_db();
+
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let ready = db.create_issue("Ready", None, "low").unwrap();
+
+        db.add_dependency(blocked, blocker).unwrap();
+
+        let ready_issues = db.list_ready_issues(false).unwrap();
+
+        // Blocker and ready should be in ready list (not blocked by anything)
+        let ready_ids: Vec<i64> = ready_issues.iter().map(|i| i.id).collect();
+        assert!(ready_ids.contains(&blocker));
+        assert!(ready_ids.contains(&ready));
+        assert!(!ready_ids.contains(&blocked));
+    }
+
+    #[test]
+    fn test_blocked_becomes_ready_when_blocker_closed() {
+        let (db, _dir) = setup_test_db();
+
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+
+        db.add_dependency(blocked, blocker).unwrap();
+
+        // Initially blocked
+        let blocked_issues = db.list_blocked_with_meta().unwrap();
+        assert_eq!(blocked_issues.len(), 1);
+
+        // Close blocker
+        db.close_issue(blocker).unwrap();
+
+        // Now should be ready
+        let blocked_issues = db.list_blocked_with_meta().unwrap();
+        assert!(blocked_issues.is_empty());
+
+        let ready_issues = db.list_ready_issues(false).unwrap();
+        assert!(ready_issues.iter().any(|i| i.id == blocked));
+    }
+
+    #[test]
+    fn test_get_blocking_mirrors_get_blockers() {
+        let (db, _dir) = setup_test_db();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        db.add_dependency(a, blocker).unwrap();
+        db.add_dependency(b, blocker).unwrap();
+
+        let mut blocking = db.get_blocking(blocker).unwrap();
+        blocking.sort();
+        assert_eq!(blocking, vec![a, b]);
+        assert_eq!(db.count_dependents(blocker).unwrap(), 2);
+        for id in blocking {
+            assert_eq!(db.get_blockers(id).unwrap(), vec![blocker]);
+        }
+
+        db.remove_dependency(a, blocker).unwrap();
+        assert_eq!(db.get_blocking(blocker).unwrap(), vec![b]);
+        assert_eq!(db.count_dependents(blocker).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_require_issue_not_found_error() {
+        let (db, _dir) = setup_test_db();
+        match db.require_issue(42) {
+            Err(DbError::NotFound(id)) => assert_eq!(id, 42),
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+        let err = db.require_issue(42).unwrap_err();
+        assert_eq!(err.to_string(), "Issue #42 not found");
+
+        let id = db.create_issue("Present", None, "high").unwrap();
+        let issue = db.require_issue(id).unwrap();
+        assert_eq!((issue.id, issue.title.as_str()), (id, "Present"));
+        assert_eq!(Some(issue), db.get_issue(id).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_priority_error() {
+        let (db, _dir) = setup_test_db();
+        match db.create_issue("Issue", None, "urgent") {
+            Err(DbError::InvalidPriority { priority, .. }) => assert_eq!(priority, "urgent"),
+            other => panic!("expected InvalidPriority, got {:?}", other),
+        }
+
+        let id = db.create_issue("Issue", None, "low").unwrap();
+        assert!(matches!(
+            db.update_issue(id, None, None, Some("bogus")),
+            Err(DbError::InvalidPriority { .. })
+        ));
+        assert_eq!(db.get_issue(id).unwrap().unwrap().priority, "low");
+    }
+
+    #[test]
+    fn test_cycle_error_reports_path() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        // a blocks b, b blocks c
+        db.add_dependency(b, a).unwrap();
+        db.add_dependency(c, b).unwrap();
+
+        // c blocking a would close a -> b -> c -> a
+        match db.add_dependency(a, c) {
+            Err(DbError::Cycle(path)) => assert_eq!(path, vec![a, b, c, a]),
+            other => panic!("expected Cycle, got {:?}", other),
+        }
+        assert!(matches!(db.add_dependency(a, a), Err(DbError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_transitive_blocker_path() {
+        let (db, _dir) = setup_test_db();
+        let top = db.create_issue("Top", None, "medium").unwrap();
+        let mid = db.create_issue("Mid", None, "medium").unwrap();
+        let leaf = db.create_issue("Leaf", None, "medium").unwrap();
+        db.add_dependency(mid, top).unwrap();
+        db.add_dependency(leaf, mid).unwrap();
+
+        assert_eq!(
+            db.transitive_blocker_path(leaf, top).unwrap(),
+            Some(vec![leaf, mid, top])
+        );
+        assert_eq!(db.transitive_blocker_path(top, leaf).unwrap(), None);
+
+        // A closed issue in the middle breaks the chain
+        db.close_issue(mid).unwrap();
+        assert_eq!(db.transitive_blocker_path(leaf, top).unwrap(), None);
+        db.reopen_issue(mid).unwrap();
+        db.close_issue(top).unwrap();
+        assert_eq!(db.transitive_blocker_path(leaf, top).unwrap(), None);
+    }
+
+    #[test]
+    fn test_swap_dependency() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        db.add_dependency(a, b).unwrap();
+
+        assert_eq!(db.swap_dependency(a, b).unwrap(), (b, a));
+        assert_eq!(db.get_blockers(b).unwrap(), vec![a]);
+        assert!(db.get_blockers(a).unwrap().is_empty());
+        // Argument order does not matter
+        assert_eq!(db.swap_dependency(a, b).unwrap(), (a, b));
+        assert_eq!(db.get_blockers(a).unwrap(), vec![b]);
+
+        assert!(matches!(db.swap_dependency(a, c), Err(DbError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_swap_dependency_rejects_cycle() {
+        let (db, _dir) = setup_test_db();
+        let x = db.create_issue("X", None, "medium").unwrap();
+        let y = db.create_issue("Y", None, "medium").unwrap();
+        let z = db.create_issue("Z", None, "medium").unwrap();
+        db.add_dependency(y, x).unwrap();
+        db.add_dependency(z, y).unwrap();
+        db.add_dependency(z, x).unwrap();
+
+        // Reversing x -> z into z -> x would close x -> y -> z -> x
+        assert!(matches!(db.swap_dependency(z, x), Err(DbError::Cycle(_))));
+        // The original edge survives the rollback
+        assert!(db.get_blockers(z).unwrap().contains(&x));
+        assert!(db.get_blockers(x).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_list_leaves() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let child = db.create_subissue(epic, "Child", None, "medium").unwrap();
+        let grandchild = db
+            .create_subissue(child, "Grandchild", None, "low")
+            .unwrap();
+        let sibling = db.create_subissue(epic, "Sibling", None, "medium").unwrap();
+        let lone = db.create_issue("Lone", None, "low").unwrap();
+        db.close_issue(lone).unwrap();
+
+        let ids = |issues: Vec<Issue>| issues.iter().map(|i| i.id).collect::<Vec<_>>();
+        // Parents with children are excluded, their childless siblings are not
+        assert_eq!(
+            ids(db.list_leaves(None).unwrap()),
+            vec![grandchild, sibling, lone]
+        );
+        assert_eq!(
+            ids(db.list_leaves(Some("open")).unwrap()),
+            vec![grandchild, sibling]
+        );
+        assert!(!ids(db.list_leaves(Some("all")).unwrap()).contains(&child));
+    }
+
+    #[test]
+    fn test_set_default_priority() {
+        let (mut db, _dir) = setup_test_db();
+        assert_eq!(db.default_priority(), DEFAULT_PRIORITY);
+
+        db.set_default_priority("high").unwrap();
+        assert_eq!(db.default_priority(), "high");
+        assert!(matches!(
+            db.set_default_priority("urgent"),
+            Err(DbError::InvalidPriority { .. })
+        ));
+        assert_eq!(db.default_priority(), "high");
+    }
+
+    #[test]
+    fn test_missing_ids() {
+        let (db, _dir) = setup_test_db();
+        assert!(db.missing_ids(0).unwrap().is_empty());
+        assert_eq!(db.last_issue_id().unwrap(), 0);
+
+        let a = db.create_issue("One", None, "medium").unwrap();
+        let b = db.create_issue("Two", None, "medium").unwrap();
+        let c = db.create_issue("Three", None, "medium").unwrap();
+        assert_eq!((a, b, c), (1, 2, 3));
+        db.delete_issue(b).unwrap();
+
+        assert_eq!(db.missing_ids(3).unwrap(), vec![2]);
+        assert_eq!(db.missing_ids(5).unwrap(), vec![2, 4, 5]);
+
+        // Trailing deletes still count towards the last id handed out
+        db.delete_issue(c).unwrap();
+        assert_eq!(db.last_issue_id().unwrap(), 3);
+        assert_eq!(db.missing_ids(3).unwrap(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_count_dependents_ignores_closed() {
+        let (db, _dir) = setup_test_db();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        let open = db.create_issue("Open", None, "medium").unwrap();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        db.add_dependency(open, blocker).unwrap();
+        db.add_dependency(closed, blocker).unwrap();
+        db.close_issue(closed).unwrap();
+
+        assert_eq!(db.count_dependents(blocker).unwrap(), 1);
+        assert_eq!(db.count_dependents(open).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_dependency_counts_both_directions() {
+        let (db, _dir) = setup_test_db();
+        let root = db.create_issue("Root", None, "medium").unwrap();
+        let other = db.create_issue("Other root", None, "medium").unwrap();
+        let middle = db.create_issue("Middle", None, "medium").unwrap();
+        let leaf = db.create_issue("Leaf", None, "medium").unwrap();
+        let dup = db.create_issue("Dup", None, "medium").unwrap();
+        db.add_dependency(middle, root).unwrap();
+        db.add_dependency(middle, other).unwrap();
+        db.add_dependency(leaf, middle).unwrap();
+        db.add_dependency_of_kind(dup, middle, "duplicates")
+            .unwrap();
+
+        let counts = db.dependency_counts().unwrap();
+        assert_eq!(counts[&middle], (2, 1));
+        assert_eq!(counts[&root], (0, 1));
+        assert_eq!(counts[&leaf], (1, 0));
+        assert!(!counts.contains_key(&dup));
+    }
+
+    #[test]
+    fn test_list_isolated() {
+        let (db, _dir) = setup_test_db();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let isolated = db.create_issue("Isolated", None, "medium").unwrap();
+        db.create_subissue(isolated, "Child", None, "medium")
+            .unwrap();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        db.close_issue(closed).unwrap();
+        db.add_dependency(blocked, blocker).unwrap();
+
+        let ids: Vec<i64> = db.list_isolated().unwrap().iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![isolated]);
+    }
+
+    #[test]
+    fn test_find_duplicates_normalizes_titles() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("Fix Login Bug", None, "medium").unwrap();
+        let other = db.create_issue("Something else", None, "medium").unwrap();
+        let b = db.create_issue("  fix   login bug ", None, "high").unwrap();
+        let closed = db.create_issue("FIX LOGIN BUG", None, "low").unwrap();
+        db.close_issue(closed).unwrap();
+
+        let groups = db.find_duplicates().unwrap();
+        assert_eq!(groups, vec![vec![a, b]]);
+        assert!(!groups.iter().flatten().any(|&id| id == other));
+    }
+
+    #[test]
+    fn test_status_filter_validation() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Open one", None, "medium").unwrap();
+
+        assert_eq!(db.list_issues(Some("open"), None, None).unwrap()[0].id, id);
+        assert_eq!(db.list_issues(Some("all"), None, None).unwrap().len(), 1);
+        assert!(db
+            .list_issues(Some("closed, archived"), None, None)
+            .unwrap()
+            .is_empty());
+
+        let err = db.list_issues(Some("opne"), None, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid status 'opne'. Must be one of: open, closed, archived, all"
+        );
+        assert!(db.list_issues(Some("open,clsoed"), None, None).is_err());
+        assert!(db.list_issues(Some(" , "), None, None).is_err());
+    }
+
+    #[test]
+    fn test_count_issues_matches_list() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "high").unwrap();
+        db.create_issue("B", None, "high").unwrap();
+        db.create_issue("C", None, "low").unwrap();
+        // Two labels must not count the issue twice
+        db.add_label(a, "ui").unwrap();
+        db.add_label(a, "bug").unwrap();
+
+        let filters = [
+            IssueFilter::default(),
+            IssueFilter {
+                priority: Some("high"),
+                ..Default::default()
+            },
+            IssueFilter {
+                label: Some("ui"),
+                ..Default::default()
+            },
+            IssueFilter {
+                status: Some("closed"),
+                ..Default::default()
+            },
+        ];
+        for filter in &filters {
+            assert_eq!(
+                db.count_issues(filter).unwrap() as usize,
+                db.list_issues_filtered(filter).unwrap().len()
+            );
+        }
+        assert_eq!(db.count_issues(&filters[1]).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_find_issue_by_title() {
+        let (db, _dir) = setup_test_db();
+        let closed = db.create_issue("Deploy", None, "medium").unwrap();
+        db.close_issue(closed).unwrap();
+        assert_eq!(db.find_issue_by_title("deploy", None).unwrap(), None);
+
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let sub = db
+            .create_subissue(parent, "Deploy", None, "medium")
+            .unwrap();
+        assert_eq!(db.find_issue_by_title("Deploy", None).unwrap(), None);
+        assert_eq!(
+            db.find_issue_by_title("deploy", Some(parent)).unwrap(),
+            Some(sub)
+        );
+
+        let id = db.create_issue("Deploy", None, "medium").unwrap();
+        assert_eq!(db.find_issue_by_title("  DEPLOY ", None).unwrap(), Some(id));
+    }
+
+    #[test]
+    fn test_find_duplicates_none() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("One", None, "medium").unwrap();
+        db.create_issue("Two", None, "medium").unwrap();
+
+        assert!(db.find_duplicates().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_and_delete_dangling_dependencies() {
+        let (db, _dir) = setup_test_db();
+
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        db.add_dependency(blocked, blocker).unwrap();
+        assert!(db.find_dangling_dependencies().unwrap().is_empty());
+
+        // Simulate a bad schema state: an edge pointing at a deleted issue
+        db.conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+        db.conn
+            .execute(
+                "INSERT INTO dependencies (blocker_id, blocked_id) VALUES (?1, ?2)",
+                params![9999, blocked],
+            )
+            .unwrap();
+        db.conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+        let dangling = db.find_dangling_dependencies().unwrap();
+        assert_eq!(dangling, vec![(blocked, 9999)]);
+
+        assert_eq!(db.delete_dangling_dependencies().unwrap(), 1);
+        assert!(db.find_dangling_dependencies().unwrap().is_empty());
+        // The valid edge survives
+        assert_eq!(db.get_blockers(blocked).unwrap(), vec![blocker]);
+    }
+
+    // ==================== Priority Tests ====================
+
+    fn five_levels() -> Vec<String> {
+        ["trivial", "minor", "normal", "major", "blocker"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_priority_scale_defaults() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(db.priority_scale().unwrap(), PriorityScale::default());
+        db.check_priority("critical").unwrap();
+    }
+
+    #[test]
+    fn test_custom_priorities_validate() {
+        let (db, _dir) = setup_test_db();
+        db.set_priorities(&five_levels()).unwrap();
+
+        db.create_issue("Crash on start", None, "blocker").unwrap();
+        let id = db.create_issue("Typo", None, "trivial").unwrap();
+        match db.create_issue("Old scheme", None, "medium") {
+            Err(DbError::InvalidPriority { priority, valid }) => {
+                assert_eq!(priority, "medium");
+                assert_eq!(valid, five_levels());
+            }
+            other => panic!("expected InvalidPriority, got {:?}", other),
+        }
+        assert!(db.update_issue(id, None, None, Some("high")).is_err());
+        assert!(db.update_issue(id, None, None, Some("major")).unwrap());
+    }
+
+    #[test]
+    fn test_custom_priorities_sort_blocked() {
+        let (db, _dir) = setup_test_db();
+        db.set_priorities(&five_levels()).unwrap();
+
+        let blocker = db.create_issue("Blocker", None, "normal").unwrap();
+        for level in ["minor", "blocker", "trivial", "major", "normal"] {
+            let id = db.create_issue(level, None, level).unwrap();
+            db.add_dependency(id, blocker).unwrap();
+        }
+
+        let order: Vec<String> = db
+            .list_blocked_with_meta()
+            .unwrap()
+            .into_iter()
+            .map(|m| m.issue.priority)
+            .collect();
+        assert_eq!(
+            order,
+            vec!["blocker", "major", "normal", "minor", "trivial"]
+        );
+    }
+
+    #[test]
+    fn test_set_priorities_rejects_bad_sets() {
+        let (db, _dir) = setup_test_db();
+        assert!(db.set_priorities(&[]).is_err());
+        assert!(db
+            .set_priorities(&["low".to_string(), " ".to_string()])
+            .is_err());
+        assert!(db
+            .set_priorities(&["low".to_string(), "low".to_string()])
+            .is_err());
+
+        // "medium" is still used, so it cannot be dropped
+        db.create_issue("Issue", None, "medium").unwrap();
+        assert!(db.set_priorities(&five_levels()).is_err());
+        assert_eq!(db.priority_scale().unwrap(), PriorityScale::default());
+    }
+
+    // ==================== Sessions Tests ====================
+
+    #[test]
+    fn test_start_and_get_session() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.start_session().unwrap();
+        assert!(id > 0);
+
+        let session = db.get_current_session().unwrap().unwrap();
+        assert_eq!(session.id, id);
+        assert!(session.ended_at.is_none());
+        assert!(session.active_issue_id.is_none());
+    }
+
+    #[test]
+    fn test_end_session() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.start_session().unwrap();
+        db.end_session(id, Some("Handoff notes")).unwrap();
+
+        let current = db.get_current_session().unwrap();
+        assert!(current.is_none());
+
+        let last = db.get_last_session().unwrap().unwrap();
+        assert_eq!(last.id, id);
+        assert!(last.ended_at.is_some());
+        assert_eq!(last.handoff_notes, Some("Handoff notes".to_string()));
+    }
+
+    #[test]
+    fn test_set_session_issue() {
+        let (db, _dir) = setup_test_db();
+
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+        let session_id = db.start_session().unwrap();
+
+        db.set_session_issue(session_id, issue_id).unwrap();
+
+        let session = db.get_current_session().unwrap().unwrap();
+        assert_eq!(session.active_issue_id, Some(issue_id));
+    }
+
+    // ==================== Time Tracking Tests ====================
+
+    #[test]
+    fn test_start_and_stop_timer() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+
+        let timer_id = db.start_timer(id).unwrap();
+        assert!(timer_id > 0);
+
+        let active = db.get_active_timer().unwrap();
+        assert!(active.is_some());
+        assert_eq!(active.unwrap().0, id);
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        db.stop_timer(id).unwrap();
+
+        let active = db.get_active_timer().unwrap();
+        assert!(active.is_none());
+    }
+
+    #[test]
+    fn test_get_total_time() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+
+        // No time tracked yet
+        let total = db.get_total_time(id).unwrap();
+        assert_eq!(total, 0);
+    }
+
+    // ==================== Search Tests ====================
+
+    #[test]
+    fn test_search_limit_keeps_best_ranked() {
+        let (db, _dir) = setup_test_db();
+        let title_old = db.create_issue("Cache warmup", None, "medium").unwrap();
+        let in_comment = db.create_issue("Unrelated", None, "medium").unwrap();
+        db.add_comment(in_comment, "cache misses").unwrap();
+        let in_desc = db
+            .create_issue("Slow pages", Some("needs a cache"), "medium")
+            .unwrap();
+        let title_new = db.create_issue("Cache eviction", None, "medium").unwrap();
+
+        let ids = |limit| -> Vec<i64> {
+            db.search_issues(
+                "cache",
+                &SearchOptions {
+                    limit,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect()
+        };
+
+        assert_eq!(ids(None), vec![title_new, title_old, in_desc, in_comment]);
+        assert_eq!(ids(Some(2)), vec![title_new, title_old]);
+        assert_eq!(ids(Some(3)).len(), 3);
+        assert!(ids(Some(0)).is_empty());
+    }
+
+    #[test]
+    fn test_search_issues_by_title() {
+        let (db, _dir) = setup_test_db();
+
+        db.create_issue("Fix authentication bug", None, "high")
+            .unwrap();
+        db.create_issue("Add dark mode", None, "medium").unwrap();
+        db.create_issue("Auth improvements", None, "low").unwrap();
+
+        let results = db.search_issues("auth", &SearchOptions::default()).unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_search_issues_by_description() {
+        let (db, _dir) = setup_test_db();
+
+        db.create_issue(
+            "Feature A",
+            Some("This relates to authentication"),
+            "medium",
+        )
+        .unwrap();
+        db.create_issue("Feature B", Some("Something else"), "medium")
+            .unwrap();
+
+        let results = db
+            .search_issues("authentication", &SearchOptions::default())
+            .unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_search_issues_by_comment() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Some issue", None, "medium").unwrap();
+        db.add_comment(id, "Found the root cause in authentication module")
+            .unwrap();
+
+        let results = db
+            .search_issues("authentication", &SearchOptions::default())
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, id);
+    }
+
+    #[test]
+    fn test_search_issue_matching_in_several_places_listed_once() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db
+            .create_issue("Token refresh", Some("Refresh the token early"), "medium")
+            .unwrap();
+        db.add_comment(id, "token expiry is 5 minutes").unwrap();
+        db.add_comment(id, "second token comment").unwrap();
+
+        let results = db
+            .search_issues("token", &SearchOptions::default())
+            .unwrap();
+        assert_eq!(results.iter().map(|i| i.id).collect::<Vec<_>>(), vec![id]);
+    }
+
+    // ==================== Relations Tests ====================
+
+    #[test]
+    fn test_add_and_get_relations() {
+        let (db, _dir) = setup_test_db();
+
+        let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
+        let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
+
+        db.add_relation(id1, id2).unwrap();
+
+        let related = db.get_related_issues(id1).unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].id, id2);
+
+        // Bidirectional
+        let related = db.get_related_issues(id2).unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].id, id1);
+    }
+
+    #[test]
+    fn test_relation_to_self_fails() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        let result = db.add_relation(id, id);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_remove_relation() {
+        let (db, _dir) = setup_test_db();
+
+        let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
+        let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
+
+        db.add_relation(id1, id2).unwrap();
+        db.remove_relation(id1, id2).unwrap();
+
+        let related = db.get_related_issues(id1).unwrap();
+        assert!(related.is_empty());
+    }
+
+    // ==================== Milestones Tests ====================
+
+    #[test]
+    fn test_create_and_get_milestone() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_milestone("v1.0", Some("First release")).unwrap();
+        assert!(id > 0);
+
+        let milestone = db.get_milestone(id).unwrap().unwrap();
+        assert_eq!(milestone.name, "v1.0");
+        assert_eq!(milestone.description, Some("First release".to_string()));
+        assert_eq!(milestone.status, "open");
+    }
+
+    #[test]
+    fn test_list_milestones() {
+        let (db, _dir) = setup_test_db();
+
+        db.create_milestone("v1.0", None).unwrap();
+        db.create_milestone("v2.0", None).unwrap();
+
+        let milestones = db.list_milestones(None).unwrap();
+        assert_eq!(milestones.len(), 2);
+    }
+
+    #[test]
+    fn test_add_issue_to_milestone() {
+        let (db, _dir) = setup_test_db();
+
+        let milestone_id = db.create_milestone("v1.0", None).unwrap();
+        let issue_id = db.create_issue("Feature", None, "medium").unwrap();
+
+        db.add_issue_to_milestone(milestone_id, issue_id).unwrap();
+
+        let issues = db.get_milestone_issues(milestone_id).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].id, issue_id);
+
+        let milestone = db.get_issue_milestone(issue_id).unwrap().unwrap();
+        assert_eq!(milestone.id, milestone_id);
+    }
+
+    #[test]
+    fn test_close_milestone() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_milestone("v1.0", None).unwrap();
+        db.close_milestone(id).unwrap();
+
+        let milestone = db.get_milestone(id).unwrap().unwrap();
+        assert_eq!(milestone.status, "closed");
+        assert!(milestone.closed_at.is_some());
+    }
+
+    // ==================== Archive Tests ====================
+
+    #[test]
+    fn test_archive_closed_issue() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test", None, "medium").unwrap();
+        db.close_issue(id).unwrap();
+
+        let archived = db.archive_issue(id).unwrap();
+        assert!(archived);
+
+        let issue = db.get_issue(id).unwrap().unwrap();
+        assert_eq!(issue.status, "archived");
+    }
+
+    #[test]
+    fn test_archive_open_issue_fails() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test", None, "medium").unwrap();
+
+        let archived = db.archive_issue(id).unwrap();
+        assert!(!archived);
+
+        let issue = db.get_issue(id).unwrap().unwrap();
+        assert_eq!(issue.status, "open");
+    }
+
+    #[test]
+    fn test_unarchive_issue() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test", None, "medium").unwrap();
+        db.close_issue(id).unwrap();
+        db.archive_issue(id).unwrap();
+
+        let unarchived = db.unarchive_issue(id).unwrap();
+        assert!(unarchived);
+
+        let issue = db.get_issue(id).unwrap().unwrap();
+        assert_eq!(issue.status, "closed");
+    }
+
+    #[test]
+    fn test_list_issues_hides_archived_by_default() {
+        let (db, _dir) = setup_test_db();
+
+        let open = db.create_issue("Open", None, "medium").unwrap();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        let archived = db.create_issue("Archived", None, "medium").unwrap();
+        db.close_issue(closed).unwrap();
+        db.close_issue(archived).unwrap();
+        db.archive_issue(archived).unwrap();
+
+        let ids: Vec<i64> = db
+            .list_issues(None, None, None)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ids, vec![closed, open]);
+
+        let closed_only = db.list_issues(Some("closed"), None, None).unwrap();
+        assert!(!closed_only.iter().any(|i| i.id == archived));
+
+        let archived_only = db.list_issues(Some("archived"), None, None).unwrap();
+        assert_eq!(archived_only.len(), 1);
+        assert_eq!(archived_only[0].id, archived);
+
+        assert_eq!(db.list_issues(Some("all"), None, None).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_archived_blocker_does_not_block() {
+        let (db, _dir) = setup_test_db();
+
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        db.add_dependency(blocked, blocker).unwrap();
+        db.close_issue(blocker).unwrap();
+        db.archive_issue(blocker).unwrap();
+
+        let ready = db.list_ready_issues(false).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id, blocked);
+        assert!(db.list_blocked_with_meta().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_iter_issues_counts_across_pages() {
+        let (db, _dir) = setup_test_db();
+        let total = ISSUE_PAGE_SIZE * 2 + 3;
+        for i in 0..total {
+            db.create_issue(&format!("Issue {}", i), None, "medium")
+                .unwrap();
+        }
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        db.close_issue(closed).unwrap();
+
+        let mut count = 0;
+        let mut last_id = i64::MAX;
+        for issue in db.iter_issues(IssueFilter {
+            status: Some("open"),
+            ..Default::default()
+        }) {
+            let issue = issue.unwrap();
+            assert!(issue.id < last_id, "ids should be strictly descending");
+            last_id = issue.id;
+            count += 1;
+        }
+        assert_eq!(count, total);
+        assert_eq!(
+            db.iter_issues(IssueFilter {
+                status: Some("all"),
+                ..Default::default()
+            })
+            .count(),
+            total + 1
+        );
     }
 
-    // ==================== Search Tests ====================
-
     #[test]
-    fn test_search_issues_by_title() {
+    fn test_iter_issues_matches_list_issues() {
         let (db, _dir) = setup_test_db();
+        for i in 0..5 {
+            db.create_issue(&format!("Issue {}", i), None, "medium")
+                .unwrap();
+        }
 
-        db.create_issue("Fix authentication bug", None, "high")
+        let streamed: Vec<Issue> = db
+            .iter_issues(IssueFilter::default())
+            .collect::<Result<Vec<_>>>()
             .unwrap();
-        db.create_issue("Add dark mode", None, "medium").unwrap();
-        db.create_issue("Auth improvements", None, "low").unwrap();
-
-        let results = db.search_issues("auth").unwrap();
-        assert_eq!(results.len(), 2);
+        assert_eq!(streamed, db.list_issues(None, None, None).unwrap());
     }
 
     #[test]
-    fn test_search_issues_by_description() {
+    fn test_save_and_list_templates() {
         let (db, _dir) = setup_test_db();
-
-        db.create_issue(
-            "Feature A",
-            Some("This relates to authentication"),
-            "medium",
+        let subs = vec!["Plan".to_string(), "Retro".to_string()];
+        db.save_template(
+            "sprint",
+            "Sprint checklist",
+            Some("Every two weeks"),
+            "high",
+            &subs,
         )
         .unwrap();
-        db.create_issue("Feature B", Some("Something else"), "medium")
+        db.save_template("bugfix", "Bug triage", None, "medium", &[])
             .unwrap();
 
-        let results = db.search_issues("authentication").unwrap();
-        assert_eq!(results.len(), 1);
+        let templates = db.list_templates().unwrap();
+        let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["bugfix", "sprint"]);
+
+        let sprint = db.get_template("sprint").unwrap().unwrap();
+        assert_eq!(sprint.title, "Sprint checklist");
+        assert_eq!(sprint.description.as_deref(), Some("Every two weeks"));
+        assert_eq!(sprint.priority, "high");
+        assert_eq!(sprint.subissues, subs);
     }
 
     #[test]
-    fn test_search_issues_by_comment() {
+    fn test_save_template_replaces_existing() {
         let (db, _dir) = setup_test_db();
-
-        let id = db.create_issue("Some issue", None, "medium").unwrap();
-        db.add_comment(id, "Found the root cause in authentication module")
+        db.save_template("t", "Old", None, "low", &["A".to_string(), "B".to_string()])
+            .unwrap();
+        db.save_template("t", "New", None, "medium", &["C".to_string()])
             .unwrap();
 
-        let results = db.search_issues("authentication").unwrap();
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].id, id);
+        let t = db.get_template("t").unwrap().unwrap();
+        assert_eq!(t.title, "New");
+        assert_eq!(t.subissues, vec!["C".to_string()]);
     }
 
-    // ==================== Relations Tests ====================
-
     #[test]
-    fn test_add_and_get_relations() {
+    fn test_instantiate_template_with_two_subissues() {
         let (db, _dir) = setup_test_db();
+        let subs = vec!["Plan".to_string(), "Retro".to_string()];
+        db.save_template("sprint", "Sprint checklist", None, "high", &subs)
+            .unwrap();
 
-        let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
-        let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
+        let parent = db.instantiate_template("sprint").unwrap();
+        let issue = db.get_issue(parent).unwrap().unwrap();
+        assert_eq!(issue.title, "Sprint checklist");
+        assert_eq!(issue.priority, "high");
 
-        db.add_relation(id1, id2).unwrap();
+        let children = db.get_subissues(parent).unwrap();
+        let titles: Vec<&str> = children.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Plan", "Retro"]);
 
-        let related = db.get_related_issues(id1).unwrap();
-        assert_eq!(related.len(), 1);
-        assert_eq!(related[0].id, id2);
+        // Instantiating again creates a fresh copy
+        let second = db.instantiate_template("sprint").unwrap();
+        assert_ne!(second, parent);
+        assert_eq!(db.get_subissues(second).unwrap().len(), 2);
+    }
 
-        // Bidirectional
-        let related = db.get_related_issues(id2).unwrap();
-        assert_eq!(related.len(), 1);
-        assert_eq!(related[0].id, id1);
+    #[test]
+    fn test_template_from_issue_roundtrip() {
+        let (db, _dir) = setup_test_db();
+        let release = db
+            .create_issue("Release", Some("Ship it"), "critical")
+            .unwrap();
+        let build = db.create_subissue(release, "Build", None, "low").unwrap();
+        db.create_subissue(release, "Announce", None, "low")
+            .unwrap();
+        // Only immediate subissues are captured
+        db.create_subissue(build, "Tag", None, "low").unwrap();
+
+        db.template_from_issue(release, "release").unwrap();
+        let template = db.get_template("release").unwrap().unwrap();
+        assert_eq!(template.subissues, vec!["Build", "Announce"]);
+
+        let copy = db.instantiate_template("release").unwrap();
+        let issue = db.get_issue(copy).unwrap().unwrap();
+        assert_eq!(issue.title, "Release");
+        assert_eq!(issue.description.as_deref(), Some("Ship it"));
+        assert_eq!(issue.priority, "critical");
+        let titles: Vec<String> = db
+            .get_subissues(copy)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.title)
+            .collect();
+        assert_eq!(titles, vec!["Build", "Announce"]);
+
+        assert!(matches!(
+            db.template_from_issue(999, "missing"),
+            Err(DbError::NotFound(999))
+        ));
     }
 
     #[test]
-    fn test_relation_to_self_fails() {
+    fn test_instantiate_missing_template() {
         let (db, _dir) = setup_test_db();
+        assert!(matches!(
+            db.instantiate_template("nope"),
+            Err(DbError::TemplateNotFound(_))
+        ));
+        assert!(!db.delete_template("nope").unwrap());
+    }
 
+    #[test]
+    fn test_set_assignee_blank_is_unassigned() {
+        let (db, _dir) = setup_test_db();
         let id = db.create_issue("Issue", None, "medium").unwrap();
 
-        let result = db.add_relation(id, id);
-        assert!(result.is_err());
+        assert!(db.set_assignee(id, Some("alice")).unwrap());
+        assert_eq!(db.get_assignee(id).unwrap(), Some("alice".to_string()));
+        db.set_assignee(id, Some("  ")).unwrap();
+        assert_eq!(db.get_assignee(id).unwrap(), None);
+        assert!(!db.set_assignee(99999, Some("alice")).unwrap());
     }
 
     #[test]
-    fn test_remove_relation() {
+    fn test_resolve_id_prefix() {
         let (db, _dir) = setup_test_db();
+        for n in 1..=12 {
+            db.create_issue(&format!("Issue {}", n), None, "medium")
+                .unwrap();
+        }
+        db.conn
+            .execute(
+                "INSERT INTO issues (id, title, created_at, updated_at) \
+                 VALUES (120, 'Far ahead', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z')",
+                [],
+            )
+            .unwrap();
 
-        let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
-        let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
-
-        db.add_relation(id1, id2).unwrap();
-        db.remove_relation(id1, id2).unwrap();
+        // An exact match wins over #120, which also starts with 12
+        assert_eq!(db.resolve_id_prefix(12).unwrap(), 12);
 
-        let related = db.get_related_issues(id1).unwrap();
-        assert!(related.is_empty());
+        db.delete_issue(12).unwrap();
+        assert_eq!(db.resolve_id_prefix(12).unwrap(), 120);
+        // Plain resolution never guesses
+        assert_eq!(db.resolve_issue(&IssueRef::Id(12)).unwrap(), 12);
+        // With no match at all the id is passed through for the caller to report
+        assert_eq!(db.resolve_id_prefix(99).unwrap(), 99);
     }
 
-    // ==================== Milestones Tests ====================
+    #[test]
+    fn test_resolve_id_prefix_ambiguous() {
+        let (db, _dir) = setup_test_db();
+        for n in 1..=13 {
+            db.create_issue(&format!("Issue {}", n), None, "medium")
+                .unwrap();
+        }
+        db.delete_issue(1).unwrap();
+
+        match db.resolve_id_prefix(1) {
+            Err(DbError::AmbiguousId { prefix, candidates }) => {
+                assert_eq!(prefix, 1);
+                assert_eq!(candidates, vec![10, 11, 12, 13]);
+            }
+            other => panic!("expected an ambiguous id, got {:?}", other),
+        }
+        let err = db.resolve_id_prefix(1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Issue id 1 is ambiguous; it could be #10, #11, #12, #13"
+        );
+    }
 
     #[test]
-    fn test_create_and_get_milestone() {
+    fn test_set_url() {
         let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Linked", None, "medium").unwrap();
+        assert_eq!(db.get_url(id).unwrap(), None);
+
+        let url = "https://github.com/RhoMancer/chainlink/pull/12";
+        assert!(db.set_url(id, Some(url)).unwrap());
+        assert_eq!(db.get_url(id).unwrap().as_deref(), Some(url));
+        assert!(db.set_url(id, Some(" HTTP://example.com ")).unwrap());
+        assert_eq!(
+            db.get_url(id).unwrap().as_deref(),
+            Some("HTTP://example.com")
+        );
 
-        let id = db.create_milestone("v1.0", Some("First release")).unwrap();
-        assert!(id > 0);
+        assert!(db.set_url(id, None).unwrap());
+        assert_eq!(db.get_url(id).unwrap(), None);
+        assert!(!db.set_url(99999, Some(url)).unwrap());
+    }
 
-        let milestone = db.get_milestone(id).unwrap().unwrap();
-        assert_eq!(milestone.name, "v1.0");
-        assert_eq!(milestone.description, Some("First release".to_string()));
-        assert_eq!(milestone.status, "open");
+    #[test]
+    fn test_set_url_rejects_other_schemes() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Linked", None, "medium").unwrap();
+
+        for bad in [
+            "ftp://example.com",
+            "javascript:alert(1)",
+            "example.com",
+            "https://",
+            "https://exa mple.com",
+            "https://example.com/\x1b[2J",
+        ] {
+            assert!(
+                matches!(db.set_url(id, Some(bad)), Err(DbError::Invalid(_))),
+                "{} should be rejected",
+                bad
+            );
+        }
+        assert_eq!(db.get_url(id).unwrap(), None);
     }
 
     #[test]
-    fn test_list_milestones() {
+    fn test_set_slug_and_resolve() {
         let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Login fails", None, "medium").unwrap();
+        let other = db.create_issue("Other", None, "medium").unwrap();
+
+        assert!(db.set_slug(id, "login-bug").unwrap());
+        assert_eq!(db.get_slug(id).unwrap(), Some("login-bug".to_string()));
+        assert_eq!(
+            db.resolve_issue(&IssueRef::Slug("login-bug".to_string()))
+                .unwrap(),
+            id
+        );
+        assert_eq!(db.resolve_issue(&IssueRef::Id(other)).unwrap(), other);
+        assert!(matches!(
+            db.resolve_issue(&IssueRef::Slug("missing".to_string())),
+            Err(DbError::SlugNotFound(_))
+        ));
+
+        // Setting the same slug again is fine; giving it to another issue is not
+        assert!(db.set_slug(id, "login-bug").unwrap());
+        assert!(matches!(
+            db.set_slug(other, "login-bug"),
+            Err(DbError::Invalid(_))
+        ));
+        assert_eq!(db.get_slug(other).unwrap(), None);
+        assert!(matches!(
+            db.set_slug(other, "Not A Slug"),
+            Err(DbError::Invalid(_))
+        ));
+
+        assert!(db.clear_slug(id).unwrap());
+        assert_eq!(db.get_slug(id).unwrap(), None);
+        assert!(db.set_slug(other, "login-bug").unwrap());
+        assert!(!db.set_slug(99999, "ghost").unwrap());
+    }
 
-        db.create_milestone("v1.0", None).unwrap();
-        db.create_milestone("v2.0", None).unwrap();
+    #[test]
+    fn test_list_issues_filtered_by_status_set() {
+        let (db, _dir) = setup_test_db();
+        let open = db.create_issue("Open", None, "medium").unwrap();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        let archived = db.create_issue("Archived", None, "medium").unwrap();
+        db.close_issue(closed).unwrap();
+        db.close_issue(archived).unwrap();
+        db.archive_issue(archived).unwrap();
+
+        let filter = |status| IssueFilter {
+            status: Some(status),
+            ..Default::default()
+        };
+        let ids = |status| -> Vec<i64> {
+            let mut ids: Vec<i64> = db
+                .list_issues_filtered(&filter(status))
+                .unwrap()
+                .iter()
+                .map(|i| i.id)
+                .collect();
+            ids.sort();
+            ids
+        };
 
-        let milestones = db.list_milestones(None).unwrap();
-        assert_eq!(milestones.len(), 2);
+        assert_eq!(ids("open,closed"), vec![open, closed]);
+        assert_eq!(ids(" closed , archived "), vec![closed, archived]);
+        assert_eq!(ids("open,all"), vec![open, closed, archived]);
+        assert_eq!(ids("archived"), vec![archived]);
     }
 
     #[test]
-    fn test_add_issue_to_milestone() {
+    fn test_list_ready_for_assignee() {
         let (db, _dir) = setup_test_db();
+        let mine = db.create_issue("Mine", None, "medium").unwrap();
+        let mine_blocked = db.create_issue("Mine, blocked", None, "high").unwrap();
+        let theirs = db.create_issue("Theirs", None, "medium").unwrap();
+        let nobody = db.create_issue("Nobody's", None, "low").unwrap();
+        let blocker = db.create_issue("Blocker", None, "low").unwrap();
+        db.set_assignee(mine, Some("me")).unwrap();
+        db.set_assignee(mine_blocked, Some("me")).unwrap();
+        db.set_assignee(theirs, Some("sam")).unwrap();
+        db.add_dependency(mine_blocked, blocker).unwrap();
+
+        let ids = |assignee: &str| -> Vec<i64> {
+            let mut ids: Vec<i64> = db
+                .list_ready_for_assignee(false, assignee)
+                .unwrap()
+                .iter()
+                .map(|m| m.issue.id)
+                .collect();
+            ids.sort_unstable();
+            ids
+        };
+        assert_eq!(ids("me"), vec![mine]);
+        assert_eq!(ids("sam"), vec![theirs]);
+        assert_eq!(ids(UNASSIGNED), vec![nobody, blocker]);
+        assert!(ids("carol").is_empty());
 
-        let milestone_id = db.create_milestone("v1.0", None).unwrap();
-        let issue_id = db.create_issue("Feature", None, "medium").unwrap();
+        db.remove_dependency(mine_blocked, blocker).unwrap();
+        assert_eq!(ids("me"), vec![mine, mine_blocked]);
+    }
 
-        db.add_issue_to_milestone(milestone_id, issue_id).unwrap();
+    #[test]
+    fn test_list_issues_filtered_by_assignee() {
+        let (db, _dir) = setup_test_db();
+        let alice = db.create_issue("Alice's", None, "medium").unwrap();
+        let bob = db.create_issue("Bob's", None, "medium").unwrap();
+        let nobody = db.create_issue("Nobody's", None, "medium").unwrap();
+        let blank = db.create_issue("Blank", None, "medium").unwrap();
+        db.set_assignee(alice, Some("alice")).unwrap();
+        db.set_assignee(bob, Some("bob")).unwrap();
+        // Rows written by older tools may hold an empty string rather than NULL
+        db.conn
+            .execute("UPDATE issues SET assignee = '' WHERE id = ?1", [blank])
+            .unwrap();
 
-        let issues = db.get_milestone_issues(milestone_id).unwrap();
-        assert_eq!(issues.len(), 1);
-        assert_eq!(issues[0].id, issue_id);
+        let ids = |assignee: Option<&str>| -> Vec<i64> {
+            db.list_issues_filtered(&IssueFilter {
+                assignee,
+                ..Default::default()
+            })
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect()
+        };
 
-        let milestone = db.get_issue_milestone(issue_id).unwrap().unwrap();
-        assert_eq!(milestone.id, milestone_id);
+        assert_eq!(ids(Some("alice")), vec![alice]);
+        assert_eq!(ids(Some(UNASSIGNED)), vec![blank, nobody]);
+        assert_eq!(ids(None), vec![blank, nobody, bob, alice]);
+        assert!(ids(Some("carol")).is_empty());
     }
 
     #[test]
-    fn test_close_milestone() {
+    fn test_set_and_clear_estimate() {
         let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
 
-        let id = db.create_milestone("v1.0", None).unwrap();
-        db.close_milestone(id).unwrap();
-
-        let milestone = db.get_milestone(id).unwrap().unwrap();
-        assert_eq!(milestone.status, "closed");
-        assert!(milestone.closed_at.is_some());
+        assert_eq!(db.get_estimate(id).unwrap(), None);
+        assert!(db.set_estimate(id, Some(5)).unwrap());
+        assert_eq!(db.get_estimate(id).unwrap(), Some(5));
+        assert!(db.set_estimate(id, None).unwrap());
+        assert_eq!(db.get_estimate(id).unwrap(), None);
+        assert!(!db.set_estimate(99999, Some(1)).unwrap());
     }
 
-    // ==================== Archive Tests ====================
+    #[test]
+    fn test_clear_dependencies() {
+        let (db, _dir) = setup_test_db();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let original = db.create_issue("Original", None, "medium").unwrap();
+        let other = db.create_issue("Other", None, "medium").unwrap();
+        db.add_dependency(blocked, a).unwrap();
+        db.add_dependency(blocked, b).unwrap();
+        db.add_dependency_of_kind(blocked, original, "duplicates")
+            .unwrap();
+        db.add_dependency(other, a).unwrap();
+        // An edge where the issue is the blocker is not its dependency
+        db.add_dependency(other, blocked).unwrap();
+
+        assert_eq!(db.clear_dependencies(blocked).unwrap(), 2);
+        assert!(db.get_blockers(blocked).unwrap().is_empty());
+        assert_eq!(db.get_duplicate_of(blocked).unwrap(), vec![original]);
+        let mut remaining = db.get_blockers(other).unwrap();
+        remaining.sort();
+        assert_eq!(remaining, vec![blocked, a]);
+
+        assert_eq!(db.clear_dependencies(blocked).unwrap(), 0);
+    }
 
     #[test]
-    fn test_archive_closed_issue() {
+    fn test_time_to_close_uses_final_close() {
         let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Slow one", None, "medium").unwrap();
+        let quick = db.create_issue("Quick one", None, "medium").unwrap();
+        assert_eq!(db.time_to_close(id).unwrap(), None);
+        assert_eq!(db.average_time_to_close().unwrap(), None);
 
-        let id = db.create_issue("Test", None, "medium").unwrap();
         db.close_issue(id).unwrap();
+        db.reopen_issue(id).unwrap();
+        db.close_issue(id).unwrap();
+        db.close_issue(quick).unwrap();
 
-        let archived = db.archive_issue(id).unwrap();
-        assert!(archived);
+        // Pin the history: created day 0, closed day 1, reopened day 2, closed day 5
+        let t0 = parse_timestamp("2026-01-01T00:00:00+00:00").unwrap();
+        let at = |days: i64| (t0 + chrono::Duration::days(days)).to_rfc3339();
+        let set = |sql: &str, params: &[&dyn rusqlite::ToSql]| {
+            db.conn.execute(sql, params).unwrap();
+        };
+        let events: Vec<i64> = {
+            let mut stmt = db
+                .conn
+                .prepare("SELECT id FROM issue_events WHERE issue_id = ?1 ORDER BY id")
+                .unwrap();
+            stmt.query_map([id], |row| row.get(0))
+                .unwrap()
+                .collect::<std::result::Result<_, _>>()
+                .unwrap()
+        };
+        assert_eq!(events.len(), 4);
+        for (event, day) in events.iter().zip([0, 1, 2, 5]) {
+            set(
+                "UPDATE issue_events SET created_at = ?1 WHERE id = ?2",
+                &[&at(day), event],
+            );
+        }
+        set(
+            "UPDATE issue_events SET created_at = ?1 WHERE issue_id = ?2 AND kind = 'created'",
+            &[&at(0), &quick],
+        );
+        set(
+            "UPDATE issue_events SET created_at = ?1 WHERE issue_id = ?2 AND kind = 'status'",
+            &[&at(1), &quick],
+        );
 
-        let issue = db.get_issue(id).unwrap().unwrap();
-        assert_eq!(issue.status, "archived");
+        assert_eq!(
+            db.time_to_close(id).unwrap(),
+            Some(chrono::Duration::days(5))
+        );
+        assert_eq!(
+            db.average_time_to_close().unwrap(),
+            Some(chrono::Duration::days(3))
+        );
+
+        // Archiving and unarchiving is not a new close
+        db.archive_issue(id).unwrap();
+        db.unarchive_issue(id).unwrap();
+        assert_eq!(
+            db.time_to_close(id).unwrap(),
+            Some(chrono::Duration::days(5))
+        );
+
+        db.reopen_issue(id).unwrap();
+        assert_eq!(db.time_to_close(id).unwrap(), None);
     }
 
     #[test]
-    fn test_archive_open_issue_fails() {
+    fn test_resolution_cleared_on_reopen() {
         let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Bug", None, "high").unwrap();
+        assert_eq!(db.get_resolution(id).unwrap(), None);
 
-        let id = db.create_issue("Test", None, "medium").unwrap();
+        db.close_issue(id).unwrap();
+        assert!(db.set_resolution(id, "  fixed in abc123\r\n").unwrap());
+        assert_eq!(
+            db.get_resolution(id).unwrap().as_deref(),
+            Some("fixed in abc123")
+        );
+        assert!(matches!(
+            db.set_resolution(id, "   "),
+            Err(DbError::Invalid(_))
+        ));
+        assert!(!db.set_resolution(99999, "note").unwrap());
+
+        db.reopen_issue(id).unwrap();
+        assert_eq!(db.get_resolution(id).unwrap(), None);
+    }
 
-        let archived = db.archive_issue(id).unwrap();
-        assert!(!archived);
+    #[test]
+    fn test_set_and_clear_due_date() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+
+        assert_eq!(db.get_due_date(id).unwrap(), None);
+        assert!(db.set_due_date(id, Some(date)).unwrap());
+        assert_eq!(db.get_due_date(id).unwrap(), Some(date));
+        assert!(db.set_due_date(id, None).unwrap());
+        assert_eq!(db.get_due_date(id).unwrap(), None);
+        assert!(!db.set_due_date(99999, Some(date)).unwrap());
+    }
 
-        let issue = db.get_issue(id).unwrap().unwrap();
-        assert_eq!(issue.status, "open");
+    #[test]
+    fn test_rank_ready_overdue_blocker_outranks_isolated() {
+        let (db, _dir) = setup_test_db();
+        let now = Utc::now();
+        let isolated = db.create_issue("Isolated", None, "low").unwrap();
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let waiting = db.create_issue("Waiting", None, "low").unwrap();
+        db.add_dependency(waiting, blocker).unwrap();
+        db.set_due_date(blocker, Some(now.date_naive() - chrono::Duration::days(2)))
+            .unwrap();
+
+        let ranked = db.rank_ready(now).unwrap();
+        let ids: Vec<i64> = ranked.iter().map(|(i, _)| i.id).collect();
+        assert_eq!(ids, vec![blocker, isolated]);
+        assert!(ranked[0].1 > ranked[1].1);
     }
 
     #[test]
-    fn test_unarchive_issue() {
+    fn test_score_ready_components() {
         let (db, _dir) = setup_test_db();
+        let now = Utc::now();
+        let today = now.date_naive();
+        let id = db.create_issue("Scored", None, "medium").unwrap();
+        let issue = db.get_issue(id).unwrap().unwrap();
 
-        let id = db.create_issue("Test", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
-        db.archive_issue(id).unwrap();
+        let bare = db.score_ready(&issue, now).unwrap();
+        assert_eq!(bare.priority, 20.0);
+        assert_eq!(bare.total(), 20.0);
 
-        let unarchived = db.unarchive_issue(id).unwrap();
-        assert!(unarchived);
+        db.set_due_date(id, Some(today + chrono::Duration::days(3)))
+            .unwrap();
+        db.set_estimate(id, Some(1)).unwrap();
+        let a = db.create_subissue(id, "A", None, "low").unwrap();
+        db.create_subissue(id, "B", None, "low").unwrap();
+        db.close_issue(a).unwrap();
+        let dependent = db.create_issue("Dependent", None, "low").unwrap();
+        db.add_dependency(dependent, id).unwrap();
+
+        let score = db.score_ready(&issue, now).unwrap();
+        assert_eq!(score.due, 5.0);
+        assert_eq!(score.unblocks, 5.0);
+        assert_eq!(score.progress, 5.0);
+        assert_eq!(score.effort, 0.5);
+        assert_eq!(score.total(), 35.5);
+
+        // Due today counts the same as overdue
+        db.set_due_date(id, Some(today)).unwrap();
+        assert_eq!(db.score_ready(&issue, now).unwrap().due, 20.0);
+    }
 
-        let issue = db.get_issue(id).unwrap().unwrap();
-        assert_eq!(issue.status, "closed");
+    #[test]
+    fn test_rank_ready_ties_keep_id_order() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+
+        let ids: Vec<i64> = db
+            .rank_ready(Utc::now())
+            .unwrap()
+            .iter()
+            .map(|(i, _)| i.id)
+            .collect();
+        assert_eq!(ids, vec![first, second]);
+    }
+
+    #[test]
+    fn test_subtree_estimate_rollup() {
+        let (db, _dir) = setup_test_db();
+        let root = db.create_issue("Root", None, "medium").unwrap();
+        let child_a = db.create_subissue(root, "A", None, "medium").unwrap();
+        let child_b = db.create_subissue(root, "B", None, "medium").unwrap();
+        let grandchild = db.create_subissue(child_a, "A1", None, "medium").unwrap();
+        let unrelated = db.create_issue("Other", None, "medium").unwrap();
+
+        db.set_estimate(root, Some(1)).unwrap();
+        db.set_estimate(child_a, Some(2)).unwrap();
+        // child_b has no estimate and counts as 0
+        db.set_estimate(grandchild, Some(4)).unwrap();
+        db.set_estimate(unrelated, Some(100)).unwrap();
+
+        assert_eq!(db.subtree_estimate(root).unwrap(), 7);
+        assert_eq!(db.subtree_estimate(child_a).unwrap(), 6);
+        assert_eq!(db.subtree_estimate(child_b).unwrap(), 0);
+        assert_eq!(db.subtree_estimate(99999).unwrap(), 0);
     }
 
     #[test]
@@ -1731,7 +5844,9 @@ mod tests {
 
         // Attempt injection in search
         let malicious = "%'; DROP TABLE issues; --";
-        let results = db.search_issues(malicious).unwrap();
+        let results = db
+            .search_issues(malicious, &SearchOptions::default())
+            .unwrap();
 
         // Should return empty results, not crash
         assert!(results.is_empty());
@@ -1774,7 +5889,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
 
         let long_title = "a".repeat(10000);
-        let long_desc = "b".repeat(100000);
+        let long_desc = "b".repeat(DEFAULT_MAX_DESCRIPTION_BYTES);
 
         let id = db
             .create_issue(&long_title, Some(&long_desc), "medium")
@@ -1782,7 +5897,10 @@ mod tests {
 
         let issue = db.get_issue(id).unwrap().unwrap();
         assert_eq!(issue.title.len(), 10000);
-        assert_eq!(issue.description.unwrap().len(), 100000);
+        assert_eq!(
+            issue.description.unwrap().len(),
+            DEFAULT_MAX_DESCRIPTION_BYTES
+        );
     }
 
     #[test]
@@ -1848,9 +5966,71 @@ mod tests {
     fn test_empty_title() {
         let (db, _dir) = setup_test_db();
 
-        let id = db.create_issue("", None, "medium").unwrap();
+        let err = db.create_issue("", None, "medium").unwrap_err();
+        assert_eq!(err.to_string(), "Issue title cannot be empty");
+        assert!(db.list_issues(Some("all"), None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_whitespace_only_title() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+
+        assert!(matches!(
+            db.create_issue("  \t\n ", None, "medium"),
+            Err(DbError::Invalid(_))
+        ));
+        assert!(db.create_subissue(parent, "   ", None, "medium").is_err());
+        assert!(db.update_issue(parent, Some(" "), None, None).is_err());
+        assert_eq!(db.get_issue(parent).unwrap().unwrap().title, "Parent");
+        assert_eq!(db.list_issues(Some("all"), None, None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_title_trimmed() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("  Fix login \n", None, "medium").unwrap();
+        assert_eq!(db.get_issue(id).unwrap().unwrap().title, "Fix login");
+
+        db.update_issue(id, Some("\tFix logout  "), None, None)
+            .unwrap();
+        assert_eq!(db.get_issue(id).unwrap().unwrap().title, "Fix logout");
+    }
+
+    #[test]
+    fn test_description_line_endings_normalized() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db
+            .create_issue("Issue", Some("one\r\ntwo\rthree\n"), "medium")
+            .unwrap();
+        let issue = db.get_issue(id).unwrap().unwrap();
+        assert_eq!(issue.description.as_deref(), Some("one\ntwo\nthree\n"));
+
+        db.update_issue(id, None, Some("a\r\nb"), None).unwrap();
         let issue = db.get_issue(id).unwrap().unwrap();
-        assert_eq!(issue.title, "");
+        assert_eq!(issue.description.as_deref(), Some("a\nb"));
+    }
+
+    #[test]
+    fn test_description_too_long_rejected() {
+        let (mut db, _dir) = setup_test_db();
+        let big = "x".repeat(DEFAULT_MAX_DESCRIPTION_BYTES + 1);
+
+        let err = db.create_issue("Paste", Some(&big), "medium").unwrap_err();
+        assert!(matches!(err, DbError::Invalid(_)));
+        assert!(err.to_string().contains("limit is 65536"));
+        assert!(db.list_issues(Some("all"), None, None).unwrap().is_empty());
+
+        let at_limit = "x".repeat(DEFAULT_MAX_DESCRIPTION_BYTES);
+        let id = db.create_issue("Fits", Some(&at_limit), "medium").unwrap();
+
+        db.set_max_description_bytes(8);
+        assert!(db.update_issue(id, None, Some("123456789"), None).is_err());
+        assert!(db.update_issue(id, None, Some("12345678"), None).unwrap());
+        // The cap applies after normalization, so CRLF pairs count once
+        assert!(db.update_issue(id, None, Some("1234\r\n56"), None).unwrap());
     }
 
     #[test]
@@ -1952,6 +6132,150 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_after_edge_orders_next_without_blocking() {
+        let (db, _dir) = setup_test_db();
+        let later = db.create_issue("Later", None, "medium").unwrap();
+        let earlier = db.create_issue("Earlier", None, "medium").unwrap();
+        let now = Utc::now();
+
+        // Equal scores keep id order, so `later` ranks first until the hint exists
+        let ranked: Vec<i64> = db.rank_ready(now).unwrap().iter().map(|r| r.0.id).collect();
+        assert_eq!(ranked, vec![later, earlier]);
+
+        assert!(db.add_dependency_of_kind(later, earlier, "after").unwrap());
+        let ready: Vec<i64> = db
+            .list_ready_issues(false)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert!(ready.contains(&later) && ready.contains(&earlier));
+        assert!(db.list_blocked_with_meta().unwrap().is_empty());
+        assert!(db.get_blockers(later).unwrap().is_empty());
+
+        let ranked: Vec<i64> = db.rank_ready(now).unwrap().iter().map(|r| r.0.id).collect();
+        assert_eq!(ranked, vec![earlier, later]);
+        assert_eq!(
+            db.score_ready(&db.require_issue(later).unwrap(), now)
+                .unwrap()
+                .order,
+            -5.0
+        );
+
+        // Once the earlier issue is closed the hint no longer counts
+        db.close_issue(earlier).unwrap();
+        assert_eq!(db.count_open_predecessors(later).unwrap(), 0);
+
+        assert!(matches!(
+            db.add_dependency_of_kind(later, later, "after"),
+            Err(DbError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_would_unblock_sole_blocker() {
+        let (db, _dir) = setup_test_db();
+        let candidate = db.create_issue("Candidate", None, "medium").unwrap();
+        let done = db.create_issue("Done", None, "medium").unwrap();
+        let target = db.create_issue("Target", None, "medium").unwrap();
+        db.add_dependency(target, candidate).unwrap();
+        db.add_dependency(target, done).unwrap();
+        db.close_issue(done).unwrap();
+
+        // Closed blockers don't count
+        assert!(db.would_unblock(candidate, target).unwrap());
+        assert!(!db.would_unblock(done, target).unwrap());
+
+        db.close_issue(target).unwrap();
+        assert!(!db.would_unblock(candidate, target).unwrap());
+        assert!(matches!(
+            db.would_unblock(candidate, 999),
+            Err(DbError::NotFound(999))
+        ));
+    }
+
+    #[test]
+    fn test_would_unblock_still_blocked_by_others() {
+        let (db, _dir) = setup_test_db();
+        let candidate = db.create_issue("Candidate", None, "medium").unwrap();
+        let other = db.create_issue("Other", None, "medium").unwrap();
+        let middle = db.create_issue("Middle", None, "medium").unwrap();
+        let target = db.create_issue("Target", None, "medium").unwrap();
+        db.add_dependency(target, candidate).unwrap();
+        db.add_dependency(target, other).unwrap();
+
+        assert!(!db.would_unblock(candidate, target).unwrap());
+        assert_eq!(db.open_blockers(target).unwrap(), vec![candidate, other]);
+
+        // Only transitively: candidate -> middle -> target leaves middle open
+        db.remove_dependency(target, candidate).unwrap();
+        db.remove_dependency(target, other).unwrap();
+        db.add_dependency(target, middle).unwrap();
+        db.add_dependency(middle, candidate).unwrap();
+        assert!(!db.would_unblock(candidate, target).unwrap());
+        assert!(db.would_unblock(middle, target).unwrap());
+    }
+
+    #[test]
+    fn test_get_blockers_for_matches_get_blockers() {
+        let (db, _dir) = setup_test_db();
+        let ids: Vec<i64> = (0..6)
+            .map(|n| {
+                db.create_issue(&format!("Issue {}", n), None, "medium")
+                    .unwrap()
+            })
+            .collect();
+        db.add_dependency(ids[0], ids[3]).unwrap();
+        db.add_dependency(ids[0], ids[1]).unwrap();
+        db.add_dependency(ids[2], ids[1]).unwrap();
+        db.add_dependency(ids[4], ids[5]).unwrap();
+        // Relations are not blockers
+        db.add_relation(ids[1], ids[5]).unwrap();
+
+        let batch = db.get_blockers_for(&ids).unwrap();
+        assert_eq!(batch.len(), ids.len());
+        for id in &ids {
+            let mut single = db.get_blockers(*id).unwrap();
+            single.sort_unstable();
+            assert_eq!(batch[id], single, "blockers of #{}", id);
+        }
+        assert!(db.get_blockers_for(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_info_reports_schema_and_count() {
+        let (db, _dir) = setup_test_db();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        db.close_issue(closed).unwrap();
+        db.create_issue("Open", None, "medium").unwrap();
+
+        let info = db.info().unwrap();
+        assert_eq!(info.schema_version, SCHEMA_VERSION);
+        assert_eq!(info.issue_count, 2);
+        assert!(!info.journal_mode.is_empty());
+        assert!(info.sqlite_version.starts_with('3'));
+    }
+
+    #[test]
+    fn test_open_creates_missing_parent_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("a").join("b").join("issues.db");
+
+        let db = Database::open(&db_path).unwrap();
+        db.create_issue("First", None, "medium").unwrap();
+        assert!(db_path.is_file());
+    }
+
+    #[test]
+    fn test_open_directory_path_is_clear_error() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let err = Database::open(dir.path()).err().unwrap();
+        assert!(matches!(err, DbError::Storage { .. }));
+        assert!(err.to_string().contains("path is a directory"));
+    }
+
     #[test]
     fn test_db_readonly_location() {
         // This test only works on Unix-like systems
@@ -2007,14 +6331,23 @@ mod proptest_tests {
         "[a-zA-Z0-9 _\\-\\.!?]{0,1000}".prop_map(|s| s)
     }
 
+    // Safe strings that are never blank, for issue titles
+    fn title_string() -> impl Strategy<Value = String> {
+        "[a-zA-Z0-9_\\-\\.!?][a-zA-Z0-9 _\\-\\.!?]{0,999}"
+    }
+
     proptest! {
-        /// Any valid title should be storable and retrievable unchanged
+        /// Titles are stored trimmed; blank ones are rejected without panicking
         #[test]
         fn prop_title_roundtrip(title in safe_string()) {
             let (db, _dir) = setup_test_db();
-            let id = db.create_issue(&title, None, "medium").unwrap();
-            let issue = db.get_issue(id).unwrap().unwrap();
-            prop_assert_eq!(issue.title, title);
+            match db.create_issue(&title, None, "medium") {
+                Ok(id) => {
+                    let issue = db.get_issue(id).unwrap().unwrap();
+                    prop_assert_eq!(issue.title, title.trim());
+                }
+                Err(_) => prop_assert!(title.trim().is_empty()),
+            }
         }
 
         /// Any valid description should be storable and retrievable unchanged
@@ -2069,7 +6402,7 @@ mod proptest_tests {
 
         /// Close then reopen should leave issue open
         #[test]
-        fn prop_close_reopen_idempotent(title in safe_string()) {
+        fn prop_close_reopen_idempotent(title in title_string()) {
             let (db, _dir) = setup_test_db();
             let id = db.create_issue(&title, None, "medium").unwrap();
 
@@ -2098,6 +6431,8 @@ mod proptest_tests {
             db.add_dependency(a, b).unwrap();
             let blockers = db.get_blockers(a).unwrap();
             prop_assert!(blockers.contains(&b));
+            prop_assert!(db.get_blocking(b).unwrap().contains(&a));
+            prop_assert_eq!(db.count_dependents(b).unwrap(), 1);
         }
 
         /// Search should find issues with matching titles
@@ -2111,7 +6446,7 @@ mod proptest_tests {
             db.create_issue(&title, None, "medium").unwrap();
 
             // Search for the unique marker
-            let results = db.search_issues("unique marker").unwrap();
+            let results = db.search_issues("unique marker", &SearchOptions::default()).unwrap();
             prop_assert!(!results.is_empty());
             prop_assert!(results.iter().any(|i| i.title.contains("unique marker")));
         }
@@ -2189,7 +6524,7 @@ mod proptest_tests {
             }
 
             // Get ready issues
-            let ready = db.list_ready_issues().unwrap();
+            let ready = db.list_ready_issues(false).unwrap();
 
             // Verify: no ready issue should have open blockers
             for issue in &ready {
@@ -2208,7 +6543,7 @@ mod proptest_tests {
 
         /// Session active_issue_id should be set to NULL when issue is deleted
         #[test]
-        fn prop_session_issue_delete_cascade(title in safe_string()) {
+        fn prop_session_issue_delete_cascade(title in title_string()) {
             let (db, _dir) = setup_test_db();
 
             // Create issue and session
@@ -2245,7 +6580,7 @@ mod proptest_tests {
             db.create_issue("other content here", None, "medium").unwrap();
 
             // Search for the special characters literally
-            let results = db.search_issues("%test_").unwrap();
+            let results = db.search_issues("%test_", &SearchOptions::default()).unwrap();
 
             // Should find only the issue with literal % and _
             prop_assert!(results.iter().all(|i| i.title.contains("%test_")));
@@ -2,14 +2,18 @@ mod commands;
 mod daemon;
 mod db;
 mod models;
+mod output;
 mod utils;
 
 use anyhow::{bail, Context, Result};
 use clap::{Parser, Subcommand};
 use std::env;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
 use db::Database;
+use models::IssueRef;
+use output::OutputFormat;
 
 #[derive(Parser)]
 #[command(name = "chainlink")]
@@ -20,10 +24,36 @@ struct Cli {
     #[arg(short, long, global = true)]
     quiet: bool,
 
-    /// Output as JSON (supported by list, show, search, session status)
-    #[arg(long, global = true)]
+    /// Verbose mode: also report what each change did to the database
+    #[arg(short, long, global = true, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// Output format for read commands (see --help of each command); markdown is
+    /// accepted as human for export, and import accepts json
+    #[arg(long, global = true, value_enum)]
+    format: Option<output::OutputFormat>,
+
+    /// Output as JSON; shorthand for --format json
+    #[arg(long, global = true, conflicts_with = "format")]
     json: bool,
 
+    /// When to use colored output
+    #[arg(long, global = true, default_value = "auto", value_parser = ["auto", "always", "never"])]
+    color: String,
+
+    /// Show what a destructive command would change without saving it (delete, close-all)
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Line width for clipping titles in list, tree, blocked and ready
+    /// (defaults to the terminal's width; output that is not a terminal is not clipped)
+    #[arg(long, global = true, value_name = "COLUMNS")]
+    width: Option<usize>,
+
+    /// Database file to use (overrides CHAINLINK_DB and the .chainlink lookup)
+    #[arg(long, global = true, value_name = "PATH")]
+    db: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -38,15 +68,23 @@ enum Commands {
     },
 
     /// Create a new issue
+    #[command(visible_alias = "add")]
     Create {
         /// Issue title
-        title: String,
+        #[arg(required_unless_present = "stdin")]
+        title: Option<String>,
         /// Issue description
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with_all = ["description_file", "edit"])]
         description: Option<String>,
-        /// Priority (low, medium, high, critical)
-        #[arg(short, long, default_value = "medium")]
-        priority: String,
+        /// Read the description from a file
+        #[arg(long, value_name = "PATH", conflicts_with = "edit")]
+        description_file: Option<PathBuf>,
+        /// Write the description in $VISUAL or $EDITOR
+        #[arg(long)]
+        edit: bool,
+        /// Priority (low, medium, high, critical); defaults to CHAINLINK_DEFAULT_PRIORITY or medium
+        #[arg(short, long)]
+        priority: Option<String>,
         /// Template (bug, feature, refactor, research)
         #[arg(short, long)]
         template: Option<String>,
@@ -56,6 +94,15 @@ enum Commands {
         /// Set as current session work item
         #[arg(short, long)]
         work: bool,
+        /// Issues that block the new one (comma-separated IDs)
+        #[arg(long, value_delimiter = ',')]
+        blocked_by: Vec<IssueRef>,
+        /// Reuse an open top-level issue with the same title instead of creating a new one
+        #[arg(long)]
+        unique: bool,
+        /// Read one title per line from stdin ("> " prefix makes a subissue)
+        #[arg(long, conflicts_with_all = ["title", "description", "description_file", "edit", "template", "label", "work", "blocked_by"])]
+        stdin: bool,
     },
 
     /// Quick-create an issue and start working on it (create + label + session work)
@@ -65,9 +112,9 @@ enum Commands {
         /// Issue description
         #[arg(short, long)]
         description: Option<String>,
-        /// Priority (low, medium, high, critical)
-        #[arg(short, long, default_value = "medium")]
-        priority: String,
+        /// Priority (low, medium, high, critical); defaults to CHAINLINK_DEFAULT_PRIORITY or medium
+        #[arg(short, long)]
+        priority: Option<String>,
         /// Template (bug, feature, refactor, research)
         #[arg(short, long)]
         template: Option<String>,
@@ -79,15 +126,15 @@ enum Commands {
     /// Create a subissue under a parent issue
     Subissue {
         /// Parent issue ID
-        parent: i64,
+        parent: IssueRef,
         /// Subissue title
         title: String,
         /// Subissue description
         #[arg(short, long)]
         description: Option<String>,
-        /// Priority (low, medium, high, critical)
-        #[arg(short, long, default_value = "medium")]
-        priority: String,
+        /// Priority (low, medium, high, critical); defaults to CHAINLINK_DEFAULT_PRIORITY or medium
+        #[arg(short, long)]
+        priority: Option<String>,
         /// Add labels to the subissue
         #[arg(short, long)]
         label: Vec<String>,
@@ -98,7 +145,32 @@ enum Commands {
 
     /// List issues
     List {
-        /// Filter by status (open, closed, all)
+        /// Filter by status (open, closed, archived, all); comma-separate for several
+        #[arg(short, long, default_value = "open")]
+        status: String,
+        /// Filter by label
+        #[arg(short, long)]
+        label: Option<String>,
+        /// Filter by priority
+        #[arg(short, long)]
+        priority: Option<String>,
+        /// Filter by assignee ("none" for unassigned issues)
+        #[arg(short, long)]
+        assignee: Option<String>,
+        /// Show blocker and dependent counts for each issue
+        #[arg(long)]
+        show_deps: bool,
+        /// Show each issue's parent and mark subissues
+        #[arg(long)]
+        show_parent: bool,
+        /// Leave out the footer counting open, blocked and ready issues
+        #[arg(long)]
+        no_summary: bool,
+    },
+
+    /// Print how many issues match, and nothing else
+    Count {
+        /// Filter by status (open, closed, archived, all); comma-separate for several
         #[arg(short, long, default_value = "open")]
         status: String,
         /// Filter by label
@@ -107,24 +179,36 @@ enum Commands {
         /// Filter by priority
         #[arg(short, long)]
         priority: Option<String>,
+        /// Filter by assignee ("none" for unassigned issues)
+        #[arg(short, long)]
+        assignee: Option<String>,
     },
 
     /// Search issues by text
     Search {
         /// Search query
         query: String,
+        /// Only search descendants of this issue
+        #[arg(long)]
+        under: Option<IssueRef>,
+        /// Match only at the start of words in titles and descriptions
+        #[arg(long)]
+        prefix: bool,
+        /// Show at most this many results (title matches rank first)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
     },
 
     /// Show issue details
     Show {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
     },
 
     /// Update an issue
     Update {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
         /// New title
         #[arg(short, long)]
         title: Option<String>,
@@ -136,10 +220,26 @@ enum Commands {
         priority: Option<String>,
     },
 
+    /// Move every matching issue from one priority to another
+    Reprioritize {
+        /// Current priority
+        #[arg(long)]
+        from: String,
+        /// New priority
+        #[arg(long)]
+        to: String,
+        /// Filter by status (open, closed, archived, all); comma-separate for several
+        #[arg(short, long, default_value = "open")]
+        status: String,
+    },
+
     /// Close an issue
     Close {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
+        /// Resolution note, e.g. "fixed in abc123"
+        #[arg(long)]
+        note: Option<String>,
         /// Skip changelog entry
         #[arg(long)]
         no_changelog: bool,
@@ -156,43 +256,49 @@ enum Commands {
         /// Skip changelog entries
         #[arg(long)]
         no_changelog: bool,
+        /// Skip confirmation
+        #[arg(short, long, visible_alias = "yes")]
+        force: bool,
     },
 
     /// Reopen a closed issue
     Reopen {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
+        /// Also reopen every closed subissue beneath it
+        #[arg(short, long)]
+        recursive: bool,
     },
 
     /// Delete an issue
     Delete {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
         /// Skip confirmation
-        #[arg(short, long)]
+        #[arg(short, long, visible_alias = "yes")]
         force: bool,
     },
 
     /// Add a comment to an issue
     Comment {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
         /// Comment text
         text: String,
     },
 
     /// Add a label to an issue
     Label {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
         /// Label name
         label: String,
     },
 
     /// Remove a label from an issue
     Unlabel {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
         /// Label name
         label: String,
     },
@@ -200,61 +306,204 @@ enum Commands {
     /// Mark an issue as blocked by another
     Block {
         /// Issue ID that is blocked
-        id: i64,
+        id: IssueRef,
         /// Issue ID that is blocking
-        blocker: i64,
+        blocker: IssueRef,
     },
 
     /// Remove a blocking relationship
     Unblock {
         /// Issue ID that was blocked
-        id: i64,
+        id: IssueRef,
         /// Issue ID that was blocking
-        blocker: i64,
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        blocker: Option<IssueRef>,
+        /// Remove every blocker of the issue
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Dependency graph inspection
+    Deps {
+        #[command(subcommand)]
+        action: DepsCommands,
+    },
+
+    /// Mark an issue as a duplicate of another (does not block)
+    Dup {
+        /// Duplicate issue ID
+        id: IssueRef,
+        /// Original issue ID
+        original: IssueRef,
+    },
+
+    /// Hint that an issue should be done after another (does not block)
+    After {
+        /// Issue ID to do later
+        id: IssueRef,
+        /// Issue ID to do first
+        earlier: IssueRef,
     },
 
     /// List blocked issues
     Blocked,
 
     /// List issues ready to work on (no open blockers)
-    Ready,
+    Ready {
+        /// Leave out subissues whose parent is closed
+        #[arg(long)]
+        exclude_closed_parents: bool,
+        /// Only show issues assigned to this person ("none" for unassigned issues)
+        #[arg(short, long)]
+        assignee: Option<String>,
+    },
+
+    /// List open issues that haven't been updated recently
+    Stale {
+        /// Minimum days since the last update
+        #[arg(short, long, default_value = "30")]
+        days: i64,
+    },
+
+    /// Show issue counts by status
+    Stats {
+        /// Also show the average time from creation to final close
+        #[arg(long)]
+        cycle_time: bool,
+    },
+
+    /// List open top-level issues with no dependencies in either direction
+    Isolated,
+
+    /// List issues without subissues (the concrete tasks)
+    Leaves {
+        /// Filter by status (open, closed, archived, all); comma-separate for several
+        #[arg(short, long, default_value = "open")]
+        status: String,
+        /// Only show open leaves with no open blockers
+        #[arg(long)]
+        ready: bool,
+    },
+
+    /// Detach a subissue from its parent, keeping its own subissues
+    Promote {
+        /// Issue ID or slug
+        id: IssueRef,
+    },
+
+    /// Explain why an issue is or isn't ready to work on
+    Why {
+        /// Issue ID or slug
+        id: IssueRef,
+    },
 
     /// Link two related issues
     Relate {
         /// First issue ID
-        id: i64,
+        id: IssueRef,
         /// Second issue ID
-        related: i64,
+        related: IssueRef,
     },
 
     /// Remove a relation between issues
     Unrelate {
         /// First issue ID
-        id: i64,
+        id: IssueRef,
         /// Second issue ID
-        related: i64,
+        related: IssueRef,
     },
 
     /// List related issues
     Related {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
     },
 
     /// Suggest the next issue to work on
-    Next,
+    Next {
+        /// Show how each suggestion's score breaks down
+        #[arg(long)]
+        explain: bool,
+    },
+
+    /// List the direct subissues of an issue
+    Children {
+        /// Parent issue ID
+        id: IssueRef,
+    },
 
     /// Show issues as a tree hierarchy
     Tree {
-        /// Filter by status (open, closed, all)
+        /// Filter by status (open, closed, archived, all)
         #[arg(short, long, default_value = "all")]
         status: String,
+        /// Show each issue's estimate rolled up over its subissues
+        #[arg(long)]
+        show_estimates: bool,
+        /// Only show the subtree rooted at this issue
+        #[arg(long)]
+        root: Option<IssueRef>,
+        /// Redraw whenever the database changes (Ctrl-C to exit)
+        #[arg(long)]
+        watch: bool,
+        /// Also list blocking edges between different top-level trees
+        #[arg(long)]
+        with_deps: bool,
+        /// Only show blocked or ready issues, with their ancestors
+        #[arg(long, value_enum, default_value = "all")]
+        filter: commands::tree::TreeFilter,
+        /// Status icons and priority markers: plain text or emoji
+        #[arg(long, value_enum, default_value = "plain")]
+        style: commands::tree::TreeStyle,
+        /// Leave off the legend footer (always left off when stdout is not a terminal)
+        #[arg(long)]
+        no_legend: bool,
+    },
+
+    /// Assign an issue to someone (omit the name to unassign)
+    Assign {
+        /// Issue ID or slug
+        id: IssueRef,
+        /// Assignee name
+        name: Option<String>,
+    },
+
+    /// Give an issue a short name usable in place of its id (omit the slug to clear it)
+    Slug {
+        /// Issue ID or slug
+        id: IssueRef,
+        /// Lowercase letters, digits and hyphens, e.g. "login-bug"
+        slug: Option<String>,
+    },
+
+    /// Set an effort estimate on an issue (omit the value to clear it)
+    Estimate {
+        /// Issue ID or slug
+        id: IssueRef,
+        /// Estimate in whatever unit you plan with (points, hours, ...)
+        value: Option<i64>,
+    },
+
+    /// Link an issue to an external ticket or pull request
+    Url {
+        /// Issue ID or slug
+        id: IssueRef,
+        /// http or https URL (omit to clear)
+        url: Option<String>,
+    },
+
+    /// Set or clear an issue's due date
+    Due {
+        /// Issue ID or slug
+        id: IssueRef,
+        /// Due date as YYYY-MM-DD (omit to clear)
+        date: Option<String>,
     },
 
     /// Start a timer for an issue
     Start {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
     },
 
     /// Stop the current timer
@@ -266,20 +515,51 @@ enum Commands {
     /// Mark tests as run (resets test reminder)
     Tested,
 
+    /// List open issues that share the same title
+    Dedupe,
+
+    /// Check the database for inconsistencies
+    Doctor {
+        /// Remove dangling dependency references
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Show the database path, size, SQLite and schema versions, and issue count
+    Info,
+
     /// Export issues to JSON or markdown
     Export {
-        /// Output file path (defaults to stdout)
+        /// Output file path ("-" or omitted writes to stdout)
         #[arg(short, long)]
         output: Option<String>,
-        /// Format (json, markdown)
-        #[arg(short, long, default_value = "json")]
-        format: String,
+        /// Shorthand for --format (json, csv, markdown); defaults to json
+        #[arg(short = 'f', value_enum, value_name = "FORMAT")]
+        short_format: Option<output::OutputFormat>,
+        /// Only export this issue and its subissues (JSON and CSV only)
+        #[arg(long)]
+        root: Option<IssueRef>,
+        /// Filter by status (open, closed, archived, all); comma-separate for several
+        #[arg(short, long, default_value = "all")]
+        status: String,
+        /// Filter by label
+        #[arg(short, long)]
+        label: Option<String>,
+        /// Filter by priority
+        #[arg(short, long)]
+        priority: Option<String>,
     },
 
-    /// Import issues from JSON file
+    /// Import issues from a JSON file or stdin
     Import {
-        /// Input file path
-        input: String,
+        /// Input file path ("-" or omitted reads stdin)
+        input: Option<String>,
+        /// Read a GitHub issues JSON dump instead of a chainlink export
+        #[arg(long)]
+        github: bool,
+        /// Skip issues and dependencies that fail to import instead of rolling back
+        #[arg(long, conflicts_with = "github")]
+        continue_on_error: bool,
     },
 
     /// Archive management
@@ -294,6 +574,25 @@ enum Commands {
         action: MilestoneCommands,
     },
 
+    /// Configure priority levels
+    Priorities {
+        #[command(subcommand)]
+        action: PrioritiesCommands,
+    },
+
+    /// Saved issue templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateCommands,
+    },
+
+    /// Create an issue and its subissues from a saved template
+    New {
+        /// Template name
+        #[arg(long)]
+        from: String,
+    },
+
     /// Session management
     Session {
         #[command(subcommand)]
@@ -311,13 +610,13 @@ enum Commands {
 enum ArchiveCommands {
     /// Archive a closed issue
     Add {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
     },
     /// Unarchive an issue (restore to closed)
     Remove {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
     },
     /// List archived issues
     List,
@@ -354,14 +653,14 @@ enum MilestoneCommands {
         /// Milestone ID
         id: i64,
         /// Issue IDs to add
-        issues: Vec<i64>,
+        issues: Vec<IssueRef>,
     },
     /// Remove an issue from a milestone
     Remove {
         /// Milestone ID
         id: i64,
         /// Issue ID to remove
-        issue: i64,
+        issue: IssueRef,
     },
     /// Close a milestone
     Close {
@@ -375,6 +674,99 @@ enum MilestoneCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum DepsCommands {
+    /// List every blocking relationship
+    List,
+    /// List issues blocked since before a date
+    Stale {
+        /// Cutoff (YYYY-MM-DD or RFC 3339 timestamp)
+        #[arg(long)]
+        since: String,
+    },
+    /// Export the dependency graph in Graphviz DOT format
+    Dot {
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Add dependency edges from a DOT file (`a -> b` means a blocks b)
+    ImportDot {
+        /// DOT file, or "-" for stdin
+        input: String,
+    },
+    /// Reverse the blocking edge between two issues
+    Swap {
+        /// One end of the edge
+        a: IssueRef,
+        /// The other end of the edge
+        b: IssueRef,
+    },
+    /// List independent clusters of blocking relationships
+    Components,
+    /// Group open issues into tiers that can start once earlier tiers are done
+    Levels,
+    /// Rank open issues by how many open issues wait on them, directly or transitively
+    Bottlenecks {
+        /// Show at most this many issues
+        #[arg(short = 'n', long, default_value = "10")]
+        limit: usize,
+    },
+    /// Check whether finishing one issue would make another ready
+    Impact {
+        /// Issue you would finish
+        candidate: IssueRef,
+        /// Issue you want unblocked
+        target: IssueRef,
+    },
+}
+
+#[derive(Subcommand)]
+enum PrioritiesCommands {
+    /// Show the priority levels, most urgent first
+    List,
+    /// Replace the priority levels
+    Set {
+        /// Levels from least to most urgent
+        #[arg(required = true)]
+        levels: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplateCommands {
+    /// Save a template (replaces any template with the same name)
+    Save {
+        /// Template name
+        name: String,
+        /// Title of the parent issue
+        title: String,
+        /// Description of the parent issue
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Priority (low, medium, high, critical)
+        #[arg(short, long, default_value = "medium")]
+        priority: String,
+        /// Subissue title (repeat for each subissue)
+        #[arg(short, long = "sub")]
+        subissues: Vec<String>,
+    },
+    /// Save an existing issue and its immediate subissues as a template
+    FromIssue {
+        /// Issue ID or slug
+        id: IssueRef,
+        /// Template name
+        name: String,
+    },
+    /// List saved templates
+    List,
+    /// Delete a template
+    Delete {
+        /// Template name
+        name: String,
+    },
+}
+
 #[derive(Subcommand)]
 enum SessionCommands {
     /// Start a new session
@@ -389,8 +781,8 @@ enum SessionCommands {
     Status,
     /// Set the issue being worked on
     Work {
-        /// Issue ID
-        id: i64,
+        /// Issue ID or slug
+        id: IssueRef,
     },
     /// Show handoff notes from the previous session
     LastHandoff,
@@ -432,14 +824,133 @@ fn find_chainlink_dir() -> Result<PathBuf> {
     }
 }
 
-fn get_db() -> Result<Database> {
-    let chainlink_dir = find_chainlink_dir()?;
-    let db_path = chainlink_dir.join("issues.db");
-    Database::open(&db_path).context("Failed to open database")
+/// Pick the database file: `--db` first, then `CHAINLINK_DB`, then `.chainlink/issues.db`
+/// in the current directory or nearest parent.
+fn resolve_db_path(flag: Option<&Path>) -> Result<PathBuf> {
+    if let Some(path) = flag {
+        return Ok(path.to_path_buf());
+    }
+    if let Some(path) = env::var_os("CHAINLINK_DB").filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(find_chainlink_dir()?.join("issues.db"))
+}
+
+/// Where close writes CHANGELOG.md. With the database picked by `--db` or
+/// `CHAINLINK_DB` there may be no .chainlink at all; then the changelog is skipped.
+fn changelog_dir(db_flag: Option<&Path>, no_changelog: bool) -> Result<Option<PathBuf>> {
+    if no_changelog {
+        return Ok(None);
+    }
+    let explicit_db =
+        db_flag.is_some() || env::var_os("CHAINLINK_DB").is_some_and(|p| !p.is_empty());
+    match find_chainlink_dir() {
+        Ok(dir) => Ok(Some(dir)),
+        Err(_) if explicit_db => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn get_db(db_flag: Option<&Path>) -> Result<Database> {
+    let db_path = resolve_db_path(db_flag)?;
+    let mut db = Database::open(&db_path)
+        .with_context(|| format!("Failed to open database at {}", db_path.display()))?;
+    if let Ok(value) = env::var("CHAINLINK_MAX_DEPTH") {
+        let max_depth = value
+            .parse()
+            .with_context(|| format!("Invalid CHAINLINK_MAX_DEPTH '{}'", value))?;
+        db.set_max_depth(max_depth);
+    }
+    if let Ok(value) = env::var("CHAINLINK_MAX_DESCRIPTION_BYTES") {
+        let max_bytes = value
+            .parse()
+            .with_context(|| format!("Invalid CHAINLINK_MAX_DESCRIPTION_BYTES '{}'", value))?;
+        db.set_max_description_bytes(max_bytes);
+    }
+    if let Ok(value) = env::var("CHAINLINK_DEFAULT_PRIORITY") {
+        db.set_default_priority(&value)
+            .with_context(|| format!("Invalid CHAINLINK_DEFAULT_PRIORITY '{}'", value))?;
+    }
+    Ok(db)
+}
+
+/// Resolve several issue ids or slugs, failing on the first unknown slug.
+fn resolve_issues(db: &Database, issues: &[IssueRef]) -> Result<Vec<i64>> {
+    issues
+        .iter()
+        .map(|issue| Ok(db.resolve_issue(issue)?))
+        .collect()
+}
+
+/// Resolve an issue for a read-only command. A number that matches no issue
+/// may stand for the one issue whose id starts with it; the substitution is
+/// reported on stderr so it is never silent. Commands that change anything use
+/// `Database::resolve_issue`, which never guesses.
+fn lookup_issue(db: &Database, issue: &IssueRef) -> Result<i64> {
+    match issue {
+        IssueRef::Id(id) => {
+            let found = db.resolve_id_prefix(*id)?;
+            if found != *id {
+                eprintln!("#{} → #{}", id, found);
+            }
+            Ok(found)
+        }
+        IssueRef::Slug(_) => Ok(db.resolve_issue(issue)?),
+    }
+}
+
+/// Output formats each command accepts through `--format`.
+fn supported_formats(command: &Commands) -> &'static [OutputFormat] {
+    use OutputFormat::{Csv, Human, Json};
+    match command {
+        Commands::List { .. }
+        | Commands::Export { .. }
+        | Commands::Deps {
+            action: DepsCommands::List,
+        } => &[Human, Json, Csv],
+        Commands::Tree { watch: false, .. } | Commands::Search { .. } | Commands::Show { .. } => {
+            &[Human, Json]
+        }
+        Commands::Import { .. } => &[Json],
+        _ => &[Human],
+    }
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let out = output::Reporter::new(output::Verbosity::from_flags(cli.quiet, cli.verbose));
+
+    // Refuse rather than ignore, so a dry run can never change anything by accident
+    if cli.dry_run
+        && !matches!(
+            cli.command,
+            Commands::Delete { .. } | Commands::CloseAll { .. }
+        )
+    {
+        bail!("--dry-run is only supported by delete and close-all");
+    }
+
+    let format = if cli.json {
+        Some(OutputFormat::Json)
+    } else {
+        cli.format
+    };
+    if let Some(format) = format {
+        let supported = supported_formats(&cli.command);
+        if !supported.contains(&format) {
+            let names: Vec<&str> = supported.iter().map(|f| f.name()).collect();
+            bail!(
+                "--format {} is not supported by this command (supported: {})",
+                format.name(),
+                names.join(", ")
+            );
+        }
+    }
+
+    let title_width = cli
+        .width
+        .or_else(utils::terminal_width)
+        .map(utils::title_width);
 
     match cli.command {
         Commands::Init { force } => {
@@ -450,22 +961,49 @@ fn main() -> Result<()> {
         Commands::Create {
             title,
             description,
+            description_file,
+            edit,
             priority,
             template,
             label,
             work,
+            blocked_by,
+            unique,
+            stdin,
         } => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
+            let blocked_by = resolve_issues(&db, &blocked_by)?;
+            if stdin {
+                let count = commands::import::from_lines(
+                    &db,
+                    &out,
+                    std::io::stdin().lock(),
+                    priority.as_deref().unwrap_or(db.default_priority()),
+                    unique,
+                )?;
+                println!("Created {} issue(s)", count);
+                return Ok(());
+            }
+            let title = title.unwrap_or_default();
+            let description = if let Some(path) = description_file {
+                commands::create::description_from_file(&path)?
+            } else if edit {
+                commands::create::description_from_editor()?
+            } else {
+                description
+            };
             let opts = commands::create::CreateOpts {
                 labels: &label,
+                blocked_by: &blocked_by,
                 work,
                 quiet: cli.quiet,
+                unique,
             };
             commands::create::run(
                 &db,
                 &title,
                 description.as_deref(),
-                &priority,
+                priority.as_deref(),
                 template.as_deref(),
                 &opts,
             )
@@ -478,17 +1016,19 @@ fn main() -> Result<()> {
             template,
             label,
         } => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
             let opts = commands::create::CreateOpts {
                 labels: &label,
+                blocked_by: &[],
                 work: true,
                 quiet: cli.quiet,
+                unique: false,
             };
             commands::create::run(
                 &db,
                 &title,
                 description.as_deref(),
-                &priority,
+                priority.as_deref(),
                 template.as_deref(),
                 &opts,
             )
@@ -502,47 +1042,95 @@ fn main() -> Result<()> {
             label,
             work,
         } => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
             let opts = commands::create::CreateOpts {
                 labels: &label,
+                blocked_by: &[],
                 work,
                 quiet: cli.quiet,
+                unique: false,
             };
             commands::create::run_subissue(
                 &db,
-                parent,
+                db.resolve_issue(&parent)?,
                 &title,
                 description.as_deref(),
-                &priority,
+                priority.as_deref().unwrap_or(db.default_priority()),
                 &opts,
             )
         }
 
+        Commands::Count {
+            status,
+            label,
+            priority,
+            assignee,
+        } => {
+            let db = get_db(cli.db.as_deref())?;
+            let filter = db::IssueFilter {
+                status: Some(&status),
+                label: label.as_deref(),
+                priority: priority.as_deref(),
+                assignee: assignee.as_deref(),
+            };
+            commands::count::run(&db, &filter)
+        }
+
         Commands::List {
             status,
             label,
             priority,
+            assignee,
+            show_deps,
+            show_parent,
+            no_summary,
         } => {
-            let db = get_db()?;
-            if cli.json {
-                commands::list::run_json(&db, Some(&status), label.as_deref(), priority.as_deref())
-            } else {
-                commands::list::run(&db, Some(&status), label.as_deref(), priority.as_deref())
+            let db = get_db(cli.db.as_deref())?;
+            let filter = db::IssueFilter {
+                status: Some(&status),
+                label: label.as_deref(),
+                priority: priority.as_deref(),
+                assignee: assignee.as_deref(),
+            };
+            match format.unwrap_or_default() {
+                OutputFormat::Json => commands::list::run_json(&db, &filter),
+                OutputFormat::Csv => commands::list::run_csv(&db, &filter),
+                OutputFormat::Human => {
+                    let opts = commands::list::ListOpts {
+                        show_deps,
+                        show_parent,
+                        color: utils::color_enabled(&cli.color),
+                        title_width,
+                        summary: !no_summary,
+                    };
+                    commands::list::run(&db, &filter, &opts)
+                }
             }
         }
 
-        Commands::Search { query } => {
-            let db = get_db()?;
-            if cli.json {
-                commands::search::run_json(&db, &query)
+        Commands::Search {
+            query,
+            under,
+            prefix,
+            limit,
+        } => {
+            let db = get_db(cli.db.as_deref())?;
+            let opts = db::SearchOptions {
+                under: under.map(|r| lookup_issue(&db, &r)).transpose()?,
+                prefix,
+                limit,
+            };
+            if format == Some(OutputFormat::Json) {
+                commands::search::run_json(&db, &query, &opts)
             } else {
-                commands::search::run(&db, &query)
+                commands::search::run(&db, &query, &opts, utils::color_enabled(&cli.color))
             }
         }
 
         Commands::Show { id } => {
-            let db = get_db()?;
-            if cli.json {
+            let db = get_db(cli.db.as_deref())?;
+            let id = lookup_issue(&db, &id)?;
+            if format == Some(OutputFormat::Json) {
                 commands::show::run_json(&db, id)
             } else {
                 commands::show::run(&db, id)
@@ -555,9 +1143,11 @@ fn main() -> Result<()> {
             description,
             priority,
         } => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
             commands::update::run(
                 &db,
+                &out,
                 id,
                 title.as_deref(),
                 description.as_deref(),
@@ -565,114 +1155,293 @@ fn main() -> Result<()> {
             )
         }
 
-        Commands::Close { id, no_changelog } => {
-            let db = get_db()?;
-            let chainlink_dir = find_chainlink_dir()?;
-            if cli.quiet {
-                commands::status::close_quiet(&db, id, !no_changelog, &chainlink_dir)
-            } else {
-                commands::status::close(&db, id, !no_changelog, &chainlink_dir)
-            }
+        Commands::Reprioritize { from, to, status } => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::update::reprioritize(&db, &out, &status, &from, &to)
+        }
+
+        Commands::Close {
+            id,
+            note,
+            no_changelog,
+        } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            let changelog_dir = changelog_dir(cli.db.as_deref(), no_changelog)?;
+            commands::status::close(&db, &out, id, note.as_deref(), changelog_dir.as_deref())
         }
 
         Commands::CloseAll {
             label,
             priority,
             no_changelog,
+            force,
         } => {
-            let db = get_db()?;
-            let chainlink_dir = find_chainlink_dir()?;
+            let db = get_db(cli.db.as_deref())?;
+            // A dry run never writes the changelog, so it needs no .chainlink either
+            let changelog_dir = changelog_dir(cli.db.as_deref(), no_changelog || cli.dry_run)?;
             commands::status::close_all(
                 &db,
+                &out,
                 label.as_deref(),
                 priority.as_deref(),
-                !no_changelog,
-                &chainlink_dir,
+                changelog_dir.as_deref(),
+                force,
+                cli.dry_run,
             )
         }
 
-        Commands::Reopen { id } => {
-            let db = get_db()?;
-            commands::status::reopen(&db, id)
+        Commands::Reopen { id, recursive } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::status::reopen(&db, &out, id, recursive)
         }
 
         Commands::Delete { id, force } => {
-            let db = get_db()?;
-            commands::delete::run(&db, id, force)
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::delete::run(&db, &out, id, force, cli.dry_run)
         }
 
         Commands::Comment { id, text } => {
-            let db = get_db()?;
-            commands::comment::run(&db, id, &text)
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::comment::run(&db, &out, id, &text)
         }
 
         Commands::Label { id, label } => {
-            let db = get_db()?;
-            commands::label::add(&db, id, &label)
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::label::add(&db, &out, id, &label)
         }
 
         Commands::Unlabel { id, label } => {
-            let db = get_db()?;
-            commands::label::remove(&db, id, &label)
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::label::remove(&db, &out, id, &label)
         }
 
         Commands::Block { id, blocker } => {
-            let db = get_db()?;
-            commands::deps::block(&db, id, blocker)
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            let blocker = db.resolve_issue(&blocker)?;
+            commands::deps::block(&db, &out, id, blocker)
+        }
+
+        Commands::Unblock { id, blocker, .. } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            match blocker {
+                Some(blocker) => {
+                    commands::deps::unblock(&db, &out, id, db.resolve_issue(&blocker)?)
+                }
+                None => commands::deps::unblock_all(&db, &out, id),
+            }
+        }
+
+        Commands::Deps { action } => {
+            let db = get_db(cli.db.as_deref())?;
+            match action {
+                DepsCommands::List => commands::deps::list_all(&db, format.unwrap_or_default()),
+                DepsCommands::Stale { since } => commands::deps::list_long_blocked(&db, &since),
+                DepsCommands::Dot { output } => {
+                    commands::deps::export_dot(&db, &out, output.as_deref())
+                }
+                DepsCommands::Swap { a, b } => {
+                    commands::deps::swap(&db, &out, db.resolve_issue(&a)?, db.resolve_issue(&b)?)
+                }
+                DepsCommands::Components => commands::deps::components(&db),
+                DepsCommands::Levels => commands::deps::levels(&db),
+                DepsCommands::Bottlenecks { limit } => commands::deps::bottlenecks(&db, limit),
+                DepsCommands::Impact { candidate, target } => commands::deps::impact(
+                    &db,
+                    lookup_issue(&db, &candidate)?,
+                    lookup_issue(&db, &target)?,
+                ),
+                DepsCommands::ImportDot { input } => {
+                    let added = if input == "-" {
+                        commands::deps::import_dot(&db, std::io::stdin().lock())?
+                    } else {
+                        let file = std::fs::File::open(&input)
+                            .with_context(|| format!("Failed to open {}", input))?;
+                        commands::deps::import_dot(&db, file)?
+                    };
+                    out.info(&format!("Added {} dependency edge(s)", added));
+                    Ok(())
+                }
+            }
         }
 
-        Commands::Unblock { id, blocker } => {
-            let db = get_db()?;
-            commands::deps::unblock(&db, id, blocker)
+        Commands::Dup { id, original } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            let original = db.resolve_issue(&original)?;
+            commands::deps::dup(&db, &out, id, original)
+        }
+
+        Commands::After { id, earlier } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            let earlier = db.resolve_issue(&earlier)?;
+            commands::deps::after(&db, &out, id, earlier)
         }
 
         Commands::Blocked => {
-            let db = get_db()?;
-            commands::deps::list_blocked(&db)
+            let db = get_db(cli.db.as_deref())?;
+            commands::deps::list_blocked(&db, title_width)
+        }
+
+        Commands::Ready {
+            exclude_closed_parents,
+            assignee,
+        } => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::deps::list_ready(
+                &db,
+                exclude_closed_parents,
+                assignee.as_deref(),
+                utils::color_enabled(&cli.color),
+                title_width,
+            )
+        }
+
+        Commands::Stale { days } => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::stale::run(&db, days)
+        }
+
+        Commands::Stats { cycle_time } => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::stats::run(&db, cycle_time)
+        }
+
+        Commands::Leaves { status, ready } => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::leaves::run(&db, Some(&status), ready)
+        }
+
+        Commands::Isolated => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::isolated::run(&db)
         }
 
-        Commands::Ready => {
-            let db = get_db()?;
-            commands::deps::list_ready(&db)
+        Commands::Promote { id } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::promote::run(&db, &out, id)
+        }
+
+        Commands::Why { id } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = lookup_issue(&db, &id)?;
+            commands::why::run(&db, id)
         }
 
         Commands::Relate { id, related } => {
-            let db = get_db()?;
-            commands::relate::add(&db, id, related)
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            let related = db.resolve_issue(&related)?;
+            commands::relate::add(&db, &out, id, related)
         }
 
         Commands::Unrelate { id, related } => {
-            let db = get_db()?;
-            commands::relate::remove(&db, id, related)
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            let related = db.resolve_issue(&related)?;
+            commands::relate::remove(&db, &out, id, related)
         }
 
         Commands::Related { id } => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
+            let id = lookup_issue(&db, &id)?;
             commands::relate::list(&db, id)
         }
 
-        Commands::Next => {
-            let db = get_db()?;
-            commands::next::run(&db)
+        Commands::Next { explain } => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::next::run(&db, explain)
+        }
+
+        Commands::Children { id } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = lookup_issue(&db, &id)?;
+            commands::children::run(&db, id)
+        }
+
+        Commands::Tree {
+            status,
+            show_estimates,
+            root,
+            watch,
+            with_deps,
+            filter,
+            style,
+            no_legend,
+        } => {
+            let db = get_db(cli.db.as_deref())?;
+            let opts = commands::tree::TreeOpts {
+                show_estimates,
+                root: root.map(|r| lookup_issue(&db, &r)).transpose()?,
+                color: utils::color_enabled(&cli.color),
+                with_deps,
+                filter,
+                title_width,
+                style,
+                no_legend: no_legend || !std::io::stdout().is_terminal(),
+            };
+            if watch {
+                let db_path = resolve_db_path(cli.db.as_deref())?;
+                commands::tree::watch(&db, &db_path, Some(&status), &opts)
+            } else if format == Some(OutputFormat::Json) {
+                commands::tree::run_json(&db, Some(&status), &opts)
+            } else {
+                commands::tree::run(&db, Some(&status), &opts)
+            }
+        }
+
+        Commands::Assign { id, name } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::assign::run(&db, &out, id, name.as_deref())
+        }
+
+        Commands::Slug { id, slug } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::slug::run(&db, &out, id, slug.as_deref())
         }
 
-        Commands::Tree { status } => {
-            let db = get_db()?;
-            commands::tree::run(&db, Some(&status))
+        Commands::Estimate { id, value } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::estimate::run(&db, &out, id, value)
+        }
+
+        Commands::Url { id, url } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::url::run(&db, &out, id, url.as_deref())
+        }
+
+        Commands::Due { id, date } => {
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
+            commands::due::run(&db, &out, id, date.as_deref())
         }
 
         Commands::Start { id } => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
+            let id = db.resolve_issue(&id)?;
             commands::timer::start(&db, id)
         }
 
         Commands::Stop => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
             commands::timer::stop(&db)
         }
 
         Commands::Timer => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
             commands::timer::status(&db)
         }
 
@@ -681,57 +1450,175 @@ fn main() -> Result<()> {
             commands::tested::run(&chainlink_dir)
         }
 
-        Commands::Export { output, format } => {
-            let db = get_db()?;
-            match format.as_str() {
-                "json" => commands::export::run_json(&db, output.as_deref()),
-                "markdown" | "md" => commands::export::run_markdown(&db, output.as_deref()),
-                _ => {
-                    bail!("Unknown format '{}'. Use 'json' or 'markdown'", format);
+        Commands::Dedupe => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::dedupe::run(&db)
+        }
+
+        Commands::Doctor { fix } => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::doctor::run(&db, fix)
+        }
+
+        Commands::Info => {
+            let db = get_db(cli.db.as_deref())?;
+            let db_path = resolve_db_path(cli.db.as_deref())?;
+            commands::info::run(&db, &db_path)
+        }
+
+        Commands::Export {
+            output,
+            short_format,
+            root,
+            status,
+            label,
+            priority,
+        } => {
+            let db = get_db(cli.db.as_deref())?;
+            let root = root.map(|r| lookup_issue(&db, &r)).transpose()?;
+            let filter = db::IssueFilter {
+                status: Some(&status),
+                label: label.as_deref(),
+                priority: priority.as_deref(),
+                ..Default::default()
+            };
+            match short_format.or(format).unwrap_or(OutputFormat::Json) {
+                OutputFormat::Json => {
+                    commands::export::run_json(&db, output.as_deref(), root, &filter)
+                }
+                OutputFormat::Csv => {
+                    commands::export::run_csv(&db, output.as_deref(), root, &filter)
+                }
+                OutputFormat::Human if root.is_some() => {
+                    bail!("--root is only supported for JSON and CSV export");
+                }
+                OutputFormat::Human => {
+                    commands::export::run_markdown(&db, output.as_deref(), &filter)
                 }
             }
         }
 
-        Commands::Import { input } => {
-            let db = get_db()?;
-            let path = std::path::Path::new(&input);
-            commands::import::run_json(&db, path)
+        Commands::Import {
+            input,
+            github,
+            continue_on_error,
+        } => {
+            let db = get_db(cli.db.as_deref())?;
+            let input = input.filter(|path| path != "-");
+            if github {
+                let summary = match &input {
+                    None => commands::import::from_github(&db, std::io::stdin().lock())?,
+                    Some(input) => {
+                        let file = std::fs::File::open(input)
+                            .with_context(|| format!("Failed to open {}", input))?;
+                        commands::import::from_github(&db, std::io::BufReader::new(file))?
+                    }
+                };
+                out.info(&format!(
+                    "Imported {} issue(s) from GitHub ({} closed, {} label(s))",
+                    summary.issues, summary.closed, summary.labels
+                ));
+                if summary.skipped > 0 {
+                    out.info(&format!("Skipped {} pull request(s)", summary.skipped));
+                }
+                return Ok(());
+            }
+            match input {
+                None => commands::import::from_json(
+                    &db,
+                    std::io::stdin().lock(),
+                    "stdin",
+                    continue_on_error,
+                ),
+                Some(input) => {
+                    commands::import::run_json(&db, Path::new(&input), continue_on_error)
+                }
+            }
         }
 
         Commands::Archive { action } => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
             match action {
-                ArchiveCommands::Add { id } => commands::archive::archive(&db, id),
-                ArchiveCommands::Remove { id } => commands::archive::unarchive(&db, id),
+                ArchiveCommands::Add { id } => {
+                    commands::archive::archive(&db, &out, db.resolve_issue(&id)?)
+                }
+                ArchiveCommands::Remove { id } => {
+                    commands::archive::unarchive(&db, &out, db.resolve_issue(&id)?)
+                }
                 ArchiveCommands::List => commands::archive::list(&db),
-                ArchiveCommands::Older { days } => commands::archive::archive_older(&db, days),
+                ArchiveCommands::Older { days } => {
+                    commands::archive::archive_older(&db, &out, days)
+                }
             }
         }
 
         Commands::Milestone { action } => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
             match action {
                 MilestoneCommands::Create { name, description } => {
                     commands::milestone::create(&db, &name, description.as_deref())
                 }
                 MilestoneCommands::List { status } => commands::milestone::list(&db, Some(&status)),
                 MilestoneCommands::Show { id } => commands::milestone::show(&db, id),
-                MilestoneCommands::Add { id, issues } => commands::milestone::add(&db, id, &issues),
+                MilestoneCommands::Add { id, issues } => {
+                    commands::milestone::add(&db, id, &resolve_issues(&db, &issues)?)
+                }
                 MilestoneCommands::Remove { id, issue } => {
-                    commands::milestone::remove(&db, id, issue)
+                    commands::milestone::remove(&db, id, db.resolve_issue(&issue)?)
                 }
                 MilestoneCommands::Close { id } => commands::milestone::close(&db, id),
                 MilestoneCommands::Delete { id } => commands::milestone::delete(&db, id),
             }
         }
 
+        Commands::Priorities { action } => {
+            let db = get_db(cli.db.as_deref())?;
+            match action {
+                PrioritiesCommands::List => commands::priorities::list(&db),
+                PrioritiesCommands::Set { levels } => commands::priorities::set(&db, &out, &levels),
+            }
+        }
+
+        Commands::Template { action } => {
+            let db = get_db(cli.db.as_deref())?;
+            match action {
+                TemplateCommands::Save {
+                    name,
+                    title,
+                    description,
+                    priority,
+                    subissues,
+                } => commands::template::save(
+                    &db,
+                    &out,
+                    &name,
+                    &title,
+                    description.as_deref(),
+                    &priority,
+                    &subissues,
+                ),
+                TemplateCommands::FromIssue { id, name } => {
+                    commands::template::save_from_issue(&db, &out, db.resolve_issue(&id)?, &name)
+                }
+                TemplateCommands::List => commands::template::list(&db),
+                TemplateCommands::Delete { name } => commands::template::delete(&db, &out, &name),
+            }
+        }
+
+        Commands::New { from } => {
+            let db = get_db(cli.db.as_deref())?;
+            commands::template::instantiate(&db, &out, &from)
+        }
+
         Commands::Session { action } => {
-            let db = get_db()?;
+            let db = get_db(cli.db.as_deref())?;
             match action {
                 SessionCommands::Start => commands::session::start(&db),
                 SessionCommands::End { notes } => commands::session::end(&db, notes.as_deref()),
                 SessionCommands::Status => commands::session::status(&db),
-                SessionCommands::Work { id } => commands::session::work(&db, id),
+                SessionCommands::Work { id } => {
+                    commands::session::work(&db, db.resolve_issue(&id)?)
+                }
                 SessionCommands::LastHandoff => commands::session::last_handoff(&db),
                 SessionCommands::Action { text } => commands::session::action(&db, &text),
             }
@@ -1,5 +1,104 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthStr;
+
+use crate::utils::{pad, paint};
+
+/// Priorities accepted for issues, from least to most urgent.
+pub const VALID_PRIORITIES: [&str; 4] = ["low", "medium", "high", "critical"];
+
+/// Issue statuses, in display order.
+pub const STATUSES: [&str; 3] = ["open", "closed", "archived"];
+
+/// Kinds of issue-to-issue dependency. Only "blocks" edges affect readiness;
+/// "after" edges are soft ordering hints used when ranking ready issues.
+pub const DEPENDENCY_KINDS: [&str; 3] = ["blocks", "duplicates", "after"];
+
+/// Longest slug accepted for an issue.
+pub const MAX_SLUG_LEN: usize = 40;
+
+/// ANSI colors for priorities, most urgent first. Levels past the end share the last.
+const PRIORITY_COLORS: [&str; 4] = ["31", "33", "36", "2"];
+
+/// An ordered set of priority levels, least to most urgent. The default is
+/// `VALID_PRIORITIES`; a database may configure its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorityScale {
+    levels: Vec<String>,
+}
+
+impl PriorityScale {
+    pub fn new(levels: Vec<String>) -> Self {
+        PriorityScale { levels }
+    }
+
+    pub fn levels(&self) -> &[String] {
+        &self.levels
+    }
+
+    pub fn contains(&self, priority: &str) -> bool {
+        self.levels.iter().any(|p| p == priority)
+    }
+
+    /// Rank used when ordering issues by priority (higher = more important, 0 = unknown).
+    pub fn rank(&self, priority: &str) -> i32 {
+        self.levels
+            .iter()
+            .position(|p| p == priority)
+            .map_or(0, |pos| pos as i32 + 1)
+    }
+
+    /// Stable-sort `items` most urgent first, with unknown priorities last.
+    /// Items of equal priority keep their existing order.
+    pub fn sort_most_urgent_first<T>(&self, items: &mut [T], priority: impl Fn(&T) -> &str) {
+        items.sort_by_key(|item| std::cmp::Reverse(self.rank(priority(item))));
+    }
+
+    /// Display width of the longest level, so a priority column lines up for any scale.
+    pub fn column_width(&self) -> usize {
+        self.levels.iter().map(|p| p.width()).max().unwrap_or(0)
+    }
+
+    /// `priority` padded to `width` columns, colored by urgency when `color` is set.
+    /// Padding is applied first so escape codes never shift the column.
+    pub fn display_padded(&self, priority: &str, width: usize, color: bool) -> String {
+        let padded = pad(priority, width);
+        match self.color_code(priority) {
+            Some(code) if color => paint(&padded, code),
+            _ => padded,
+        }
+    }
+
+    /// One line naming every level in its color, most urgent first.
+    pub fn legend(&self) -> String {
+        let names: Vec<String> = self
+            .levels
+            .iter()
+            .rev()
+            .map(|p| match self.color_code(p) {
+                Some(code) => paint(p, code),
+                None => p.clone(),
+            })
+            .collect();
+        format!("Priorities: {}", names.join(" "))
+    }
+
+    fn color_code(&self, priority: &str) -> Option<&'static str> {
+        let rank = self.rank(priority) as usize;
+        if rank == 0 {
+            return None;
+        }
+        // Steps below the most urgent level
+        let step = self.levels.len() - rank;
+        Some(PRIORITY_COLORS[step.min(PRIORITY_COLORS.len() - 1)])
+    }
+}
+
+impl Default for PriorityScale {
+    fn default() -> Self {
+        PriorityScale::new(VALID_PRIORITIES.iter().map(|p| p.to_string()).collect())
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Issue {
@@ -22,6 +121,18 @@ pub struct Comment {
     pub created_at: DateTime<Utc>,
 }
 
+/// One entry in an issue's history. `kind` is "created", "status", "priority"
+/// or "parent"; the values are what the field changed from and to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Event {
+    pub id: i64,
+    pub issue_id: i64,
+    pub kind: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Session {
     pub id: i64,
@@ -42,11 +153,217 @@ pub struct Milestone {
     pub closed_at: Option<DateTime<Utc>>,
 }
 
+/// An issue together with the ids of every issue that blocks it (open or closed), ascending.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueWithBlockers {
+    pub issue: Issue,
+    pub blockers: Vec<i64>,
+}
+
+/// How a ready issue's recommendation score breaks down. The total is the sum:
+///
+/// - `priority`: 10 per priority rank (low = 10 ... critical = 40 on the default scale)
+/// - `due`: 0 without a due date, 20 when due today or overdue, otherwise `20 / (1 + days left)`
+/// - `unblocks`: 5 per open issue waiting on this one
+/// - `progress`: 5 when some but not all subissues are closed (finish what you started)
+/// - `effort`: `1 / (1 + estimate)` when estimated, so small tasks win close calls
+/// - `order`: -5 per open issue this one should come after ("after" edges)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReadyScore {
+    pub priority: f64,
+    pub due: f64,
+    pub unblocks: f64,
+    pub progress: f64,
+    pub effort: f64,
+    pub order: f64,
+}
+
+impl ReadyScore {
+    pub fn total(&self) -> f64 {
+        self.priority + self.due + self.unblocks + self.progress + self.effort + self.order
+    }
+}
+
+/// A saved issue template: a parent issue plus the titles of its subissues.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IssueTemplate {
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: String,
+    pub subissues: Vec<String>,
+}
+
+/// How a command names an issue: by numeric id or by its slug.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueRef {
+    Id(i64),
+    Slug(String),
+}
+
+impl std::str::FromStr for IssueRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(id) = s.parse() {
+            return Ok(IssueRef::Id(id));
+        }
+        check_slug(s)?;
+        Ok(IssueRef::Slug(s.to_string()))
+    }
+}
+
+/// Slugs are lowercase letters and digits in hyphen-separated words, starting
+/// with a letter so that no slug can be mistaken for an id.
+pub fn check_slug(slug: &str) -> Result<(), String> {
+    let valid = slug.len() <= MAX_SLUG_LEN
+        && slug.starts_with(|c: char| c.is_ascii_lowercase())
+        && slug.split('-').all(|word| {
+            !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid slug '{}': use up to {} lowercase letters, digits and single hyphens, starting with a letter",
+            slug, MAX_SLUG_LEN
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    #[test]
+    fn test_parse_issue_ref() {
+        assert_eq!("12".parse::<IssueRef>(), Ok(IssueRef::Id(12)));
+        assert_eq!(
+            "login-bug".parse::<IssueRef>(),
+            Ok(IssueRef::Slug("login-bug".to_string()))
+        );
+        assert_eq!(
+            "v2".parse::<IssueRef>(),
+            Ok(IssueRef::Slug("v2".to_string()))
+        );
+        for bad in [
+            "",
+            "Login",
+            "2fa",
+            "login--bug",
+            "login-",
+            "-login",
+            "login_bug",
+        ] {
+            assert!(
+                bad.parse::<IssueRef>().is_err(),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+        assert!(check_slug(&"a".repeat(MAX_SLUG_LEN)).is_ok());
+        assert!(check_slug(&"a".repeat(MAX_SLUG_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_priority_rank_order() {
+        let scale = PriorityScale::default();
+        assert_eq!(scale.rank("critical"), 4);
+        assert!(scale.rank("critical") > scale.rank("high"));
+        assert!(scale.rank("high") > scale.rank("medium"));
+        assert!(scale.rank("medium") > scale.rank("low"));
+        assert_eq!(scale.rank("bogus"), 0);
+    }
+
+    #[test]
+    fn test_rank_each_default_level() {
+        let scale = PriorityScale::default();
+        assert_eq!(scale.rank("low"), 1);
+        assert_eq!(scale.rank("medium"), 2);
+        assert_eq!(scale.rank("high"), 3);
+        assert_eq!(scale.rank("critical"), 4);
+        assert_eq!(scale.rank("urgent"), 0);
+    }
+
+    #[test]
+    fn test_sort_most_urgent_first() {
+        let mut items = vec![
+            (1, "low"),
+            (2, "bogus"),
+            (3, "critical"),
+            (4, "medium"),
+            (5, "critical"),
+            (6, "high"),
+        ];
+        PriorityScale::default().sort_most_urgent_first(&mut items, |i| i.1);
+        let ids: Vec<i32> = items.iter().map(|i| i.0).collect();
+        assert_eq!(ids, vec![3, 5, 6, 4, 1, 2]);
+    }
+
+    #[test]
+    fn test_default_scale_contains() {
+        let scale = PriorityScale::default();
+        for p in VALID_PRIORITIES {
+            assert!(scale.contains(p));
+        }
+        assert!(!scale.contains(""));
+        assert!(!scale.contains("urgent"));
+        assert!(!scale.contains("LOW")); // Case sensitive
+        assert!(!scale.contains("High"));
+        assert!(!scale.contains(" medium"));
+        assert!(!scale.contains("medium\n"));
+    }
+
+    #[test]
+    fn test_custom_scale_rank() {
+        let scale = PriorityScale::new(
+            ["p5", "p4", "p3", "p2", "p1"]
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+        );
+        assert_eq!(scale.rank("p1"), 5);
+        assert_eq!(scale.rank("p5"), 1);
+        assert_eq!(scale.rank("high"), 0);
+        assert!(!scale.contains("medium"));
+    }
+
+    #[test]
+    fn test_display_padded_constant_width() {
+        let scale = PriorityScale::default();
+        let width = scale.column_width();
+        assert_eq!(width, "critical".len());
+        for p in ["high", "medium", "low"] {
+            assert_eq!(scale.display_padded(p, width, false).width(), width);
+            // Color wraps the padded text, so the visible part is the same
+            let colored = scale.display_padded(p, width, true);
+            assert!(colored.contains(&scale.display_padded(p, width, false)));
+            assert!(colored.starts_with("\x1b["));
+        }
+        assert_eq!(
+            scale.display_padded("high", width, true),
+            "\x1b[33mhigh    \x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_custom_scale_column_and_legend() {
+        let scale = PriorityScale::new(vec!["someday".to_string(), "now-please".to_string()]);
+        assert_eq!(scale.column_width(), 10);
+        assert_eq!(scale.display_padded("someday", 10, false), "someday   ");
+        // Unknown priorities are padded but never colored
+        assert_eq!(scale.display_padded("x", 3, true), "x  ");
+        assert_eq!(
+            scale.legend(),
+            "Priorities: \x1b[31mnow-please\x1b[0m \x1b[33msomeday\x1b[0m"
+        );
+    }
+
     // ==================== Issue Tests ====================
 
     #[test]
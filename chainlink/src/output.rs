@@ -0,0 +1,137 @@
+use std::cell::RefCell;
+
+/// How much informational output commands print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only results and errors
+    Quiet,
+    #[default]
+    Normal,
+    /// Also what each command changed in the database
+    Verbose,
+}
+
+impl Verbosity {
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}
+
+/// Shape of a read command's results, chosen with the global `--format` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned text for people (markdown for export)
+    #[default]
+    #[value(alias = "markdown", alias = "md")]
+    Human,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Human => "human",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Status messages from commands ("Added label ...", "Dependency already exists").
+/// Results such as listings, ids and JSON are printed directly, not through here,
+/// except dry-run previews, which use `result` so tests can capture them.
+#[derive(Default)]
+pub struct Reporter {
+    verbosity: Verbosity,
+    /// Collects messages instead of printing them (tests only)
+    captured: Option<RefCell<String>>,
+}
+
+impl Reporter {
+    pub fn new(verbosity: Verbosity) -> Self {
+        Reporter {
+            verbosity,
+            captured: None,
+        }
+    }
+
+    /// Print a message unless running quietly.
+    pub fn info(&self, msg: &str) {
+        if self.verbosity != Verbosity::Quiet {
+            self.emit(msg);
+        }
+    }
+
+    /// Print a message even when running quietly.
+    pub fn result(&self, msg: &str) {
+        self.emit(msg);
+    }
+
+    /// Print a message only when running verbosely.
+    pub fn detail(&self, msg: &str) {
+        if self.verbosity == Verbosity::Verbose {
+            self.emit(msg);
+        }
+    }
+
+    fn emit(&self, msg: &str) {
+        match &self.captured {
+            Some(buf) => {
+                let mut buf = buf.borrow_mut();
+                buf.push_str(msg);
+                buf.push('\n');
+            }
+            None => println!("{}", msg),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn capturing(verbosity: Verbosity) -> Self {
+        Reporter {
+            verbosity,
+            captured: Some(RefCell::new(String::new())),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn output(&self) -> String {
+        self.captured
+            .as_ref()
+            .map(|buf| buf.borrow().clone())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_flags() {
+        assert_eq!(Verbosity::from_flags(false, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(true, false), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_flags(false, true), Verbosity::Verbose);
+    }
+
+    #[test]
+    fn test_levels() {
+        for (verbosity, expected) in [
+            (Verbosity::Quiet, ""),
+            (Verbosity::Normal, "info\n"),
+            (Verbosity::Verbose, "info\ndetail\n"),
+        ] {
+            let out = Reporter::capturing(verbosity);
+            out.info("info");
+            out.detail("detail");
+            out.result("result");
+            assert_eq!(out.output(), format!("{}result\n", expected));
+        }
+    }
+}
@@ -1,12 +1,178 @@
-/// Truncate a string to a maximum number of characters, adding "..." if truncated.
-/// Handles Unicode correctly by counting characters, not bytes.
-pub fn truncate(s: &str, max_chars: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_chars {
+use anyhow::Result;
+use std::io::{self, BufRead, IsTerminal, Write};
+use unicode_width::UnicodeWidthStr;
+
+/// Ask a yes/no question on stdin. Returns true immediately when `force` is set.
+pub fn confirm(prompt: &str, force: bool) -> Result<bool> {
+    if force {
+        return Ok(true);
+    }
+    confirm_with(prompt, &mut io::stdin().lock(), &mut io::stdout())
+}
+
+/// Ask a yes/no question using the given input and output.
+/// Accepts y/yes/n/no in any case; an empty answer or EOF means no.
+/// Anything else re-prompts.
+pub fn confirm_with<R: BufRead, W: Write>(
+    prompt: &str,
+    input: &mut R,
+    output: &mut W,
+) -> Result<bool> {
+    loop {
+        write!(output, "{} [y/N] ", prompt)?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(false);
+        }
+
+        match line.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => return Ok(true),
+            "n" | "no" | "" => return Ok(false),
+            _ => writeln!(output, "Please answer y or n.")?,
+        }
+    }
+}
+
+/// Resolve a `--color` setting (auto/always/never) to whether ANSI styling should be used.
+/// `auto` colors only when stdout is a terminal and `NO_COLOR` is unset.
+pub fn color_enabled(mode: &str) -> bool {
+    match mode {
+        "always" => true,
+        "never" => false,
+        _ => std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal(),
+    }
+}
+
+/// Wrap a string in ANSI bold.
+pub fn bold(s: &str) -> String {
+    paint(s, "1")
+}
+
+/// Wrap a string in the ANSI SGR style `code` (e.g. "31" for red), then reset.
+pub fn paint(s: &str, code: &str) -> String {
+    format!("\x1b[{}m{}\x1b[0m", code, s)
+}
+
+/// Escape control characters (newlines, tabs, NUL, ESC, ...) so user text stays on
+/// one line and cannot inject terminal sequences. Only for display; stored values
+/// are left as-is.
+pub fn sanitize_display(s: &str) -> String {
+    if !s.chars().any(char::is_control) {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_control() {
+            out.extend(c.escape_default());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Truncate a string to a maximum display width, adding "..." if truncated.
+/// Wide characters (CJK, most emoji) count as two columns and combining marks as none;
+/// the ellipsis counts toward the budget.
+pub fn truncate(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+
+    // Measure the whole prefix rather than summing per-char widths: emoji
+    // sequences and control characters are sized differently in context.
+    let budget = max_width.saturating_sub(3);
+    let mut truncated = String::new();
+    for c in s.chars() {
+        truncated.push(c);
+        if truncated.width() > budget {
+            truncated.pop();
+            break;
+        }
+    }
+    format!("{}...", truncated)
+}
+
+/// Pad a string with trailing spaces to the given display width.
+/// Use instead of `{:<N}`, which pads by char count and misaligns wide characters.
+pub fn pad(s: &str, width: usize) -> String {
+    let w = s.width();
+    if w >= width {
         s.to_string()
     } else {
-        let truncated: String = s.chars().take(max_chars.saturating_sub(3)).collect();
-        format!("{}...", truncated)
+        format!("{}{}", s, " ".repeat(width - w))
+    }
+}
+
+/// Title column width when the terminal width is unknown (an 80-column line).
+pub const TITLE_WIDTH: usize = 40;
+
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+/// Narrowest title budget, however narrow the terminal.
+pub const MIN_TITLE_WIDTH: usize = 10;
+
+/// Width of the terminal stdout is attached to: the size the terminal reports,
+/// else `COLUMNS`, else 80. None when stdout is not a terminal, so piped
+/// output is never clipped.
+pub fn terminal_width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    let columns = || {
+        std::env::var("COLUMNS")
+            .ok()
+            .and_then(|c| c.trim().parse().ok())
+            .filter(|&c: &usize| c > 0)
+    };
+    Some(
+        tty_columns()
+            .or_else(columns)
+            .unwrap_or(DEFAULT_TERMINAL_WIDTH),
+    )
+}
+
+/// Columns reported by the terminal on stdout, if it reports any.
+#[cfg(unix)]
+fn tty_columns() -> Option<usize> {
+    // SAFETY: TIOCGWINSZ only writes into the winsize we pass it
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0;
+    (ok && size.ws_col > 0).then_some(usize::from(size.ws_col))
+}
+
+#[cfg(not(unix))]
+fn tty_columns() -> Option<usize> {
+    None
+}
+
+/// Title budget for a line `line_width` columns wide, shared by the list, blocked,
+/// ready and tree renderers. The other columns take about the same room in each.
+pub fn title_width(line_width: usize) -> usize {
+    let other_columns = DEFAULT_TERMINAL_WIDTH - TITLE_WIDTH;
+    line_width
+        .saturating_sub(other_columns)
+        .max(MIN_TITLE_WIDTH)
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Coarse human form: days and hours, or hours and minutes under a day.
+pub fn format_duration(d: chrono::Duration) -> String {
+    if d.num_days() > 0 {
+        format!("{}d {}h", d.num_days(), d.num_hours() % 24)
+    } else if d.num_hours() > 0 {
+        format!("{}h {}m", d.num_hours(), d.num_minutes() % 60)
+    } else {
+        format!("{}m", d.num_minutes())
     }
 }
 
@@ -36,7 +202,40 @@ mod tests {
 
     #[test]
     fn test_truncate_emoji() {
-        assert_eq!(truncate("👋🌍🎉🚀🎯", 4), "👋...");
+        // Each emoji is two columns wide
+        assert_eq!(truncate("👋🌍🎉🚀🎯", 7), "👋🌍...");
+        assert_eq!(truncate("👋🌍🎉🚀🎯", 4), "...");
+    }
+
+    #[test]
+    fn test_truncate_cjk() {
+        assert_eq!(truncate("日本語のタイトル", 10), "日本語...");
+        assert_eq!(truncate("日本語のタイトル", 11), "日本語の...");
+        assert_eq!(truncate("日本語", 6), "日本語");
+    }
+
+    #[test]
+    fn test_truncate_combining_marks() {
+        // "e" + combining acute accent takes one column
+        let s = "e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}";
+        assert_eq!(truncate(s, 5), s);
+        assert_eq!(truncate(s, 4), "e\u{301}...");
+    }
+
+    #[test]
+    fn test_sanitize_display_plain() {
+        assert_eq!(sanitize_display("Fix login 日本 🎉"), "Fix login 日本 🎉");
+    }
+
+    #[test]
+    fn test_sanitize_display_escapes_controls() {
+        assert_eq!(
+            sanitize_display("line one\nline two"),
+            "line one\\nline two"
+        );
+        assert_eq!(sanitize_display("a\tb\r"), "a\\tb\\r");
+        assert_eq!(sanitize_display("nul\0byte"), "nul\\u{0}byte");
+        assert_eq!(sanitize_display("\x1b[31mred"), "\\u{1b}[31mred");
     }
 
     #[test]
@@ -48,4 +247,80 @@ mod tests {
     fn test_truncate_zero_max() {
         assert_eq!(truncate("hello", 0), "...");
     }
+
+    fn scripted_confirm(answers: &str) -> (bool, String) {
+        let mut input = answers.as_bytes();
+        let mut output = Vec::new();
+        let result = confirm_with("Proceed?", &mut input, &mut output).unwrap();
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_confirm_yes() {
+        assert!(scripted_confirm("y\n").0);
+        assert!(scripted_confirm("YES\n").0);
+    }
+
+    #[test]
+    fn test_confirm_no() {
+        assert!(!scripted_confirm("n\n").0);
+        assert!(!scripted_confirm("No\n").0);
+        assert!(!scripted_confirm("\n").0);
+    }
+
+    #[test]
+    fn test_confirm_invalid_then_yes() {
+        let (result, output) = scripted_confirm("maybe\ny\n");
+        assert!(result);
+        assert_eq!(output.matches("Proceed? [y/N]").count(), 2);
+        assert!(output.contains("Please answer y or n."));
+    }
+
+    #[test]
+    fn test_confirm_eof_is_no() {
+        assert!(!scripted_confirm("").0);
+        assert!(!scripted_confirm("what\n").0);
+    }
+
+    #[test]
+    fn test_confirm_force_skips_prompt() {
+        assert!(confirm("Proceed?", true).unwrap());
+    }
+
+    #[test]
+    fn test_color_enabled_explicit() {
+        assert!(color_enabled("always"));
+        assert!(!color_enabled("never"));
+    }
+
+    #[test]
+    fn test_bold() {
+        assert_eq!(bold("x"), "\x1b[1mx\x1b[0m");
+    }
+
+    #[test]
+    fn test_pad_ascii() {
+        assert_eq!(pad("ab", 4), "ab  ");
+        assert_eq!(pad("abcd", 2), "abcd");
+    }
+
+    #[test]
+    fn test_pad_wide_chars() {
+        assert_eq!(pad("日本", 6), "日本  ");
+        assert_eq!(pad("🎉", 3), "🎉 ");
+    }
+
+    #[test]
+    fn test_title_width() {
+        assert_eq!(title_width(80), TITLE_WIDTH);
+        assert_eq!(title_width(120), 80);
+        assert_eq!(title_width(20), MIN_TITLE_WIDTH);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(chrono::Duration::minutes(5)), "5m");
+        assert_eq!(format_duration(chrono::Duration::minutes(125)), "2h 5m");
+        assert_eq!(format_duration(chrono::Duration::hours(50)), "2d 2h");
+    }
 }
@@ -1,4 +1,5 @@
-use std::process::Command;
+use std::io::Write;
+use std::process::{Command, Stdio};
 use tempfile::tempdir;
 
 /// Helper to run chainlink commands in a temp directory
@@ -15,6 +16,34 @@ fn run_chainlink(dir: &std::path::Path, args: &[&str]) -> (bool, String, String)
     (output.status.success(), stdout, stderr)
 }
 
+/// Helper to run chainlink with `input` piped to stdin
+fn run_chainlink_with_stdin(
+    dir: &std::path::Path,
+    args: &[&str],
+    input: &str,
+) -> (bool, String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_chainlink"))
+        .current_dir(dir)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute chainlink");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    (output.status.success(), stdout, stderr)
+}
+
 /// Initialize chainlink in a temp directory
 fn init_chainlink(dir: &std::path::Path) {
     let (success, _, stderr) = run_chainlink(dir, &["init"]);
@@ -119,6 +148,29 @@ fn test_create_subissue() {
     assert!(show_out.contains("Child") || show_out.contains("subissue"));
 }
 
+#[test]
+fn test_add_stdin_creates_issue_per_line() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    let (success, stdout, stderr) = run_chainlink_with_stdin(
+        dir.path(),
+        &["add", "--stdin", "-p", "high"],
+        "Write docs\n\n> Outline\nShip release\n",
+    );
+    assert!(success, "add --stdin failed: {}", stderr);
+    assert!(stdout.contains("Created 3 issue(s)"));
+
+    let (_, tree_out, _) = run_chainlink(dir.path(), &["tree"]);
+    assert!(tree_out.contains("Write docs"));
+    assert!(tree_out.contains("Outline"));
+    assert!(tree_out.contains("Ship release"));
+
+    // A title and --stdin are mutually exclusive
+    let (success, _, _) = run_chainlink_with_stdin(dir.path(), &["add", "Title", "--stdin"], "");
+    assert!(!success);
+}
+
 // ==================== Issue Listing Tests ====================
 
 #[test]
@@ -151,6 +203,21 @@ fn test_list_issues() {
     assert!(stdout.contains("Issue 2"));
 }
 
+#[test]
+fn test_list_piped_output_is_not_clipped() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    let title = "A title well past the forty columns a list row used to allow";
+    run_chainlink(dir.path(), &["create", title]);
+    let (_, stdout, _) = run_chainlink(dir.path(), &["list"]);
+    assert!(stdout.contains(title));
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["list", "--width", "80"]);
+    assert!(!stdout.contains(title));
+    assert!(stdout.contains("..."));
+}
+
 #[test]
 fn test_list_filter_by_status() {
     let dir = tempdir().unwrap();
@@ -210,6 +277,147 @@ fn test_show_nonexistent_issue() {
     assert!(!success || stderr.contains("not found") || stderr.contains("No issue"));
 }
 
+#[test]
+fn test_commands_accept_slug() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Login fails"]);
+    run_chainlink(dir.path(), &["create", "Session expiry"]);
+    let (success, _, stderr) = run_chainlink(dir.path(), &["slug", "1", "login-bug"]);
+    assert!(success, "{}", stderr);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["show", "login-bug"]);
+    assert!(success);
+    assert!(stdout.contains("Issue #1: Login fails"));
+    assert!(stdout.contains("Slug: login-bug"));
+
+    let (success, _, _) = run_chainlink(dir.path(), &["block", "2", "login-bug"]);
+    assert!(success);
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "2"]);
+    assert!(show_out.contains("#1"));
+
+    let (success, _, _) = run_chainlink(dir.path(), &["slug", "2", "login-bug"]);
+    assert!(!success);
+    let (success, _, stderr) = run_chainlink(dir.path(), &["show", "no-such-slug"]);
+    assert!(!success);
+    assert!(stderr.contains("No issue has slug 'no-such-slug'"));
+}
+
+#[test]
+fn test_id_prefix_only_for_read_only_commands() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    for n in 1..=10 {
+        run_chainlink(dir.path(), &["create", &format!("Issue {}", n)]);
+    }
+
+    let (success, _, _) = run_chainlink(dir.path(), &["delete", "1", "--force"]);
+    assert!(success);
+    // Deleting it again must fail rather than fall through to #10
+    let (success, _, stderr) = run_chainlink(dir.path(), &["delete", "1", "--force"]);
+    assert!(!success);
+    assert!(stderr.contains("not found"), "{}", stderr);
+    let (success, _, _) = run_chainlink(dir.path(), &["show", "10"]);
+    assert!(success);
+
+    // A read-only lookup may use the prefix, and says so
+    let (success, stdout, stderr) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(success);
+    assert!(stdout.contains("Issue #10: Issue 10"));
+    assert!(stderr.contains("#1 → #10"));
+}
+
+#[test]
+fn test_export_pipes_into_import() {
+    let source = tempdir().unwrap();
+    init_chainlink(source.path());
+    run_chainlink(source.path(), &["create", "Piped issue", "-p", "high"]);
+
+    let (success, exported, _) = run_chainlink(source.path(), &["export", "-o", "-"]);
+    assert!(success);
+
+    let target = tempdir().unwrap();
+    init_chainlink(target.path());
+    let (success, _, stderr) =
+        run_chainlink_with_stdin(target.path(), &["import", "--format", "json"], &exported);
+    assert!(success, "import from stdin failed: {}", stderr);
+
+    let (_, list, _) = run_chainlink(target.path(), &["list"]);
+    assert!(list.contains("Piped issue"));
+}
+
+#[test]
+fn test_count_prints_bare_number() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    run_chainlink(dir.path(), &["create", "Urgent", "-p", "high"]);
+    run_chainlink(dir.path(), &["create", "Later", "-p", "low"]);
+
+    let (success, stdout, _) = run_chainlink(
+        dir.path(),
+        &["count", "--status", "open", "--priority", "high"],
+    );
+    assert!(success);
+    assert_eq!(stdout, "1\n");
+}
+
+#[test]
+fn test_create_unique_returns_existing_id() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    let (success, first, _) =
+        run_chainlink(dir.path(), &["-q", "create", "Nightly build", "--unique"]);
+    assert!(success);
+    let (success, second, _) =
+        run_chainlink(dir.path(), &["-q", "create", "nightly  BUILD", "--unique"]);
+    assert!(success);
+    assert_eq!(first.trim(), second.trim());
+
+    // Without --unique the default still creates a new issue
+    let (_, third, _) = run_chainlink(dir.path(), &["-q", "create", "Nightly build"]);
+    assert_ne!(first.trim(), third.trim());
+}
+
+#[test]
+fn test_create_stdin_unique_skips_existing() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Nightly build"]);
+    let (success, stdout, _) = run_chainlink_with_stdin(
+        dir.path(),
+        &["create", "--stdin", "--unique"],
+        "nightly build\nRelease notes\n",
+    );
+    assert!(success);
+    assert!(stdout.contains("already exists"));
+    assert!(stdout.contains("Created 1 issue(s)"));
+}
+
+#[test]
+fn test_quiet_silences_status_messages() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    run_chainlink(dir.path(), &["create", "Quiet issue"]);
+
+    for args in [
+        &["-q", "update", "1", "-t", "Renamed"][..],
+        &["-q", "comment", "1", "Noted"],
+        &["-q", "assign", "1", "sam"],
+        &["-q", "estimate", "1", "3"],
+        &["-q", "due", "1", "2030-01-01"],
+    ] {
+        let (success, stdout, stderr) = run_chainlink(dir.path(), args);
+        assert!(success, "{:?}: {}", args, stderr);
+        assert_eq!(stdout, "", "{:?} printed output under -q", args);
+    }
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["assign", "1"]);
+    assert!(stdout.contains("Unassigned issue #1"));
+}
+
 // ==================== Issue Update Tests ====================
 
 #[test]
@@ -271,6 +479,22 @@ fn test_reopen_issue() {
     assert!(show_out.contains("open"));
 }
 
+#[test]
+fn test_close_and_reopen_no_op() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Test issue"]);
+    let (success, stdout, _) = run_chainlink(dir.path(), &["reopen", "1"]);
+    assert!(success);
+    assert!(stdout.contains("already open"));
+
+    run_chainlink(dir.path(), &["close", "1"]);
+    let (success, stdout, _) = run_chainlink(dir.path(), &["close", "1"]);
+    assert!(success);
+    assert!(stdout.contains("already closed"));
+}
+
 // ==================== Issue Delete Tests ====================
 
 #[test]
@@ -287,6 +511,87 @@ fn test_delete_issue() {
     assert!(!list_out.contains("To delete"));
 }
 
+#[test]
+fn test_delete_issue_yes_flag() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "To delete"]);
+    let (success, stdout, _) = run_chainlink(dir.path(), &["delete", "1", "--yes"]);
+
+    assert!(success);
+    assert!(stdout.contains("Deleted issue #1"));
+}
+
+#[test]
+fn test_close_all_asks_for_confirmation() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Keep open"]);
+    let (success, stdout, _) =
+        run_chainlink_with_stdin(dir.path(), &["close-all", "--no-changelog"], "n\n");
+    assert!(success);
+    assert!(stdout.contains("Close 1 open issue(s)? [y/N]"));
+    assert!(stdout.contains("Cancelled."));
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list"]);
+    assert!(list_out.contains("Keep open"));
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["close-all", "--no-changelog", "--yes"]);
+    assert!(success);
+    assert!(stdout.contains("Closed 1 issue(s)."));
+}
+
+#[test]
+fn test_delete_dry_run() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Keep me"]);
+    let (success, stdout, _) = run_chainlink(dir.path(), &["delete", "1", "--dry-run"]);
+
+    assert!(success);
+    assert!(stdout.contains("Would delete issue #1"));
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list"]);
+    assert!(list_out.contains("Keep me"));
+}
+
+#[test]
+fn test_dry_run_rejected_for_other_commands() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Issue"]);
+    let (success, _, stderr) = run_chainlink(dir.path(), &["close", "1", "--dry-run"]);
+
+    assert!(!success);
+    assert!(stderr.contains("--dry-run is only supported"));
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list"]);
+    assert!(list_out.contains("Issue"));
+}
+
+#[test]
+fn test_list_filter_by_assignee() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Owned issue"]);
+    run_chainlink(dir.path(), &["create", "Orphan issue"]);
+    let (success, _, _) = run_chainlink(dir.path(), &["assign", "1", "alice"]);
+    assert!(success);
+
+    let (_, mine, _) = run_chainlink(dir.path(), &["list", "--assignee", "alice"]);
+    assert!(mine.contains("Owned issue"));
+    assert!(!mine.contains("Orphan issue"));
+
+    let (_, unowned, _) = run_chainlink(dir.path(), &["list", "--assignee", "none"]);
+    assert!(unowned.contains("Orphan issue"));
+    assert!(!unowned.contains("Owned issue"));
+
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(show_out.contains("Assignee: alice"));
+}
+
 // ==================== Labels Tests ====================
 
 #[test]
@@ -367,6 +672,28 @@ fn test_unblock_issue() {
     assert!(!blocked_out.contains("Blocked issue"));
 }
 
+#[test]
+fn test_unblock_all() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Blocked issue"]);
+    run_chainlink(dir.path(), &["create", "Blocker A"]);
+    run_chainlink(dir.path(), &["create", "Blocker B"]);
+    run_chainlink(dir.path(), &["block", "1", "2"]);
+    run_chainlink(dir.path(), &["block", "1", "3"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["unblock", "1", "--all"]);
+    assert!(success);
+    assert!(stdout.contains("Removed 2 blocker(s) from #1"));
+
+    // A blocker id and --all together, or neither, is a usage error
+    let (success, _, _) = run_chainlink(dir.path(), &["unblock", "1", "2", "--all"]);
+    assert!(!success);
+    let (success, _, _) = run_chainlink(dir.path(), &["unblock", "1"]);
+    assert!(!success);
+}
+
 #[test]
 fn test_ready_issues() {
     let dir = tempdir().unwrap();
@@ -497,6 +824,113 @@ fn test_invalid_priority() {
     );
 }
 
+#[test]
+fn test_default_priority_env_var() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    let chainlink = |priority: &str, args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_chainlink"))
+            .current_dir(dir.path())
+            .env("CHAINLINK_DEFAULT_PRIORITY", priority)
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = chainlink("high", &["create", "No priority given"]);
+    assert!(output.status.success());
+    let (_, stdout, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(stdout.contains("Priority: high"), "{}", stdout);
+
+    let output = chainlink("urgent", &["create", "Bad default"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("CHAINLINK_DEFAULT_PRIORITY"));
+}
+
+// ==================== Database Location Tests ====================
+
+#[test]
+fn test_chainlink_db_env_var_selects_database() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("elsewhere").join("tracker.db");
+    std::fs::create_dir_all(db_path.parent().unwrap()).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_chainlink"))
+        .current_dir(dir.path())
+        .env("CHAINLINK_DB", &db_path)
+        .args(["create", "Env tracked issue"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(db_path.exists());
+    assert!(!dir.path().join(".chainlink").exists());
+
+    // The --db flag wins over the environment variable
+    let other = dir.path().join("other.db");
+    let output = Command::new(env!("CARGO_BIN_EXE_chainlink"))
+        .current_dir(dir.path())
+        .env("CHAINLINK_DB", &db_path)
+        .args(["--db", other.to_str().unwrap(), "list"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(!stdout.contains("Env tracked issue"));
+    assert!(other.exists());
+
+    let (success, stdout, _) =
+        run_chainlink(dir.path(), &["--db", db_path.to_str().unwrap(), "list"]);
+    assert!(success);
+    assert!(stdout.contains("Env tracked issue"));
+}
+
+#[test]
+fn test_close_outside_repo_without_changelog() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("x.db");
+    let db = db_path.to_str().unwrap();
+    run_chainlink(dir.path(), &["--db", db, "create", "First"]);
+    run_chainlink(dir.path(), &["--db", db, "create", "Second"]);
+
+    let (success, _, stderr) =
+        run_chainlink(dir.path(), &["--db", db, "close", "1", "--no-changelog"]);
+    assert!(success, "{}", stderr);
+    let (success, stdout, stderr) =
+        run_chainlink(dir.path(), &["--db", db, "--dry-run", "close-all"]);
+    assert!(success, "{}", stderr);
+    assert!(stdout.contains("Would close 1 issue(s)."));
+
+    // Without a .chainlink directory the changelog is skipped
+    let (success, stdout, stderr) = run_chainlink(dir.path(), &["--db", db, "close", "2"]);
+    assert!(success, "{}", stderr);
+    assert!(stdout.contains("Closed issue #2"));
+    assert!(!dir.path().join("CHANGELOG.md").exists());
+}
+
+#[test]
+fn test_close_with_db_flag_outside_repo() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("x.db");
+    let db = db_path.to_str().unwrap();
+    run_chainlink(dir.path(), &["--db", db, "create", "Only"]);
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["--db", db, "close", "1"]);
+    assert!(success, "{}", stderr);
+    let (_, stdout, _) = run_chainlink(dir.path(), &["--db", db, "show", "1"]);
+    assert!(stdout.contains("closed"));
+    assert!(!dir.path().join("CHANGELOG.md").exists());
+
+    // Without --db, a missing .chainlink is still an error
+    let (success, _, stderr) = run_chainlink(dir.path(), &["close", "1"]);
+    assert!(!success);
+    assert!(stderr.contains("Not a chainlink repository"));
+}
+
 // ==================== Security Tests ====================
 
 #[test]
@@ -615,6 +1049,28 @@ fn test_unarchive_issue() {
     assert!(closed_list.contains("Issue to archive"));
 }
 
+#[test]
+fn test_list_archived_requires_explicit_status() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Archived issue"]);
+    run_chainlink(dir.path(), &["close", "1"]);
+    run_chainlink(dir.path(), &["archive", "add", "1"]);
+
+    let (_, closed_list, _) = run_chainlink(dir.path(), &["list", "-s", "closed"]);
+    assert!(!closed_list.contains("Archived issue"));
+
+    let (_, archived_list, _) = run_chainlink(dir.path(), &["list", "-s", "archived"]);
+    assert!(archived_list.contains("Archived issue"));
+
+    let (_, all_list, _) = run_chainlink(dir.path(), &["list", "-s", "all"]);
+    assert!(all_list.contains("Archived issue"));
+
+    let (_, tree, _) = run_chainlink(dir.path(), &["tree"]);
+    assert!(tree.contains("[-] #1"));
+}
+
 // ==================== Milestone Tests ====================
 
 #[test]
@@ -708,6 +1164,47 @@ fn test_milestone_delete() {
     assert!(!list_out.contains("v1.0") || list_out.contains("No milestones"));
 }
 
+// ==================== Template Tests ====================
+
+#[test]
+fn test_template_save_and_new_from() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    let (success, _, _) = run_chainlink(
+        dir.path(),
+        &[
+            "template",
+            "save",
+            "sprint",
+            "Sprint checklist",
+            "-s",
+            "Plan",
+            "-s",
+            "Retro",
+        ],
+    );
+    assert!(success);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["template", "list"]);
+    assert!(success);
+    assert!(stdout.contains("sprint"));
+    assert!(stdout.contains("Retro"));
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["new", "--from", "sprint"]);
+    assert!(success);
+    assert!(stdout.contains("#1"));
+
+    let (_, tree_out, _) = run_chainlink(dir.path(), &["tree"]);
+    assert!(tree_out.contains("Sprint checklist"));
+    assert!(tree_out.contains("Plan"));
+    assert!(tree_out.contains("Retro"));
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["new", "--from", "missing"]);
+    assert!(!success);
+    assert!(stderr.contains("not found"));
+}
+
 // ==================== Timer Tests ====================
 
 #[test]
@@ -829,6 +1326,22 @@ fn test_tree_command() {
     assert!(stdout.contains("Child issue"));
 }
 
+#[test]
+fn test_tree_no_legend() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    run_chainlink(dir.path(), &["create", "Parent issue"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["tree", "--no-legend"]);
+    assert!(success);
+    assert!(stdout.contains("Parent issue"));
+    assert!(!stdout.contains("Legend:"));
+
+    // Piped output leaves the legend off even without the flag
+    let (_, stdout, _) = run_chainlink(dir.path(), &["tree"]);
+    assert!(!stdout.contains("Legend:"));
+}
+
 #[test]
 fn test_tree_with_status_filter() {
     let dir = tempdir().unwrap();
@@ -846,6 +1359,56 @@ fn test_tree_with_status_filter() {
     assert!(!stdout.contains("Closed parent"));
 }
 
+#[test]
+fn test_tree_with_root() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "First epic"]);
+    run_chainlink(dir.path(), &["subissue", "1", "First child"]);
+    run_chainlink(dir.path(), &["create", "Second epic"]);
+    run_chainlink(dir.path(), &["subissue", "3", "Second child"]);
+    run_chainlink(dir.path(), &["close", "4"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["tree", "--root", "3"]);
+    assert!(success);
+    assert!(stdout.contains("Second epic"));
+    assert!(stdout.contains("Second child"));
+    assert!(!stdout.contains("First epic"));
+    assert!(!stdout.contains("First child"));
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["tree", "--root", "3", "-s", "open"]);
+    assert!(success);
+    assert!(stdout.contains("Second epic"));
+    assert!(!stdout.contains("Second child"));
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["tree", "--root", "99"]);
+    assert!(!success);
+    assert!(stderr.contains("not found"));
+}
+
+#[test]
+fn test_tree_show_estimates() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Epic"]);
+    run_chainlink(dir.path(), &["subissue", "1", "Part one"]);
+    run_chainlink(dir.path(), &["subissue", "1", "Part two"]);
+    run_chainlink(dir.path(), &["estimate", "1", "1"]);
+    run_chainlink(dir.path(), &["estimate", "2", "3"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["tree", "--show-estimates"]);
+
+    assert!(success);
+    assert!(stdout.contains("Epic (est. 4)"));
+    assert!(stdout.contains("Part one (est. 3)"));
+    assert!(stdout.contains("Part two (est. 0)"));
+
+    let (_, plain, _) = run_chainlink(dir.path(), &["tree"]);
+    assert!(!plain.contains("est."));
+}
+
 // ==================== Next Tests ====================
 
 #[test]
@@ -912,6 +1475,86 @@ fn test_export_json() {
     assert!(content.contains("Issue 2"));
 }
 
+#[test]
+fn test_export_csv_filtered() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Hot issue", "-p", "high"]);
+    run_chainlink(dir.path(), &["create", "Cold issue", "-p", "low"]);
+    run_chainlink(dir.path(), &["create", "Finished", "-p", "high"]);
+    run_chainlink(dir.path(), &["close", "3"]);
+
+    let (success, stdout, _) = run_chainlink(
+        dir.path(),
+        &[
+            "export",
+            "--status",
+            "open",
+            "--priority",
+            "high",
+            "--format",
+            "csv",
+        ],
+    );
+
+    assert!(success);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("id,title,status,priority"));
+    assert!(lines[1].starts_with("1,Hot issue,open,high"));
+}
+
+#[test]
+fn test_format_json_across_read_commands() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Epic"]);
+    run_chainlink(dir.path(), &["subissue", "1", "Child"]);
+    run_chainlink(dir.path(), &["create", "Blocker"]);
+    run_chainlink(dir.path(), &["block", "2", "3"]);
+
+    let json = |args: &[&str]| -> serde_json::Value {
+        let (success, stdout, stderr) = run_chainlink(dir.path(), args);
+        assert!(success, "{:?} failed: {}", args, stderr);
+        serde_json::from_str(&stdout).unwrap()
+    };
+
+    let list = json(&["list", "--format", "json"]);
+    assert_eq!(list.as_array().unwrap().len(), 3);
+
+    let tree = json(&["tree", "--format", "json"]);
+    let epic = tree
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["title"] == "Epic")
+        .unwrap();
+    assert_eq!(epic["children"][0]["title"], "Child");
+
+    let deps = json(&["deps", "list", "--format", "json"]);
+    assert_eq!(
+        deps,
+        serde_json::json!([{"blocked_id": 2, "blocker_id": 3, "kind": "blocks"}])
+    );
+
+    // --json stays a shorthand for --format json
+    assert_eq!(json(&["list", "--json"]), list);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["list", "--format", "csv"]);
+    assert!(success);
+    assert!(stdout.starts_with("id,status,priority,title"));
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["tree", "--format", "csv"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("--format csv is not supported"),
+        "{}",
+        stderr
+    );
+}
+
 #[test]
 fn test_export_markdown() {
     let dir = tempdir().unwrap();
@@ -1370,6 +2013,10 @@ fn test_show_with_blockers() {
 
     assert!(success);
     assert!(stdout.contains("Blocker") || stdout.contains("#2") || stdout.contains("blocked"));
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["show", "2"]);
+    assert!(success);
+    assert!(stdout.contains("Blocks: #1\n"));
 }
 
 // ==================== Additional Search Edge Cases ====================
@@ -1407,6 +2054,36 @@ fn test_search_in_description() {
     assert!(stdout.contains("Generic title") || stdout.contains("#1"));
 }
 
+#[test]
+fn test_search_under_subtree() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Epic"]);
+    run_chainlink(dir.path(), &["subissue", "1", "Inside db work"]);
+    run_chainlink(dir.path(), &["create", "Outside db work"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["search", "db", "--under", "1"]);
+
+    assert!(success);
+    assert!(stdout.contains("Inside"));
+    assert!(!stdout.contains("Outside"));
+}
+
+#[test]
+fn test_search_highlights_match_without_color() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Fix db locking"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["search", "DB", "--color", "never"]);
+
+    assert!(success);
+    assert!(stdout.contains("Fix *db* locking"));
+    assert!(!stdout.contains("\x1b["));
+}
+
 // ==================== Init Edge Cases ====================
 
 #[test]
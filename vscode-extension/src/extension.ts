@@ -380,7 +380,7 @@ function registerCommands(context: vscode.ExtensionContext): void {
             'Close All'
         );
         if (confirm !== 'Close All') { return; }
-        await executeChainlinkCommand(['close-all'], 'Closing all issues...');
+        await executeChainlinkCommand(['close-all', '--force'], 'Closing all issues...');
     });
 
     reg('chainlink.reopenIssue', async () => {